@@ -0,0 +1,14 @@
+-- Extracted text and preview thumbnails for uploaded files
+CREATE TABLE IF NOT EXISTS documents (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    file_path TEXT NOT NULL UNIQUE,
+    original_name TEXT NOT NULL,
+    extracted_text TEXT,
+    thumbnail_path TEXT,
+    search_vector TSVECTOR GENERATED ALWAYS AS (to_tsvector('simple', COALESCE(extracted_text, ''))) STORED,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_documents_user_id ON documents(user_id);
+CREATE INDEX idx_documents_search_vector ON documents USING GIN (search_vector);
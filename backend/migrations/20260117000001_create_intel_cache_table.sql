@@ -0,0 +1,6 @@
+-- Scan results keyed by normalized URL (host without www + path), shared across users
+CREATE TABLE IF NOT EXISTS intel_cache (
+    cache_key TEXT PRIMARY KEY,
+    intel JSONB NOT NULL,
+    fetched_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,5 @@
+-- Verification links expire; pending ones get a fresh window instead of dying on deploy
+ALTER TABLE users ADD COLUMN IF NOT EXISTS verification_expires_at TIMESTAMP;
+
+UPDATE users SET verification_expires_at = NOW() + INTERVAL '48 hours'
+WHERE verification_token IS NOT NULL AND verification_expires_at IS NULL;
@@ -0,0 +1,2 @@
+-- Self-service deletion: the account is purged once the grace period has passed
+ALTER TABLE users ADD COLUMN IF NOT EXISTS deletion_requested_at TIMESTAMP;
@@ -0,0 +1,2 @@
+-- New address waiting for verification; swapped into `email` by the verify link
+ALTER TABLE users ADD COLUMN IF NOT EXISTS pending_email VARCHAR(255);
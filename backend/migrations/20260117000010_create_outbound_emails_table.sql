@@ -0,0 +1,23 @@
+-- Every email goes through this queue; a background worker delivers and retries it.
+-- `user_id` is NULL for mail that isn't about an account, such as contact form messages.
+CREATE TABLE IF NOT EXISTS outbound_emails (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID REFERENCES users(id) ON DELETE CASCADE,
+    template TEXT NOT NULL,
+    recipient TEXT NOT NULL,
+    reply_to TEXT,
+    subject TEXT NOT NULL,
+    html TEXT NOT NULL,
+    text_body TEXT NOT NULL,
+    -- pending | sending | sent | dead
+    status TEXT NOT NULL DEFAULT 'pending',
+    attempts INT NOT NULL DEFAULT 0,
+    last_error TEXT,
+    next_attempt_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    sent_at TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_outbound_emails_due ON outbound_emails(next_attempt_at) WHERE status = 'pending';
+CREATE INDEX IF NOT EXISTS idx_outbound_emails_created_at ON outbound_emails(created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_outbound_emails_user_id ON outbound_emails(user_id);
@@ -0,0 +1,16 @@
+-- Messages from the public contact form; forwarding them by email is only a notification
+CREATE TABLE IF NOT EXISTS contact_messages (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name TEXT NOT NULL,
+    email TEXT NOT NULL,
+    message TEXT NOT NULL,
+    link TEXT,
+    -- Language of the sender's browser, used for replies
+    locale TEXT NOT NULL DEFAULT 'en',
+    -- unread | read | archived
+    status TEXT NOT NULL DEFAULT 'unread',
+    replied_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_contact_messages_status ON contact_messages(status, created_at DESC);
@@ -0,0 +1,6 @@
+-- Where an application came from. `source` survives deleting the contact message;
+-- `contact_message_id` links back to it while it exists.
+ALTER TABLE applications ADD COLUMN IF NOT EXISTS source TEXT;
+ALTER TABLE applications ADD COLUMN IF NOT EXISTS contact_message_id UUID REFERENCES contact_messages(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_applications_contact_message_id ON applications(contact_message_id);
@@ -0,0 +1,8 @@
+-- pending | approved | rejected. Comments posted before moderation existed stay visible.
+ALTER TABLE comments ADD COLUMN IF NOT EXISTS status TEXT NOT NULL DEFAULT 'approved';
+CREATE INDEX IF NOT EXISTS idx_comments_status ON comments(status, created_at DESC);
+
+-- auto: new comments are published at once; review: they wait for the owner
+ALTER TABLE users ADD COLUMN IF NOT EXISTS comment_moderation TEXT NOT NULL DEFAULT 'auto';
+
+ALTER TABLE applications ADD COLUMN IF NOT EXISTS comments_enabled BOOLEAN NOT NULL DEFAULT TRUE;
@@ -0,0 +1,6 @@
+-- Replies point at the top-level comment they answer; deleting it removes the thread
+ALTER TABLE comments ADD COLUMN IF NOT EXISTS parent_id UUID REFERENCES comments(id) ON DELETE CASCADE;
+CREATE INDEX IF NOT EXISTS idx_comments_parent_id ON comments(parent_id);
+
+-- Set only by the authenticated reply endpoint, never from the public form
+ALTER TABLE comments ADD COLUMN IF NOT EXISTS is_owner BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,14 @@
+-- Spam signals for public submissions. ip_hash is a keyed hash, never the address itself;
+-- content_hash fingerprints the normalized text for duplicate detection.
+ALTER TABLE comments ADD COLUMN IF NOT EXISTS ip_hash TEXT;
+ALTER TABLE comments ADD COLUMN IF NOT EXISTS content_hash TEXT;
+ALTER TABLE comments ADD COLUMN IF NOT EXISTS spam_score INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE comments ADD COLUMN IF NOT EXISTS spam_reasons TEXT[] NOT NULL DEFAULT '{}';
+CREATE INDEX IF NOT EXISTS idx_comments_content_hash ON comments(content_hash, created_at DESC);
+
+-- Suspicious messages get status 'spam' and stay out of the inbox until released
+ALTER TABLE contact_messages ADD COLUMN IF NOT EXISTS ip_hash TEXT;
+ALTER TABLE contact_messages ADD COLUMN IF NOT EXISTS content_hash TEXT;
+ALTER TABLE contact_messages ADD COLUMN IF NOT EXISTS spam_score INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE contact_messages ADD COLUMN IF NOT EXISTS spam_reasons TEXT[] NOT NULL DEFAULT '{}';
+CREATE INDEX IF NOT EXISTS idx_contact_messages_content_hash ON contact_messages(content_hash, created_at DESC);
@@ -0,0 +1,16 @@
+-- Files uploaded before the documents index only live in applications.cv_path,
+-- cover_letter_path and logo_url. Index them under the application's owner so account
+-- purges find them. Mirrored logos are shared and stay unowned; a file referenced by
+-- several accounts goes to whoever used it first.
+INSERT INTO documents (user_id, file_path, original_name, created_at)
+SELECT DISTINCT ON (path) user_id, path, regexp_replace(path, '^.*/', ''), created_at
+FROM (
+    SELECT user_id, cv_path AS path, created_at FROM applications
+    UNION ALL SELECT user_id, cover_letter_path, created_at FROM applications
+    UNION ALL SELECT user_id, logo_url, created_at FROM applications
+) referenced
+WHERE path LIKE '/uploads/%'
+  AND path NOT LIKE '/uploads/logos/%'
+  AND path NOT LIKE '%..%'
+ORDER BY path, created_at
+ON CONFLICT (file_path) DO NOTHING;
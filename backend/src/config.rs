@@ -0,0 +1,547 @@
+//! Settings read once at startup, from environment variables and an optional
+//! TOML file. Every key can be given either way; the environment wins, so a
+//! deployment can keep defaults in `config.toml` and secrets in the env.
+//!
+//! Keys are the lower-case env var names, e.g. `jwt_secret = "..."` in the file
+//! or `JWT_SECRET=...` in the environment.
+
+use crate::routes::invites::RegistrationMode;
+use crate::services::email::templates::Locale;
+use crate::services::spam::{Blocklist, DEFAULT_BLOCKED_DOMAINS, DEFAULT_BLOCKED_WORDS};
+use std::collections::HashMap;
+use std::fmt;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_CONFIG_FILE: &str = "config.toml";
+const MIN_SECRET_LENGTH: usize = 32;
+/// The example key from the setup guide; treated as "not configured"
+const PLACEHOLDER_RESEND_KEY: &str = "re_123456789";
+
+const KEYS: &[&str] = &[
+    "bind_addr",
+    "database_url",
+    "database_max_connections",
+    "jwt_secret",
+    "frontend_url",
+    "registration_mode",
+    "cookie_secure",
+    "cookie_samesite",
+    "trust_proxy",
+    "mail_backend",
+    "resend_api_key",
+    "smtp_host",
+    "smtp_port",
+    "smtp_username",
+    "smtp_password",
+    "smtp_tls",
+    "mail_dir",
+    "mail_locale",
+    "sender_email",
+    "owner_email",
+    "spam_blocked_words",
+    "spam_blocked_domains",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SameSite {
+    Strict,
+    Lax,
+    None,
+}
+
+impl SameSite {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SameSite::Strict => "Strict",
+            SameSite::Lax => "Lax",
+            SameSite::None => "None",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CookieConfig {
+    /// Off only for plain-http hosts other than localhost
+    pub secure: bool,
+    /// `None` is needed when the frontend and the API are on different sites
+    pub same_site: SameSite,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SmtpTls {
+    /// Plain text, for a local catcher such as Mailpit
+    None,
+    StartTls,
+    /// Implicit TLS from the first byte (usually port 465)
+    Tls,
+}
+
+#[derive(Debug, Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub tls: SmtpTls,
+}
+
+#[derive(Debug, Clone)]
+pub enum MailBackend {
+    /// Nothing is sent, emails are only logged
+    Log,
+    Resend {
+        api_key: String,
+    },
+    Smtp(SmtpConfig),
+    /// Each email is written to the directory as an `.eml` file
+    File {
+        dir: PathBuf,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct EmailConfig {
+    pub backend: MailBackend,
+    pub sender_email: String,
+    /// Receives contact form messages
+    pub owner_email: String,
+    /// For emails not sent in reply to a browser request, and browsers asking for neither language
+    pub locale: Locale,
+}
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub bind_addr: SocketAddr,
+    pub database_url: String,
+    pub database_max_connections: u32,
+    pub jwt_secret: String,
+    /// Origin of the admin UI, without a trailing slash. Used for CORS and email links.
+    pub frontend_url: String,
+    pub registration_mode: RegistrationMode,
+    pub cookies: CookieConfig,
+    /// Behind a reverse proxy: take the client address from the last `X-Forwarded-For`
+    /// hop instead of the TCP peer, which would be the proxy itself
+    pub trust_proxy: bool,
+    pub email: EmailConfig,
+    /// Words and domains that make a public comment or message suspicious
+    pub spam_blocklist: Blocklist,
+}
+
+/// Everything wrong with the configuration, so one restart fixes all of it.
+#[derive(Debug)]
+pub struct ConfigError(Vec<String>);
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for problem in &self.0 {
+            writeln!(f, "  - {}", problem)?;
+        }
+        Ok(())
+    }
+}
+
+impl Config {
+    /// Reads `CONFIG_FILE` (default `config.toml`, skipped if absent) and the environment.
+    pub fn load() -> Result<Config, ConfigError> {
+        let file = match std::env::var("CONFIG_FILE") {
+            Ok(path) => read_file(Path::new(&path))?,
+            Err(_) if Path::new(DEFAULT_CONFIG_FILE).exists() => {
+                read_file(Path::new(DEFAULT_CONFIG_FILE))?
+            }
+            Err(_) => HashMap::new(),
+        };
+
+        Config::from_lookup(|key| {
+            std::env::var(key.to_uppercase())
+                .ok()
+                .or_else(|| file.get(key).cloned())
+        })
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Config, ConfigError> {
+        let mut problems = Vec::new();
+        let value = |key: &str| {
+            lookup(key)
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+
+        let bind_addr = value("bind_addr")
+            .unwrap_or_else(|| "0.0.0.0:3000".to_string())
+            .parse()
+            .unwrap_or_else(|_| {
+                problems.push("BIND_ADDR must be an address like 0.0.0.0:3000".to_string());
+                SocketAddr::from(([0, 0, 0, 0], 3000))
+            });
+
+        let database_url = value("database_url").unwrap_or_default();
+        if database_url.is_empty() {
+            problems.push("DATABASE_URL is required".to_string());
+        } else if !database_url.starts_with("postgres://")
+            && !database_url.starts_with("postgresql://")
+        {
+            problems.push("DATABASE_URL must be a postgres:// URL".to_string());
+        }
+
+        let database_max_connections = match value("database_max_connections") {
+            None => 5,
+            Some(v) => match v.parse::<u32>() {
+                Ok(n) if n > 0 => n,
+                _ => {
+                    problems.push("DATABASE_MAX_CONNECTIONS must be a positive number".to_string());
+                    5
+                }
+            },
+        };
+
+        let jwt_secret = value("jwt_secret").unwrap_or_default();
+        if jwt_secret.is_empty() {
+            problems.push("JWT_SECRET is required".to_string());
+        } else if jwt_secret.len() < MIN_SECRET_LENGTH {
+            problems.push(format!(
+                "JWT_SECRET must be at least {} characters long",
+                MIN_SECRET_LENGTH
+            ));
+        }
+
+        let frontend_url = value("frontend_url")
+            .unwrap_or_else(|| "http://localhost:8080".to_string())
+            .trim_end_matches('/')
+            .to_string();
+        if !is_origin(&frontend_url) {
+            problems.push(
+                "FRONTEND_URL must be an http(s) origin such as https://example.com, without a path"
+                    .to_string(),
+            );
+        }
+
+        let registration_mode = match value("registration_mode") {
+            None => RegistrationMode::InviteOnly,
+            Some(v) => RegistrationMode::parse(&v).unwrap_or_else(|| {
+                problems.push("REGISTRATION_MODE must be open, invite or closed".to_string());
+                RegistrationMode::InviteOnly
+            }),
+        };
+
+        let secure = match value("cookie_secure").map(|v| v.to_lowercase()) {
+            None => true,
+            Some(v) => match v.as_str() {
+                "true" | "1" => true,
+                "false" | "0" => false,
+                _ => {
+                    problems.push("COOKIE_SECURE must be true or false".to_string());
+                    true
+                }
+            },
+        };
+        let same_site = match value("cookie_samesite").map(|v| v.to_lowercase()) {
+            None => SameSite::Strict,
+            Some(v) => match v.as_str() {
+                "strict" => SameSite::Strict,
+                "lax" => SameSite::Lax,
+                "none" => SameSite::None,
+                _ => {
+                    problems.push("COOKIE_SAMESITE must be Strict, Lax or None".to_string());
+                    SameSite::Strict
+                }
+            },
+        };
+        // Browsers drop SameSite=None cookies that are not Secure
+        if same_site == SameSite::None && !secure {
+            problems.push("COOKIE_SAMESITE=None requires COOKIE_SECURE=true".to_string());
+        }
+
+        let trust_proxy = match value("trust_proxy").map(|v| v.to_lowercase()) {
+            None => false,
+            Some(v) => match v.as_str() {
+                "true" | "1" => true,
+                "false" | "0" => false,
+                _ => {
+                    problems.push("TRUST_PROXY must be true or false".to_string());
+                    false
+                }
+            },
+        };
+
+        let resend_api_key = value("resend_api_key").filter(|key| key != PLACEHOLDER_RESEND_KEY);
+        let backend = match value("mail_backend").map(|v| v.to_lowercase()).as_deref() {
+            // Existing setups only have a Resend key, so that keeps working without MAIL_BACKEND
+            None if resend_api_key.is_some() => MailBackend::Resend {
+                api_key: resend_api_key.unwrap_or_default(),
+            },
+            None | Some("log") => MailBackend::Log,
+            Some("resend") => match resend_api_key {
+                Some(api_key) => MailBackend::Resend { api_key },
+                None => {
+                    problems.push("MAIL_BACKEND=resend requires RESEND_API_KEY".to_string());
+                    MailBackend::Log
+                }
+            },
+            Some("smtp") => MailBackend::Smtp(smtp_config(&value, &mut problems)),
+            Some("file") => MailBackend::File {
+                dir: PathBuf::from(value("mail_dir").unwrap_or_else(|| "mail".to_string())),
+            },
+            Some(_) => {
+                problems.push("MAIL_BACKEND must be resend, smtp, file or log".to_string());
+                MailBackend::Log
+            }
+        };
+
+        let sender_email =
+            value("sender_email").unwrap_or_else(|| "onboarding@resend.dev".to_string());
+        let owner_email = value("owner_email").unwrap_or_else(|| sender_email.clone());
+        for (key, address) in [
+            ("SENDER_EMAIL", &sender_email),
+            ("OWNER_EMAIL", &owner_email),
+        ] {
+            if address.parse::<lettre::message::Mailbox>().is_err() {
+                problems.push(format!("{} must be an email address", key));
+            }
+        }
+
+        let locale = match value("mail_locale") {
+            None => Locale::En,
+            Some(v) => Locale::parse(&v).unwrap_or_else(|| {
+                problems.push("MAIL_LOCALE must be en or fi".to_string());
+                Locale::En
+            }),
+        };
+
+        // Comma separated; setting either replaces its built-in list
+        let list = |key: &str, default: &[&str]| match value(key) {
+            Some(v) => v.split(',').map(str::to_string).collect::<Vec<_>>(),
+            None => default.iter().map(|s| s.to_string()).collect(),
+        };
+        let spam_blocklist = Blocklist::new(
+            &list("spam_blocked_words", DEFAULT_BLOCKED_WORDS),
+            &list("spam_blocked_domains", DEFAULT_BLOCKED_DOMAINS),
+        );
+
+        if !problems.is_empty() {
+            return Err(ConfigError(problems));
+        }
+
+        Ok(Config {
+            bind_addr,
+            database_url,
+            database_max_connections,
+            jwt_secret,
+            frontend_url,
+            registration_mode,
+            cookies: CookieConfig { secure, same_site },
+            trust_proxy,
+            email: EmailConfig {
+                backend,
+                sender_email,
+                owner_email,
+                locale,
+            },
+            spam_blocklist,
+        })
+    }
+}
+
+fn smtp_config(value: &impl Fn(&str) -> Option<String>, problems: &mut Vec<String>) -> SmtpConfig {
+    let host = value("smtp_host").unwrap_or_default();
+    if host.is_empty() {
+        problems.push("MAIL_BACKEND=smtp requires SMTP_HOST".to_string());
+    }
+
+    let tls = match value("smtp_tls").map(|v| v.to_lowercase()).as_deref() {
+        None | Some("starttls") => SmtpTls::StartTls,
+        Some("tls") => SmtpTls::Tls,
+        Some("none") => SmtpTls::None,
+        Some(_) => {
+            problems.push("SMTP_TLS must be starttls, tls or none".to_string());
+            SmtpTls::StartTls
+        }
+    };
+    let default_port = match tls {
+        SmtpTls::None => 25,
+        SmtpTls::StartTls => 587,
+        SmtpTls::Tls => 465,
+    };
+    let port = match value("smtp_port") {
+        None => default_port,
+        Some(v) => v.parse().unwrap_or_else(|_| {
+            problems.push("SMTP_PORT must be a port number".to_string());
+            default_port
+        }),
+    };
+
+    let username = value("smtp_username");
+    let password = value("smtp_password");
+    if username.is_some() != password.is_some() {
+        problems.push("SMTP_USERNAME and SMTP_PASSWORD must be set together".to_string());
+    }
+
+    SmtpConfig {
+        host,
+        port,
+        username,
+        password,
+        tls,
+    }
+}
+
+fn read_file(path: &Path) -> Result<HashMap<String, String>, ConfigError> {
+    let problem = |msg: String| ConfigError(vec![format!("{}: {}", path.display(), msg)]);
+
+    let text = std::fs::read_to_string(path).map_err(|e| problem(e.to_string()))?;
+    let table: toml::Table = text
+        .parse()
+        .map_err(|e: toml::de::Error| problem(e.message().to_string()))?;
+
+    let mut values = HashMap::new();
+    let mut problems = Vec::new();
+    for (key, value) in table {
+        if !KEYS.contains(&key.as_str()) {
+            problems.push(format!("{}: unknown key `{}`", path.display(), key));
+            continue;
+        }
+        let value = match value {
+            toml::Value::String(s) => s,
+            toml::Value::Integer(_) | toml::Value::Boolean(_) => value.to_string(),
+            _ => {
+                problems.push(format!(
+                    "{}: `{}` must be a string, number or boolean",
+                    path.display(),
+                    key
+                ));
+                continue;
+            }
+        };
+        values.insert(key, value);
+    }
+
+    if problems.is_empty() {
+        Ok(values)
+    } else {
+        Err(ConfigError(problems))
+    }
+}
+
+fn is_origin(value: &str) -> bool {
+    url::Url::parse(value).is_ok_and(|url| {
+        matches!(url.scheme(), "http" | "https")
+            && url.host().is_some()
+            && url.path() == "/"
+            && url.query().is_none()
+            && url.fragment().is_none()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(pairs: &[(&str, &str)]) -> Result<Config, ConfigError> {
+        let map: HashMap<String, String> = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        Config::from_lookup(|key| map.get(key).cloned())
+    }
+
+    const SECRET: &str = "0123456789abcdef0123456789abcdef";
+
+    #[test]
+    fn minimal_config_uses_defaults() {
+        let config = load(&[
+            ("database_url", "postgres://localhost/oisko"),
+            ("jwt_secret", SECRET),
+            ("resend_api_key", PLACEHOLDER_RESEND_KEY),
+        ])
+        .unwrap();
+        assert_eq!(config.bind_addr.port(), 3000);
+        assert_eq!(config.database_max_connections, 5);
+        assert_eq!(config.frontend_url, "http://localhost:8080");
+        assert_eq!(config.registration_mode, RegistrationMode::InviteOnly);
+        assert!(config.cookies.secure);
+        assert_eq!(config.cookies.same_site, SameSite::Strict);
+        assert!(!config.trust_proxy);
+        assert!(matches!(config.email.backend, MailBackend::Log));
+        assert_eq!(config.email.owner_email, config.email.sender_email);
+    }
+
+    #[test]
+    fn reports_every_problem() {
+        let err = load(&[
+            ("jwt_secret", "secret"),
+            ("frontend_url", "https://example.com/admin"),
+            ("registration_mode", "opne"),
+            ("cookie_samesite", "none"),
+            ("cookie_secure", "false"),
+        ])
+        .unwrap_err();
+        assert_eq!(err.0.len(), 5, "{}", err);
+    }
+
+    #[test]
+    fn smtp_backend_defaults_port_from_tls_mode() {
+        let config = load(&[
+            ("database_url", "postgres://localhost/oisko"),
+            ("jwt_secret", SECRET),
+            ("mail_backend", "smtp"),
+            ("smtp_host", "localhost"),
+            ("smtp_tls", "none"),
+        ])
+        .unwrap();
+        let MailBackend::Smtp(smtp) = config.email.backend else {
+            panic!("expected the smtp backend");
+        };
+        assert_eq!(smtp.port, 25);
+        assert_eq!(smtp.tls, SmtpTls::None);
+
+        let err = load(&[
+            ("database_url", "postgres://localhost/oisko"),
+            ("jwt_secret", SECRET),
+            ("mail_backend", "smtp"),
+            ("smtp_username", "mailer"),
+        ])
+        .unwrap_err();
+        assert_eq!(err.0.len(), 2, "{}", err);
+    }
+
+    #[test]
+    fn trailing_slash_is_trimmed_from_origin() {
+        let config = load(&[
+            ("database_url", "postgresql://db/oisko"),
+            ("jwt_secret", SECRET),
+            ("frontend_url", "https://admin.example.com/"),
+        ])
+        .unwrap();
+        assert_eq!(config.frontend_url, "https://admin.example.com");
+    }
+
+    #[test]
+    fn spam_blocklist_replaces_defaults() {
+        use crate::services::spam::{score, Submission};
+
+        let config = load(&[
+            ("database_url", "postgres://localhost/oisko"),
+            ("jwt_secret", SECRET),
+            ("spam_blocked_words", "cheap pills, Free Money"),
+        ])
+        .unwrap();
+        let reasons = |text| {
+            let submission = Submission {
+                fields: vec![text],
+                fill_time: Some(std::time::Duration::from_secs(30)),
+                ..Default::default()
+            };
+            score(&submission, &config.spam_blocklist, false, false).reasons
+        };
+
+        assert_eq!(
+            reasons("Get FREE money now"),
+            ["blocked word \"free money\""]
+        );
+        // The default domains still apply, the default words don't
+        assert_eq!(
+            reasons("casino night at tinyurl.com"),
+            ["blocked domain tinyurl.com"]
+        );
+    }
+}
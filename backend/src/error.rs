@@ -0,0 +1,371 @@
+//! One error type for every handler. Responses follow RFC 7807
+//! (`application/problem+json`) and carry a stable machine-readable `code`
+//! next to the human-readable `detail`, e.g.
+//!
+//! ```json
+//! { "type": "about:blank", "title": "Bad Request", "status": 400,
+//!   "code": "validation_failed", "detail": "Some fields are invalid",
+//!   "errors": [{ "field": "email", "message": "Invalid email address" }] }
+//! ```
+//!
+//! Server-side failures are logged with their source and answered with a
+//! generic detail, so internals never leak to the client.
+
+use crate::services::safe_fetch::FetchError;
+use crate::services::sessions::SessionError;
+use axum::{
+    extract::rejection::{JsonRejection, PathRejection, QueryRejection},
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldError {
+    pub field: &'static str,
+    pub message: String,
+}
+
+#[derive(Debug)]
+pub enum AppError {
+    /// 400 with one entry per invalid field
+    Validation(Vec<FieldError>),
+    BadRequest(&'static str, String),
+    Unauthorized(&'static str, String),
+    Forbidden(&'static str, String),
+    NotFound(&'static str),
+    Conflict(&'static str, String),
+    PayloadTooLarge(String),
+    UnsupportedMediaType(String),
+    TooManyRequests(Option<Duration>),
+    /// A remote site we fetch from misbehaved
+    BadGateway(String),
+    GatewayTimeout(String),
+    /// A dependency such as the email provider is not configured or down
+    ServiceUnavailable(String),
+    Database(sqlx::Error),
+    Internal(String),
+}
+
+pub type AppResult<T> = Result<T, AppError>;
+
+#[derive(Serialize)]
+struct Problem {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    title: &'static str,
+    status: u16,
+    code: &'static str,
+    detail: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    errors: Vec<FieldError>,
+}
+
+impl AppError {
+    pub fn invalid(field: &'static str, message: impl Into<String>) -> Self {
+        AppError::Validation(vec![FieldError {
+            field,
+            message: message.into(),
+        }])
+    }
+
+    pub fn bad_request(code: &'static str, detail: impl Into<String>) -> Self {
+        AppError::BadRequest(code, detail.into())
+    }
+
+    pub fn unauthorized(code: &'static str, detail: impl Into<String>) -> Self {
+        AppError::Unauthorized(code, detail.into())
+    }
+
+    pub fn forbidden(code: &'static str, detail: impl Into<String>) -> Self {
+        AppError::Forbidden(code, detail.into())
+    }
+
+    pub fn conflict(code: &'static str, detail: impl Into<String>) -> Self {
+        AppError::Conflict(code, detail.into())
+    }
+
+    pub fn internal(detail: impl Into<String>) -> Self {
+        AppError::Internal(detail.into())
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            AppError::Validation(_) | AppError::BadRequest(..) => StatusCode::BAD_REQUEST,
+            AppError::Unauthorized(..) => StatusCode::UNAUTHORIZED,
+            AppError::Forbidden(..) => StatusCode::FORBIDDEN,
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::Conflict(..) => StatusCode::CONFLICT,
+            AppError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            AppError::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            AppError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+            AppError::BadGateway(_) => StatusCode::BAD_GATEWAY,
+            AppError::GatewayTimeout(_) => StatusCode::GATEWAY_TIMEOUT,
+            AppError::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            AppError::Database(_) | AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::Validation(_) => "validation_failed",
+            AppError::BadRequest(code, _)
+            | AppError::Unauthorized(code, _)
+            | AppError::Forbidden(code, _)
+            | AppError::Conflict(code, _) => code,
+            AppError::NotFound(_) => "not_found",
+            AppError::PayloadTooLarge(_) => "payload_too_large",
+            AppError::UnsupportedMediaType(_) => "unsupported_media_type",
+            AppError::TooManyRequests(_) => "rate_limited",
+            AppError::BadGateway(_) => "upstream_failed",
+            AppError::GatewayTimeout(_) => "upstream_timeout",
+            AppError::ServiceUnavailable(_) => "service_unavailable",
+            AppError::Database(_) => "database_error",
+            AppError::Internal(_) => "internal_error",
+        }
+    }
+
+    fn detail(&self) -> String {
+        match self {
+            AppError::Validation(errors) if errors.len() == 1 => errors[0].message.clone(),
+            AppError::Validation(_) => "Some fields are invalid".to_string(),
+            AppError::BadRequest(_, detail)
+            | AppError::Unauthorized(_, detail)
+            | AppError::Forbidden(_, detail)
+            | AppError::Conflict(_, detail)
+            | AppError::PayloadTooLarge(detail)
+            | AppError::UnsupportedMediaType(detail)
+            | AppError::BadGateway(detail)
+            | AppError::GatewayTimeout(detail)
+            | AppError::ServiceUnavailable(detail) => detail.clone(),
+            AppError::NotFound(what) => format!("{} not found", what),
+            AppError::TooManyRequests(None) => "Too many attempts, try again later".to_string(),
+            AppError::TooManyRequests(Some(wait)) => {
+                let secs = wait.as_secs().max(1);
+                if secs >= 60 {
+                    format!(
+                        "Too many attempts, try again in {} minutes",
+                        secs.div_ceil(60)
+                    )
+                } else {
+                    format!("Too many attempts, try again in {} seconds", secs)
+                }
+            }
+            AppError::Database(_) => "Database error".to_string(),
+            AppError::Internal(_) => "Internal server error".to_string(),
+        }
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        match &self {
+            AppError::Database(e) => tracing::error!("Database error: {:?}", e),
+            AppError::Internal(e) => tracing::error!("Internal error: {}", e),
+            AppError::BadGateway(e)
+            | AppError::GatewayTimeout(e)
+            | AppError::ServiceUnavailable(e) => {
+                tracing::warn!("Upstream error: {}", e)
+            }
+            _ => {}
+        }
+
+        let status = self.status();
+        let retry_after = match &self {
+            AppError::TooManyRequests(Some(wait)) => Some(wait.as_secs().max(1)),
+            _ => None,
+        };
+        let problem = Problem {
+            kind: "about:blank",
+            title: status.canonical_reason().unwrap_or("Error"),
+            status: status.as_u16(),
+            code: self.code(),
+            detail: self.detail(),
+            errors: match self {
+                AppError::Validation(errors) => errors,
+                _ => Vec::new(),
+            },
+        };
+
+        let mut response = (status, Json(problem)).into_response();
+        response.headers_mut().insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/problem+json"),
+        );
+        if let Some(secs) = retry_after {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(secs));
+        }
+        response
+    }
+}
+
+impl From<sqlx::Error> for AppError {
+    fn from(e: sqlx::Error) -> Self {
+        AppError::Database(e)
+    }
+}
+
+impl From<SessionError> for AppError {
+    fn from(e: SessionError) -> Self {
+        match e {
+            SessionError::InvalidToken | SessionError::Reused => {
+                AppError::unauthorized("invalid_refresh_token", "Invalid refresh token")
+            }
+            SessionError::Database(e) => AppError::Database(e),
+            SessionError::Jwt(e) => AppError::internal(format!("Token creation failed: {}", e)),
+        }
+    }
+}
+
+impl From<FetchError> for AppError {
+    fn from(e: FetchError) -> Self {
+        let detail = e.message().to_string();
+        match e {
+            FetchError::InvalidUrl => AppError::invalid("url", detail),
+            FetchError::BlockedDestination => AppError::bad_request("blocked_destination", detail),
+            FetchError::TooManyRedirects | FetchError::Upstream(_) => AppError::BadGateway(detail),
+            FetchError::Timeout => AppError::GatewayTimeout(detail),
+        }
+    }
+}
+
+impl From<JsonRejection> for AppError {
+    fn from(e: JsonRejection) -> Self {
+        match e {
+            JsonRejection::MissingJsonContentType(_) => {
+                AppError::UnsupportedMediaType(e.body_text())
+            }
+            JsonRejection::JsonSyntaxError(_) => {
+                AppError::bad_request("malformed_json", e.body_text())
+            }
+            _ if e.status() == StatusCode::PAYLOAD_TOO_LARGE => {
+                AppError::PayloadTooLarge(e.body_text())
+            }
+            _ => AppError::bad_request("invalid_body", e.body_text()),
+        }
+    }
+}
+
+impl From<PathRejection> for AppError {
+    fn from(e: PathRejection) -> Self {
+        match e {
+            // The route and the handler disagree: our bug, not the client's
+            PathRejection::MissingPathParams(_) => AppError::internal(e.body_text()),
+            _ => AppError::bad_request("invalid_path", e.body_text()),
+        }
+    }
+}
+
+impl From<QueryRejection> for AppError {
+    fn from(e: QueryRejection) -> Self {
+        AppError::bad_request("invalid_query", e.body_text())
+    }
+}
+
+impl From<jsonwebtoken::errors::Error> for AppError {
+    fn from(e: jsonwebtoken::errors::Error) -> Self {
+        AppError::internal(format!("Token creation failed: {}", e))
+    }
+}
+
+impl From<minijinja::Error> for AppError {
+    fn from(e: minijinja::Error) -> Self {
+        AppError::internal(format!("Email template failed: {}", e))
+    }
+}
+
+impl From<argon2::password_hash::Error> for AppError {
+    fn from(e: argon2::password_hash::Error) -> Self {
+        AppError::internal(format!("Password hashing failed: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn body(error: AppError) -> (StatusCode, Option<HeaderValue>, serde_json::Value) {
+        let response = error.into_response();
+        let status = response.status();
+        let content_type = response.headers().get(header::CONTENT_TYPE).cloned();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            status,
+            content_type,
+            serde_json::from_slice(&bytes).unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn renders_problem_json() {
+        let (status, content_type, json) = body(AppError::invalid("email", "Invalid email")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(content_type.unwrap(), "application/problem+json");
+        assert_eq!(json["status"], 400);
+        assert_eq!(json["code"], "validation_failed");
+        assert_eq!(json["detail"], "Invalid email");
+        assert_eq!(json["errors"][0]["field"], "email");
+    }
+
+    #[tokio::test]
+    async fn extractor_rejections_are_problems() {
+        use crate::extract::{Json, Query};
+        use axum::extract::{FromRequest, FromRequestParts, Request};
+
+        #[derive(Debug, serde::Deserialize)]
+        struct Payload {
+            #[allow(dead_code)]
+            email: String,
+        }
+
+        let request = |body: &str| {
+            Request::builder()
+                .method("POST")
+                .uri("/?page=x")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(axum::body::Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let err = Json::<Payload>::from_request(request("{"), &())
+            .await
+            .unwrap_err();
+        let (status, content_type, json) = body(err).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(content_type.unwrap(), "application/problem+json");
+        assert_eq!(json["code"], "malformed_json");
+
+        let err = Json::<Payload>::from_request(request("{}"), &())
+            .await
+            .unwrap_err();
+        let (_, _, json) = body(err).await;
+        assert_eq!(json["code"], "invalid_body");
+
+        #[derive(Debug, serde::Deserialize)]
+        struct Page {
+            #[allow(dead_code)]
+            page: u32,
+        }
+        let (mut parts, _) = request("").into_parts();
+        let err = Query::<Page>::from_request_parts(&mut parts, &())
+            .await
+            .unwrap_err();
+        let (status, _, json) = body(err).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(json["code"], "invalid_query");
+    }
+
+    #[tokio::test]
+    async fn hides_internal_details() {
+        let (status, _, json) = body(AppError::internal("disk on fire at /var/x")).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(json["detail"], "Internal server error");
+        assert!(json.get("errors").is_none());
+    }
+}
@@ -1,4 +1,9 @@
+use crate::config::Config;
 use crate::models::event::AppEvent;
+use crate::services::email::EmailQueue;
+use crate::services::login_guard::LoginGuard;
+use crate::services::rate_limit::RateLimiter;
+use crate::services::spam::SpamFilter;
 use axum::{
     extract::FromRef,
     routing::{get, post},
@@ -8,20 +13,36 @@ use dotenvy::dotenv;
 use sqlx::postgres::PgPoolOptions;
 use std::env;
 use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::broadcast;
 use tower_http::cors::CorsLayer;
 use tower_http::services::ServeDir;
 use tower_http::trace::TraceLayer;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+mod config;
 mod db;
+mod error;
+mod extract;
 mod models;
 mod routes; // Added for Company Intel
+mod services;
 
 #[derive(Clone)]
 pub struct AppState {
     pub pool: sqlx::PgPool,
+    pub config: Arc<Config>,
+    pub emails: EmailQueue,
     pub tx: broadcast::Sender<AppEvent>,
+    pub intel_limiter: RateLimiter,
+    pub two_factor_limiter: RateLimiter,
+    /// Per-client cap on password attempts, across all accounts
+    pub login_limiter: RateLimiter,
+    pub login_guard: LoginGuard,
+    pub verification_limiter: RateLimiter,
+    /// Scores public comments and contact messages
+    pub spam: SpamFilter,
 }
 
 impl FromRef<AppState> for sqlx::PgPool {
@@ -30,6 +51,24 @@ impl FromRef<AppState> for sqlx::PgPool {
     }
 }
 
+impl FromRef<AppState> for Arc<Config> {
+    fn from_ref(state: &AppState) -> Self {
+        state.config.clone()
+    }
+}
+
+impl FromRef<AppState> for EmailQueue {
+    fn from_ref(state: &AppState) -> Self {
+        state.emails.clone()
+    }
+}
+
+impl FromRef<AppState> for SpamFilter {
+    fn from_ref(state: &AppState) -> Self {
+        state.spam.clone()
+    }
+}
+
 impl FromRef<AppState> for broadcast::Sender<AppEvent> {
     fn from_ref(state: &AppState) -> Self {
         state.tx.clone()
@@ -47,10 +86,27 @@ async fn main() {
         .with(tracing_subscriber::fmt::layer())
         .init();
 
-    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    // Fail fast on a bad configuration instead of, say, signing tokens with a default key
+    let config = match Config::load() {
+        Ok(config) => Arc::new(config),
+        Err(e) => {
+            tracing::error!("Invalid configuration:\n{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let mailer = match services::email::from_config(&config.email) {
+        Ok(mailer) => mailer,
+        Err(e) => {
+            tracing::error!("Invalid mail configuration: {}", e);
+            std::process::exit(1);
+        }
+    };
+    tracing::info!("Sending email via {}", mailer.name());
+
     let pool = PgPoolOptions::new()
-        .max_connections(5)
-        .connect(&database_url)
+        .max_connections(config.database_max_connections)
+        .connect(&config.database_url)
         .await
         .expect("Failed to connect to Postgres");
 
@@ -62,18 +118,122 @@ async fn main() {
         .await
         .expect("Failed to run migrations");
 
-    let (tx, _rx) = broadcast::channel(100);
-    let state = AppState { pool, tx };
+    services::account_deletion::spawn_purge_task(pool.clone());
+    let emails = EmailQueue::new(pool.clone(), mailer);
+    emails.spawn_worker();
 
-    let _ = env::var("JWT_SECRET").expect("JWT_SECRET must be set for signal encryption");
+    let (tx, _rx) = broadcast::channel(100);
+    let state = AppState {
+        pool,
+        config: config.clone(),
+        emails,
+        tx,
+        intel_limiter: RateLimiter::new(10, Duration::from_secs(60)),
+        two_factor_limiter: RateLimiter::new(5, Duration::from_secs(5 * 60)),
+        login_limiter: RateLimiter::new(30, Duration::from_secs(15 * 60)),
+        login_guard: LoginGuard::new(),
+        verification_limiter: RateLimiter::new(3, Duration::from_secs(15 * 60)),
+        spam: SpamFilter::new(&config),
+    };
 
     use axum::extract::DefaultBodyLimit;
 
+    let allowed_origin: axum::http::HeaderValue = config
+        .frontend_url
+        .parse()
+        .expect("FRONTEND_URL is validated at startup");
+
     let app = Router::new()
         .route("/api/auth/login", post(routes::auth::login))
         .route("/api/auth/register", post(routes::auth::register))
         .route("/api/auth/verify", post(routes::auth::verify_email))
+        .route(
+            "/api/auth/resend-verification",
+            post(routes::auth::resend_verification),
+        )
+        .route(
+            "/api/auth/registration",
+            get(routes::invites::get_registration_mode),
+        )
+        .route(
+            "/api/invites",
+            get(routes::invites::list_invites).post(routes::invites::create_invite),
+        )
+        .route(
+            "/api/invites/:id",
+            axum::routing::delete(routes::invites::delete_invite),
+        )
+        .route(
+            "/api/auth/login/2fa",
+            post(routes::two_factor::login_second_step),
+        )
+        .route("/api/auth/2fa", get(routes::two_factor::get_status))
+        .route("/api/auth/2fa/setup", post(routes::two_factor::setup))
+        .route("/api/auth/2fa/confirm", post(routes::two_factor::confirm))
+        .route("/api/auth/2fa/disable", post(routes::two_factor::disable))
+        .route("/api/auth/forgot", post(routes::auth::forgot_password))
+        .route("/api/auth/reset", post(routes::auth::reset_password))
+        .route("/api/auth/refresh", post(routes::auth::refresh))
+        .route("/api/auth/logout", post(routes::auth::logout))
+        .route(
+            "/api/auth/sessions",
+            get(routes::sessions::list_sessions).delete(routes::sessions::revoke_all_sessions),
+        )
+        .route(
+            "/api/auth/sessions/:id",
+            axum::routing::delete(routes::sessions::revoke_session),
+        )
+        .route("/api/account", get(routes::account::get_account))
+        .route("/api/account/export", get(routes::account::export_data))
+        .route(
+            "/api/account/password",
+            post(routes::account::change_password),
+        )
+        .route("/api/account/email", post(routes::account::change_email))
+        .route(
+            "/api/account/delete",
+            post(routes::account::request_deletion),
+        )
+        .route(
+            "/api/account/delete/cancel",
+            post(routes::account::cancel_deletion),
+        )
+        .route(
+            "/api/tokens",
+            get(routes::api_tokens::list_tokens).post(routes::api_tokens::create_token),
+        )
+        .route(
+            "/api/tokens/:id",
+            axum::routing::delete(routes::api_tokens::revoke_token),
+        )
+        .route(
+            "/api/email/templates",
+            get(routes::email_templates::list_templates),
+        )
+        .route(
+            "/api/email/templates/:name/preview",
+            get(routes::email_templates::preview_template),
+        )
+        .route("/api/emails", get(routes::outbound_emails::list_emails))
+        .route(
+            "/api/emails/:id/resend",
+            post(routes::outbound_emails::resend_email),
+        )
         .route("/api/contact", post(routes::contact::send_contact_email))
+        .route("/api/contact/messages", get(routes::contact::list_messages))
+        .route(
+            "/api/contact/messages/:id",
+            axum::routing::put(routes::contact::update_message)
+                .delete(routes::contact::delete_message),
+        )
+        .route(
+            "/api/contact/messages/:id/reply",
+            post(routes::contact::reply_to_message),
+        )
+        .route(
+            "/api/contact/messages/:id/lead",
+            post(routes::contact::create_lead),
+        )
         .route("/api/visit", post(routes::visitor::record_visit))
         .route(
             "/api/applications",
@@ -106,15 +266,42 @@ async fn main() {
             "/api/comments/recent",
             get(routes::applications::get_recent_comments),
         )
+        .route("/api/comments", get(routes::comments::list_comments))
+        .route(
+            "/api/comments/settings",
+            get(routes::comments::get_settings).put(routes::comments::update_settings),
+        )
+        .route(
+            "/api/comments/:id",
+            axum::routing::delete(routes::comments::delete_comment),
+        )
+        .route(
+            "/api/comments/:id/approve",
+            post(routes::comments::approve_comment),
+        )
+        .route(
+            "/api/comments/:id/hide",
+            post(routes::comments::hide_comment),
+        )
+        .route(
+            "/api/comments/:id/reply",
+            post(routes::comments::reply_to_comment),
+        )
         .route("/api/intel", get(routes::intel::get_company_intel))
         .route("/api/events", get(routes::applications::sse_handler))
         .route("/api/upload", post(routes::upload::upload_file))
+        .route(
+            "/api/documents/search",
+            get(routes::documents::search_documents),
+        )
         .nest_service("/uploads", ServeDir::new("uploads"))
         .layer(TraceLayer::new_for_http())
         .layer(DefaultBodyLimit::max(10 * 1024 * 1024)) // Increase limit to 10MB
         .layer(
+            // Session cookies make requests credentialed, which rules out a wildcard origin
             CorsLayer::new()
-                .allow_origin(tower_http::cors::Any) // Replace with specific domains in production
+                .allow_origin(allowed_origin)
+                .allow_credentials(true)
                 .allow_methods([
                     axum::http::Method::GET,
                     axum::http::Method::POST,
@@ -124,11 +311,12 @@ async fn main() {
                 .allow_headers([
                     axum::http::header::AUTHORIZATION,
                     axum::http::header::CONTENT_TYPE,
+                    axum::http::HeaderName::from_static(services::cookies::CSRF_HEADER),
                 ]),
         )
         .with_state(state);
 
-    let addr = SocketAddr::from(([0, 0, 0, 0], 3000));
+    let addr = config.bind_addr;
     tracing::info!("listening on {}", addr);
     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
     axum::serve(
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct ApiToken {
+    pub id: Uuid,
+    pub name: String,
+    pub token_hint: String,
+    pub scopes: Vec<String>,
+    pub created_at: chrono::NaiveDateTime,
+    pub last_used_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateApiToken {
+    pub name: String,
+    pub scopes: Vec<String>,
+}
+
+/// Returned once on creation; the plain token is never retrievable again.
+#[derive(Debug, Serialize)]
+pub struct CreatedApiToken {
+    pub id: Uuid,
+    pub name: String,
+    pub token: String,
+    pub scopes: Vec<String>,
+}
@@ -3,6 +3,13 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 
+/// Status of an application that is only an opportunity so far, e.g. a
+/// recruiter's message. Leads are private and never listed publicly.
+pub const LEAD_STATUS: &str = "Lead";
+
+/// `source` of applications created from a contact form message
+pub const SOURCE_CONTACT_FORM: &str = "contact_form";
+
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct Application {
     pub id: Uuid,
@@ -19,8 +26,17 @@ pub struct Application {
     pub cover_letter_path: Option<String>,
     pub logo_url: Option<String>,
     pub description: Option<String>,
+    /// How the application came in, if not typed in by hand
+    pub source: Option<String>,
+    pub contact_message_id: Option<Uuid>,
+    /// Visitors can't comment when false
+    pub comments_enabled: bool,
     #[sqlx(default)]
     pub comment_count: Option<i64>,
+    #[sqlx(default)]
+    pub cv_thumbnail_url: Option<String>,
+    #[sqlx(default)]
+    pub cover_letter_thumbnail_url: Option<String>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
 }
@@ -73,10 +89,15 @@ pub struct UpdateApplication {
     pub cover_letter_path: Option<String>,
     pub logo_url: Option<String>,
     pub description: Option<String>,
+    pub comments_enabled: Option<bool>,
 }
 
+/// What a new comment needs to know about the application it is posted on.
 #[derive(Debug, FromRow)]
 pub struct AppInfo {
     pub company: String,
     pub role: String,
+    pub comments_enabled: bool,
+    /// The owner's `ModerationMode`
+    pub comment_moderation: String,
 }
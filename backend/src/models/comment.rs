@@ -3,12 +3,69 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl CommentStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CommentStatus::Pending => "pending",
+            CommentStatus::Approved => "approved",
+            CommentStatus::Rejected => "rejected",
+        }
+    }
+}
+
+/// Per-account choice of what happens to a new visitor comment.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModerationMode {
+    /// Published immediately
+    Auto,
+    /// Held as pending until the owner approves it
+    Review,
+}
+
+impl ModerationMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ModerationMode::Auto => "auto",
+            ModerationMode::Review => "review",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "auto" => Some(ModerationMode::Auto),
+            "review" => Some(ModerationMode::Review),
+            _ => None,
+        }
+    }
+
+    pub fn initial_status(self) -> CommentStatus {
+        match self {
+            ModerationMode::Auto => CommentStatus::Approved,
+            ModerationMode::Review => CommentStatus::Pending,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct Comment {
     pub id: Uuid,
     pub application_id: Uuid,
     pub visitor_name: String,
     pub content: String,
+    pub status: String,
+    /// The top-level comment this replies to
+    pub parent_id: Option<Uuid>,
+    /// Posted by the signed-in owner, shown with a verified badge
+    pub is_owner: bool,
     pub created_at: NaiveDateTime,
 }
 
@@ -16,17 +73,42 @@ pub struct Comment {
 pub struct CreateComment {
     pub visitor_name: String,
     pub content: String,
+    pub parent_id: Option<Uuid>,
+    /// Milliseconds between the page showing the form and sending it
+    pub fill_time_ms: Option<u64>,
     // Honeypot field
     pub bot_field: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct OwnerReply {
+    pub content: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct CommentWithContext {
     pub id: Uuid,
     pub application_id: Uuid,
     pub visitor_name: String,
     pub content: String,
+    pub status: String,
+    pub parent_id: Option<Uuid>,
+    pub is_owner: bool,
+    /// See `services::spam`; reasons are empty for comments that looked fine
+    pub spam_score: i32,
+    pub spam_reasons: Vec<String>,
     pub created_at: NaiveDateTime,
     pub company: String,
     pub role: String,
 }
+
+#[derive(Debug, Deserialize)]
+pub struct ModerationQuery {
+    /// Defaults to pending, the moderation queue
+    pub status: Option<CommentStatus>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ModerationSettings {
+    pub mode: ModerationMode,
+}
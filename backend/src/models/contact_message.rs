@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageStatus {
+    Unread,
+    Read,
+    Archived,
+    /// Held by the spam filter; setting another status releases it
+    Spam,
+}
+
+impl MessageStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MessageStatus::Unread => "unread",
+            MessageStatus::Read => "read",
+            MessageStatus::Archived => "archived",
+            MessageStatus::Spam => "spam",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct ContactMessage {
+    pub id: Uuid,
+    pub name: String,
+    pub email: String,
+    pub message: String,
+    pub link: Option<String>,
+    pub locale: String,
+    pub status: String,
+    pub replied_at: Option<chrono::NaiveDateTime>,
+    /// See `services::spam`
+    pub spam_score: i32,
+    pub spam_reasons: Vec<String>,
+    pub created_at: chrono::NaiveDateTime,
+    /// Lead created from this message, if any
+    #[sqlx(default)]
+    pub application_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContactMessageQuery {
+    /// Without a status, everything except archived and spam messages
+    pub status: Option<MessageStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateContactMessage {
+    pub status: MessageStatus,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContactReply {
+    pub message: String,
+}
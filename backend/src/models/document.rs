@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct DocumentSearchQuery {
+    pub q: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct DocumentSearchHit {
+    pub file_path: String,
+    pub original_name: String,
+    pub thumbnail_path: Option<String>,
+    pub snippet: String,
+    pub application_id: Option<Uuid>,
+    pub company: Option<String>,
+    pub role: Option<String>,
+}
@@ -16,4 +16,14 @@ pub enum AppEvent {
         company: String,
         status: String,
     },
+    /// The owner answered a comment. The reply itself is public, so the page can show it.
+    CommentReplied {
+        id: Uuid,
+        application_id: Uuid,
+        parent_id: Uuid,
+    },
+    /// A comment held for moderation. Only ids, so the text stays private until approved.
+    CommentAwaitingReview { id: Uuid, application_id: Uuid },
+    /// Carries no sender details, since anyone can subscribe to the event stream
+    ContactMessageReceived { id: Uuid },
 }
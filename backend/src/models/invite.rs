@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct Invite {
+    pub id: Uuid,
+    /// First characters of the code, enough to tell invites apart
+    pub code_hint: String,
+    pub note: Option<String>,
+    pub expires_at: chrono::NaiveDateTime,
+    pub used_at: Option<chrono::NaiveDateTime>,
+    pub used_by_email: Option<String>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateInvite {
+    pub note: Option<String>,
+    pub expires_in_days: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreatedInvite {
+    pub id: Uuid,
+    pub code: String,
+    pub expires_at: chrono::NaiveDateTime,
+}
@@ -1,6 +1,12 @@
+pub mod api_token;
 pub mod application;
 pub mod comment;
+pub mod contact_message;
+pub mod document;
 pub mod event;
+pub mod invite;
+pub mod outbound_email;
 pub mod public_application;
+pub mod session;
 pub mod user;
 pub mod visitor;
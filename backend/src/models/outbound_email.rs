@@ -0,0 +1,18 @@
+use serde::Serialize;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// A row of the outbound queue without its bodies, which can hold sign-in links.
+#[derive(Debug, Serialize, FromRow)]
+pub struct OutboundEmail {
+    pub id: Uuid,
+    pub template: String,
+    pub recipient: String,
+    pub subject: String,
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: chrono::NaiveDateTime,
+    pub created_at: chrono::NaiveDateTime,
+    pub sent_at: Option<chrono::NaiveDateTime>,
+}
@@ -24,7 +24,11 @@ pub struct PublicApplicationDetail {
     pub salary: Option<String>,
     pub cover_letter: Option<String>,
     pub cv_path: Option<String>,
+    #[sqlx(default)]
+    pub cv_thumbnail_url: Option<String>,
     pub logo_url: Option<String>,
     pub description: Option<String>,
+    pub source: Option<String>,
+    pub comments_enabled: bool,
     pub created_at: NaiveDateTime,
 }
@@ -13,4 +13,18 @@ pub struct User {
     #[serde(skip_serializing)]
     #[allow(dead_code)]
     pub verification_token: Option<String>,
+    #[serde(skip_serializing)]
+    #[allow(dead_code)]
+    pub verification_expires_at: Option<chrono::NaiveDateTime>,
+    pub pending_email: Option<String>,
+    #[serde(skip_serializing)]
+    #[allow(dead_code)]
+    pub totp_secret: Option<String>,
+    pub totp_enabled: bool,
+    #[serde(skip_serializing)]
+    #[allow(dead_code)]
+    pub totp_last_step: Option<i64>,
+    #[serde(skip_serializing)]
+    #[allow(dead_code)]
+    pub deletion_requested_at: Option<chrono::NaiveDateTime>,
 }
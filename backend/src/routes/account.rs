@@ -0,0 +1,363 @@
+use crate::config::Config;
+use crate::error::{AppError, AppResult};
+use crate::extract::Json;
+use crate::models::user::User;
+use crate::routes::auth::{
+    generate_verification_token, hash_password, password_matches, session_response, Claims,
+    MIN_PASSWORD_LENGTH, VERIFICATION_TOKEN_HOURS,
+};
+use crate::services::account_deletion::GRACE_DAYS;
+use crate::services::email::templates::{self, Locale};
+use crate::services::email::{Email, EmailQueue};
+use crate::services::sessions::{self, ClientInfo};
+use axum::{
+    extract::State,
+    http::{header, HeaderMap},
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::sync::Arc;
+
+#[derive(Serialize)]
+pub struct AccountInfo {
+    pub email: String,
+    /// New address awaiting verification
+    pub pending_email: Option<String>,
+    pub created_at: chrono::NaiveDateTime,
+    pub totp_enabled: bool,
+    /// When the account will be purged, if deletion was requested
+    pub deletion_scheduled_for: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Deserialize)]
+pub struct DeleteAccountPayload {
+    password: String,
+}
+
+#[derive(Deserialize)]
+pub struct ChangePasswordPayload {
+    current_password: String,
+    new_password: String,
+}
+
+#[derive(Deserialize)]
+pub struct ChangeEmailPayload {
+    new_email: String,
+    password: String,
+}
+
+async fn current_user(pool: &PgPool, claims: &Claims) -> AppResult<User> {
+    sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = $1")
+        .bind(claims.user_id()?)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::unauthorized("invalid_token", "Invalid token"))
+}
+
+fn incorrect_password() -> AppError {
+    AppError::forbidden("incorrect_password", "Incorrect password")
+}
+
+fn account_info(user: &User) -> AccountInfo {
+    AccountInfo {
+        email: user.email.clone(),
+        pending_email: user.pending_email.clone(),
+        created_at: user.created_at,
+        totp_enabled: user.totp_enabled,
+        deletion_scheduled_for: user
+            .deletion_requested_at
+            .map(|at| at + chrono::Duration::days(GRACE_DAYS as i64)),
+    }
+}
+
+pub async fn get_account(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> AppResult<Json<AccountInfo>> {
+    let user = current_user(&pool, &claims).await?;
+    Ok(Json(account_info(&user)))
+}
+
+/// Changes the password and signs out every other device: all sessions are
+/// revoked and the caller gets a fresh token pair.
+pub async fn change_password(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    State(emails): State<EmailQueue>,
+    claims: Claims,
+    headers: HeaderMap,
+    Json(payload): Json<ChangePasswordPayload>,
+) -> AppResult<Response> {
+    let user = current_user(&pool, &claims).await?;
+
+    if !password_matches(&user.password_hash, &payload.current_password) {
+        return Err(incorrect_password());
+    }
+    if payload.new_password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(AppError::invalid(
+            "new_password",
+            format!(
+                "Password must be at least {} characters",
+                MIN_PASSWORD_LENGTH
+            ),
+        ));
+    }
+
+    let password_hash = hash_password(&payload.new_password)?;
+
+    // The new password only counts together with every old session being gone
+    let mut tx = pool.begin().await?;
+    sqlx::query("UPDATE users SET password_hash = $2 WHERE id = $1")
+        .bind(user.id)
+        .bind(&password_hash)
+        .execute(&mut *tx)
+        .await?;
+    sessions::revoke_all(&mut *tx, user.id).await?;
+    tx.commit().await?;
+    tracing::info!("ACCOUNT: Password changed for user {}", user.id);
+
+    let client = ClientInfo::from_headers(&headers);
+    let tokens = sessions::create_session(&pool, &config.jwt_secret, user.id, &client).await?;
+
+    // Best effort: the change is done, a missing notice must not turn it into an error
+    let locale = Locale::from_headers(&headers, config.email.locale);
+    let notified = match templates::render("password_changed", locale, serde_json::json!({})) {
+        Ok(rendered) => emails
+            .enqueue(Some(user.id), Email::new(user.email.clone(), rendered))
+            .await
+            .map(|_| ())
+            .map_err(AppError::from),
+        Err(e) => Err(e.into()),
+    };
+    if let Err(e) = notified {
+        tracing::error!(
+            "ACCOUNT: Could not queue password change notice for {}: {:?}",
+            user.id,
+            e
+        );
+    }
+
+    Ok(session_response(&config, tokens, claims.via_cookie))
+}
+
+/// Starts an email change. The new address only replaces the old one once
+/// the link sent to it is opened (see `auth::verify_email`).
+pub async fn change_email(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    State(emails): State<EmailQueue>,
+    headers: HeaderMap,
+    claims: Claims,
+    Json(payload): Json<ChangeEmailPayload>,
+) -> AppResult<Json<AccountInfo>> {
+    let user = current_user(&pool, &claims).await?;
+
+    if !password_matches(&user.password_hash, &payload.password) {
+        return Err(incorrect_password());
+    }
+
+    let new_email = payload.new_email.trim().to_string();
+    if !new_email.contains('@')
+        || new_email.len() > 255
+        || new_email
+            .chars()
+            .any(|c| c.is_whitespace() || "<>\"'".contains(c))
+    {
+        return Err(AppError::invalid("new_email", "Invalid email address"));
+    }
+    if new_email.eq_ignore_ascii_case(&user.email) {
+        return Err(AppError::invalid(
+            "new_email",
+            "That is already your email address",
+        ));
+    }
+
+    let taken: bool =
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM users WHERE LOWER(email) = LOWER($1))")
+            .bind(&new_email)
+            .fetch_one(&pool)
+            .await?;
+    if taken {
+        return Err(AppError::conflict(
+            "email_taken",
+            "That email address is already in use",
+        ));
+    }
+
+    let verification_token = generate_verification_token();
+    let user = sqlx::query_as::<_, User>(
+        "UPDATE users SET pending_email = $2, verification_token = $3,
+             verification_expires_at = NOW() + make_interval(hours => $4)
+         WHERE id = $1 RETURNING *",
+    )
+    .bind(user.id)
+    .bind(&new_email)
+    .bind(&verification_token)
+    .bind(VERIFICATION_TOKEN_HOURS)
+    .fetch_one(&pool)
+    .await?;
+
+    let verify_link = format!(
+        "{}/admin/verify?token={}",
+        config.frontend_url, verification_token
+    );
+
+    tracing::info!(">>> EMAIL CHANGE REQUESTED <<<");
+    tracing::info!("To: {}", new_email);
+    tracing::info!("Link: {}", verify_link);
+
+    let locale = Locale::from_headers(&headers, config.email.locale);
+    let confirm = templates::render(
+        "confirm_new_email",
+        locale,
+        serde_json::json!({ "verify_link": verify_link, "hours": VERIFICATION_TOKEN_HOURS }),
+    )?;
+    let notice = templates::render(
+        "email_change_requested",
+        locale,
+        serde_json::json!({ "new_email": new_email }),
+    )?;
+    emails
+        .enqueue(Some(user.id), Email::new(new_email.clone(), confirm))
+        .await?;
+    emails
+        .enqueue(Some(user.id), Email::new(user.email.clone(), notice))
+        .await?;
+
+    Ok(Json(account_info(&user)))
+}
+
+/// Schedules the account for deletion after the grace period. Sessions stay
+/// valid until then so the owner can change their mind.
+pub async fn request_deletion(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    State(emails): State<EmailQueue>,
+    headers: HeaderMap,
+    claims: Claims,
+    Json(payload): Json<DeleteAccountPayload>,
+) -> AppResult<Json<AccountInfo>> {
+    let user = current_user(&pool, &claims).await?;
+
+    if !password_matches(&user.password_hash, &payload.password) {
+        return Err(incorrect_password());
+    }
+
+    let user = sqlx::query_as::<_, User>(
+        "UPDATE users SET deletion_requested_at = COALESCE(deletion_requested_at, NOW())
+         WHERE id = $1 RETURNING *",
+    )
+    .bind(user.id)
+    .fetch_one(&pool)
+    .await?;
+    let info = account_info(&user);
+
+    if let Some(scheduled_for) = info.deletion_scheduled_for {
+        tracing::info!("ACCOUNT: Deletion scheduled for user {}", user.id);
+        let rendered = templates::render(
+            "deletion_scheduled",
+            Locale::from_headers(&headers, config.email.locale),
+            serde_json::json!({ "scheduled_for": scheduled_for.format("%Y-%m-%d %H:%M").to_string() }),
+        )?;
+        emails
+            .enqueue(Some(user.id), Email::new(user.email.clone(), rendered))
+            .await?;
+    }
+
+    Ok(Json(info))
+}
+
+pub async fn cancel_deletion(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> AppResult<Json<AccountInfo>> {
+    let user = sqlx::query_as::<_, User>(
+        "UPDATE users SET deletion_requested_at = NULL WHERE id = $1 RETURNING *",
+    )
+    .bind(claims.user_id()?)
+    .fetch_optional(&pool)
+    .await?
+    .ok_or_else(|| AppError::unauthorized("invalid_token", "Invalid token"))?;
+
+    Ok(Json(account_info(&user)))
+}
+
+/// Machine-readable copy of everything stored about the user (GDPR art. 15/20).
+/// Secrets (password, TOTP and token hashes) are left out.
+pub async fn export_data(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> AppResult<impl IntoResponse> {
+    let user_id = claims.user_id()?;
+
+    // Each entry is one aggregate query returning a JSON value
+    let sections: [(&str, &str); 9] = [
+        (
+            "account",
+            "SELECT to_jsonb(u) FROM (SELECT id, email, pending_email, created_at, is_verified, totp_enabled, comment_moderation, deletion_requested_at
+             FROM users WHERE id = $1) u",
+        ),
+        (
+            "applications",
+            "SELECT COALESCE(jsonb_agg(to_jsonb(a) ORDER BY a.created_at), '[]')
+             FROM applications a WHERE a.user_id = $1",
+        ),
+        (
+            "comments",
+            "SELECT COALESCE(jsonb_agg(to_jsonb(c) - 'ip_hash' - 'content_hash' ORDER BY c.created_at), '[]')
+             FROM comments c JOIN applications a ON a.id = c.application_id WHERE a.user_id = $1",
+        ),
+        (
+            "documents",
+            "SELECT COALESCE(jsonb_agg(to_jsonb(d) - 'search_vector' ORDER BY d.created_at), '[]')
+             FROM documents d WHERE d.user_id = $1",
+        ),
+        (
+            "sessions",
+            "SELECT COALESCE(jsonb_agg(to_jsonb(s) - 'refresh_token_hash' - 'previous_token_hash' ORDER BY s.created_at), '[]')
+             FROM sessions s WHERE s.user_id = $1",
+        ),
+        (
+            "api_tokens",
+            "SELECT COALESCE(jsonb_agg(to_jsonb(t) - 'token_hash' ORDER BY t.created_at), '[]')
+             FROM api_tokens t WHERE t.user_id = $1",
+        ),
+        (
+            "invites",
+            "SELECT COALESCE(jsonb_agg(to_jsonb(i) - 'code_hash' ORDER BY i.created_at), '[]')
+             FROM invite_codes i WHERE i.created_by = $1",
+        ),
+        (
+            "password_resets",
+            "SELECT COALESCE(jsonb_agg(to_jsonb(p) - 'token_hash' ORDER BY p.created_at), '[]')
+             FROM password_resets p WHERE p.user_id = $1",
+        ),
+        (
+            "emails",
+            "SELECT COALESCE(jsonb_agg(to_jsonb(e) - 'html' - 'text_body' ORDER BY e.created_at), '[]')
+             FROM outbound_emails e WHERE e.user_id = $1",
+        ),
+    ];
+
+    let mut export = serde_json::Map::new();
+    export.insert(
+        "exported_at".to_string(),
+        serde_json::json!(chrono::Utc::now().naive_utc()),
+    );
+    for (name, query) in sections {
+        let value = sqlx::query_scalar::<_, Option<serde_json::Value>>(query)
+            .bind(user_id)
+            .fetch_one(&pool)
+            .await?;
+        export.insert(name.to_string(), value.unwrap_or(serde_json::Value::Null));
+    }
+
+    Ok((
+        [(
+            header::CONTENT_DISPOSITION,
+            "attachment; filename=\"oisko-toita-export.json\"",
+        )],
+        Json(serde_json::Value::Object(export)),
+    ))
+}
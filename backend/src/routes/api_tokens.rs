@@ -0,0 +1,122 @@
+use crate::error::{AppError, AppResult, FieldError};
+use crate::extract::{Json, Path};
+use crate::models::api_token::{ApiToken, CreateApiToken, CreatedApiToken};
+use crate::routes::auth::Claims;
+use crate::services::api_tokens::{self, Scope};
+use crate::services::sessions::hash_token;
+use axum::{extract::State, http::StatusCode};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+const MAX_TOKENS_PER_USER: i64 = 20;
+
+pub async fn list_tokens(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> AppResult<Json<Vec<ApiToken>>> {
+    let user_id = claims.user_id()?;
+
+    let list = sqlx::query_as::<_, ApiToken>(
+        "SELECT id, name, token_hint, scopes, created_at, last_used_at
+         FROM api_tokens WHERE user_id = $1 ORDER BY created_at DESC",
+    )
+    .bind(user_id)
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(list))
+}
+
+pub async fn create_token(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Json(payload): Json<CreateApiToken>,
+) -> AppResult<(StatusCode, Json<CreatedApiToken>)> {
+    let user_id = claims.user_id()?;
+    let mut problems = Vec::new();
+
+    let name = payload.name.trim();
+    if name.is_empty() || name.chars().count() > 100 {
+        problems.push(FieldError {
+            field: "name",
+            message: "Name must be 1-100 characters".to_string(),
+        });
+    }
+
+    let mut scopes = Vec::new();
+    for raw in &payload.scopes {
+        match Scope::parse(raw) {
+            Some(scope) if !scopes.contains(&scope) => scopes.push(scope),
+            Some(_) => {}
+            None => problems.push(FieldError {
+                field: "scopes",
+                message: format!("Unknown scope '{}'", raw),
+            }),
+        }
+    }
+    if payload.scopes.is_empty() {
+        problems.push(FieldError {
+            field: "scopes",
+            message: "Pick at least one scope".to_string(),
+        });
+    }
+    if !problems.is_empty() {
+        return Err(AppError::Validation(problems));
+    }
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM api_tokens WHERE user_id = $1")
+        .bind(user_id)
+        .fetch_one(&pool)
+        .await?;
+    if count >= MAX_TOKENS_PER_USER {
+        return Err(AppError::conflict(
+            "token_limit_reached",
+            "Token limit reached, revoke an old token first",
+        ));
+    }
+
+    let token = api_tokens::generate_token();
+    let scopes: Vec<String> = scopes.iter().map(|s| s.as_str().to_string()).collect();
+
+    let id: Uuid = sqlx::query_scalar(
+        "INSERT INTO api_tokens (user_id, name, token_hash, token_hint, scopes)
+         VALUES ($1, $2, $3, $4, $5) RETURNING id",
+    )
+    .bind(user_id)
+    .bind(name)
+    .bind(hash_token(&token))
+    .bind(&token[..api_tokens::TOKEN_PREFIX.len() + 4])
+    .bind(&scopes)
+    .fetch_one(&pool)
+    .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreatedApiToken {
+            id,
+            name: name.to_string(),
+            token,
+            scopes,
+        }),
+    ))
+}
+
+pub async fn revoke_token(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+) -> AppResult<StatusCode> {
+    let user_id = claims.user_id()?;
+
+    let res = sqlx::query("DELETE FROM api_tokens WHERE id = $1 AND user_id = $2")
+        .bind(id)
+        .bind(user_id)
+        .execute(&pool)
+        .await?;
+
+    if res.rows_affected() > 0 {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(AppError::NotFound("Token"))
+    }
+}
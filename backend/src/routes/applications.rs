@@ -1,55 +1,118 @@
-use crate::models::application::{Application, CreateApplication, UpdateApplication};
+use crate::error::{AppError, AppResult};
+use crate::extract::{Json, Path};
+use crate::models::application::{
+    AppInfo, Application, CreateApplication, DailyCount, DashboardStats, StatusCount,
+    UpdateApplication, LEAD_STATUS,
+};
+use crate::models::comment::{
+    Comment, CommentStatus, CommentWithContext, CreateComment, ModerationMode,
+};
 use crate::models::event::AppEvent;
+use crate::models::public_application::{PublicApplication, PublicApplicationDetail};
 use crate::routes::auth::Claims;
+use crate::routes::comments;
+use crate::services::logos;
+use crate::services::spam::{Source, SpamFilter, Submission};
 use async_stream::stream;
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    response::{
-        sse::{Event, Sse},
-        IntoResponse,
-    },
-    Json,
+    extract::{ConnectInfo, State},
+    http::{HeaderMap, StatusCode},
+    response::sse::{Event, Sse},
 };
 use futures_util::stream::Stream;
 use sqlx::PgPool;
 use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::time::Duration;
 use tokio::sync::broadcast;
 use uuid::Uuid;
 
-pub async fn list_applications(State(pool): State<PgPool>, claims: Claims) -> impl IntoResponse {
-    let user_id = Uuid::parse_str(&claims.sub).map_err(|_| StatusCode::UNAUTHORIZED);
-    let user_id = match user_id {
-        Ok(id) => id,
-        Err(e) => return e.into_response(),
-    };
+pub async fn list_applications(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> AppResult<Json<Vec<Application>>> {
+    let user_id = claims.user_id()?;
 
     let applications = sqlx::query_as::<_, Application>(
-        "SELECT a.*, (SELECT COUNT(*) FROM comments c WHERE c.application_id = a.id) as comment_count 
+        "SELECT a.*, (SELECT COUNT(*) FROM comments c WHERE c.application_id = a.id) as comment_count,
+            (SELECT d.thumbnail_path FROM documents d WHERE d.file_path = a.cv_path) as cv_thumbnail_url,
+            (SELECT d.thumbnail_path FROM documents d WHERE d.file_path = a.cover_letter_path) as cover_letter_thumbnail_url
          FROM applications a WHERE user_id = $1 ORDER BY created_at DESC",
     )
     .bind(user_id)
     .fetch_all(&pool)
-    .await;
+    .await?;
 
-    match applications {
-        Ok(apps) => Json(apps).into_response(),
-        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    Ok(Json(applications))
+}
+
+/// Rejects a `/uploads/...` URL the caller did not upload, so an application cannot
+/// point at (and on account deletion take along) someone else's file. Remote URLs
+/// and mirrored logos under `/uploads/logos/` are not owned by anyone and pass.
+async fn check_upload_owner(
+    pool: &PgPool,
+    user_id: Uuid,
+    field: &'static str,
+    url: Option<&str>,
+) -> AppResult<()> {
+    let Some(url) = url.map(str::trim).filter(|u| !u.is_empty()) else {
+        return Ok(());
+    };
+    if field == "logo_url" {
+        if logos::is_remote(url) {
+            return Ok(());
+        }
+        if let Some(name) = url.strip_prefix("/uploads/logos/") {
+            if !name.is_empty() && !name.contains('/') && !name.contains("..") {
+                return Ok(());
+            }
+        }
+    }
+
+    let owned: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM documents WHERE user_id = $1 AND file_path = $2)",
+    )
+    .bind(user_id)
+    .bind(url)
+    .fetch_one(pool)
+    .await?;
+    if !owned {
+        return Err(AppError::invalid(field, "Not one of your uploaded files"));
     }
+    Ok(())
+}
+
+async fn check_upload_owners(
+    pool: &PgPool,
+    user_id: Uuid,
+    cv_path: Option<&str>,
+    cover_letter_path: Option<&str>,
+    logo_url: Option<&str>,
+) -> AppResult<()> {
+    check_upload_owner(pool, user_id, "cv_path", cv_path).await?;
+    check_upload_owner(pool, user_id, "cover_letter_path", cover_letter_path).await?;
+    check_upload_owner(pool, user_id, "logo_url", logo_url).await
 }
 
 pub async fn create_application(
     State(pool): State<PgPool>,
     claims: Claims,
     Json(payload): Json<CreateApplication>,
-) -> impl IntoResponse {
-    let user_id = Uuid::parse_str(&claims.sub).map_err(|_| StatusCode::UNAUTHORIZED);
-    let user_id = match user_id {
-        Ok(id) => id,
-        Err(e) => return e.into_response(),
-    };
+) -> AppResult<(StatusCode, Json<Application>)> {
+    let user_id = claims.user_id()?;
+
+    // Manually pasted logo URLs get mirrored too, not just scanned ones
+    let logo_url = logos::localize(payload.logo_url.clone()).await;
+    check_upload_owners(
+        &pool,
+        user_id,
+        payload.cv_path.as_deref(),
+        payload.cover_letter_path.as_deref(),
+        logo_url.as_deref(),
+    )
+    .await?;
 
-    let result = sqlx::query_as::<_, Application>(
+    let app = sqlx::query_as::<_, Application>(
         r#"
         INSERT INTO applications (
             user_id, company, company_website, role, status, salary, contact_person, 
@@ -70,44 +133,34 @@ pub async fn create_application(
     .bind(&payload.cv_path)
     .bind(&payload.cover_letter)
     .bind(&payload.cover_letter_path)
-    .bind(&payload.logo_url)
+    .bind(&logo_url)
     .bind(&payload.description)
     .fetch_one(&pool)
-    .await;
+    .await?;
 
-    match result {
-        Ok(app) => (StatusCode::CREATED, Json(app)).into_response(),
-        Err(e) => {
-            tracing::error!("Failed to create application: {:?}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
-        }
-    }
+    Ok((StatusCode::CREATED, Json(app)))
 }
 
 pub async fn get_application(
     State(pool): State<PgPool>,
     Path(id): Path<Uuid>,
     claims: Claims,
-) -> impl IntoResponse {
-    let user_id = Uuid::parse_str(&claims.sub).map_err(|_| StatusCode::UNAUTHORIZED);
-    let user_id = match user_id {
-        Ok(uid) => uid,
-        Err(e) => return e.into_response(),
-    };
+) -> AppResult<Json<Application>> {
+    let user_id = claims.user_id()?;
 
     let application = sqlx::query_as::<_, Application>(
-        "SELECT * FROM applications WHERE id = $1 AND user_id = $2",
+        "SELECT a.*,
+            (SELECT d.thumbnail_path FROM documents d WHERE d.file_path = a.cv_path) as cv_thumbnail_url,
+            (SELECT d.thumbnail_path FROM documents d WHERE d.file_path = a.cover_letter_path) as cover_letter_thumbnail_url
+         FROM applications a WHERE a.id = $1 AND a.user_id = $2",
     )
     .bind(id)
     .bind(user_id)
     .fetch_optional(&pool)
-    .await;
+    .await?
+    .ok_or(AppError::NotFound("Application"))?;
 
-    match application {
-        Ok(Some(app)) => Json(app).into_response(),
-        Ok(None) => (StatusCode::NOT_FOUND, "Application not found").into_response(),
-        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
-    }
+    Ok(Json(application))
 }
 
 pub async fn update_application(
@@ -116,12 +169,18 @@ pub async fn update_application(
     Path(id): Path<Uuid>,
     claims: Claims,
     Json(payload): Json<UpdateApplication>,
-) -> impl IntoResponse {
-    let user_id = Uuid::parse_str(&claims.sub).map_err(|_| StatusCode::UNAUTHORIZED);
-    let user_id = match user_id {
-        Ok(uid) => uid,
-        Err(e) => return e.into_response(),
-    };
+) -> AppResult<Json<Application>> {
+    let user_id = claims.user_id()?;
+
+    let logo_url = logos::localize(payload.logo_url.clone()).await;
+    check_upload_owners(
+        &pool,
+        user_id,
+        payload.cv_path.as_deref(),
+        payload.cover_letter_path.as_deref(),
+        logo_url.as_deref(),
+    )
+    .await?;
 
     // First check if it exists and belongs to user
     // (Optional optimization: do it in one query with UPDATE ... WHERE user_id = ...)
@@ -131,7 +190,7 @@ pub async fn update_application(
     // For simplicity, let's fetch first to verify ownership (handling 404 vs 403 implicitly)
     // Actually, `UPDATE ... RETURNING *` returning nothing means not found/not owned.
 
-    let result = sqlx::query_as::<_, Application>(
+    let app = sqlx::query_as::<_, Application>(
         r#"
         UPDATE applications
         SET 
@@ -147,6 +206,7 @@ pub async fn update_application(
             cover_letter_path = COALESCE($10, cover_letter_path),
             logo_url = COALESCE($11, logo_url),
             description = COALESCE($12, description),
+            comments_enabled = COALESCE($15, comments_enabled),
             updated_at = NOW()
         WHERE id = $13 AND user_id = $14
         RETURNING *
@@ -162,182 +222,189 @@ pub async fn update_application(
     .bind(&payload.cv_path)
     .bind(&payload.cover_letter)
     .bind(&payload.cover_letter_path)
-    .bind(&payload.logo_url)
+    .bind(&logo_url)
     .bind(&payload.description)
     .bind(id)
     .bind(user_id)
+    .bind(payload.comments_enabled)
     .fetch_optional(&pool)
-    .await;
-
-    match result {
-        Ok(Some(app)) => {
-            // Broadcast update
-            let _ = tx.send(AppEvent::ApplicationStatusUpdated {
-                id: app.id,
-                company: app.company.clone(),
-                status: app.status.clone(),
-            });
-            Json(app).into_response()
-        }
-        Ok(None) => (StatusCode::NOT_FOUND, "Application not found").into_response(),
-        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    .await?
+    .ok_or(AppError::NotFound("Application"))?;
+
+    // The event stream is public, and leads stay private until they leave Lead
+    if app.status != LEAD_STATUS {
+        let _ = tx.send(AppEvent::ApplicationStatusUpdated {
+            id: app.id,
+            company: app.company.clone(),
+            status: app.status.clone(),
+        });
     }
+    Ok(Json(app))
 }
 
 pub async fn delete_application(
     State(pool): State<PgPool>,
     Path(id): Path<Uuid>,
     claims: Claims,
-) -> impl IntoResponse {
-    let user_id = Uuid::parse_str(&claims.sub).map_err(|_| StatusCode::UNAUTHORIZED);
-    let user_id = match user_id {
-        Ok(uid) => uid,
-        Err(e) => return e.into_response(),
-    };
+) -> AppResult<StatusCode> {
+    let user_id = claims.user_id()?;
 
     let result = sqlx::query("DELETE FROM applications WHERE id = $1 AND user_id = $2")
         .bind(id)
         .bind(user_id)
         .execute(&pool)
-        .await;
-
-    match result {
-        Ok(res) => {
-            if res.rows_affected() > 0 {
-                StatusCode::NO_CONTENT.into_response()
-            } else {
-                (StatusCode::NOT_FOUND, "Application not found").into_response()
-            }
-        }
-        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Application"));
     }
+    Ok(StatusCode::NO_CONTENT)
 }
 
-pub async fn get_public_applications(State(pool): State<PgPool>) -> impl IntoResponse {
-    use crate::models::public_application::PublicApplication;
-
+pub async fn get_public_applications(
+    State(pool): State<PgPool>,
+) -> AppResult<Json<Vec<PublicApplication>>> {
     let applications = sqlx::query_as::<_, PublicApplication>(
-        "SELECT id, company, company_website, role, status, logo_url, created_at FROM applications ORDER BY created_at DESC",
+        "SELECT id, company, company_website, role, status, logo_url, created_at FROM applications
+         WHERE status <> $1 ORDER BY created_at DESC",
     )
+    .bind(LEAD_STATUS)
     .fetch_all(&pool)
-    .await;
+    .await?;
 
-    match applications {
-        Ok(apps) => Json(apps).into_response(),
-        Err(e) => {
-            tracing::error!("Failed to fetch public applications: {:?}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
-        }
-    }
+    Ok(Json(applications))
 }
 
 pub async fn get_public_application_detail(
     State(pool): State<PgPool>,
     Path(id): Path<Uuid>,
-) -> impl IntoResponse {
-    use crate::models::public_application::PublicApplicationDetail;
-
+) -> AppResult<Json<PublicApplicationDetail>> {
     let application = sqlx::query_as::<_, PublicApplicationDetail>(
-        "SELECT id, company, company_website, role, status, salary, cover_letter, cv_path, logo_url, description, created_at FROM applications WHERE id = $1",
+        "SELECT a.id, a.company, a.company_website, a.role, a.status, a.salary, a.cover_letter, a.cv_path,
+            (SELECT d.thumbnail_path FROM documents d WHERE d.file_path = a.cv_path) as cv_thumbnail_url,
+            a.logo_url, a.description, a.source, a.comments_enabled, a.created_at
+         FROM applications a WHERE a.id = $1 AND a.status <> $2",
     )
     .bind(id)
+    .bind(LEAD_STATUS)
     .fetch_optional(&pool)
-    .await;
-
-    match application {
-        Ok(Some(app)) => Json(app).into_response(),
-        Ok(None) => (StatusCode::NOT_FOUND, "Application not found").into_response(),
-        Err(e) => {
-            tracing::error!("Failed to fetch public application detail: {:?}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
-        }
-    }
+    .await?
+    .ok_or(AppError::NotFound("Application"))?;
+
+    Ok(Json(application))
 }
 
 pub async fn get_comments(
     State(pool): State<PgPool>,
     Path(application_id): Path<Uuid>,
-) -> impl IntoResponse {
-    use crate::models::comment::Comment;
-
+) -> AppResult<Json<Vec<Comment>>> {
     let comments = sqlx::query_as::<_, Comment>(
-        "SELECT * FROM comments WHERE application_id = $1 ORDER BY created_at DESC",
+        "SELECT * FROM comments WHERE application_id = $1 AND status = $2 ORDER BY created_at DESC",
     )
     .bind(application_id)
+    .bind(CommentStatus::Approved.as_str())
     .fetch_all(&pool)
-    .await;
+    .await?;
 
-    match comments {
-        Ok(comments) => Json(comments).into_response(),
-        Err(e) => {
-            tracing::error!("Failed to fetch comments: {:?}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
-        }
-    }
+    Ok(Json(comments))
 }
 
+/// New comments are published or held for review depending on the owner's
+/// moderation mode. Ones the spam filter flags are always held. Held comments
+/// stay private until approved.
 pub async fn create_comment(
     State(pool): State<PgPool>,
     State(tx): State<broadcast::Sender<AppEvent>>,
+    State(spam): State<SpamFilter>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Path(application_id): Path<Uuid>,
-    Json(payload): Json<crate::models::comment::CreateComment>,
-) -> impl IntoResponse {
-    use crate::models::comment::Comment;
-
-    // 1. Bot Detection (Honeypot)
-    if let Some(val) = &payload.bot_field {
-        if !val.is_empty() {
-            // Fake success for bots
-            return (
-                StatusCode::CREATED,
-                Json(Comment {
-                    id: Uuid::new_v4(),
-                    application_id,
-                    visitor_name: payload.visitor_name,
-                    content: payload.content,
-                    created_at: chrono::Utc::now().naive_utc(),
-                }),
-            )
-                .into_response();
-        }
+    Json(payload): Json<CreateComment>,
+) -> AppResult<(StatusCode, Json<Comment>)> {
+    let ip_hash = spam.ip_hash(&headers, addr);
+    if !spam.allow(&ip_hash) {
+        return Err(AppError::TooManyRequests(None));
     }
 
-    // Get application info for the event
-    let app_info = sqlx::query_as::<_, crate::models::application::AppInfo>(
-        "SELECT company, role FROM applications WHERE id = $1",
+    let app = sqlx::query_as::<_, AppInfo>(
+        "SELECT a.company, a.role, a.comments_enabled, u.comment_moderation
+         FROM applications a JOIN users u ON u.id = a.user_id
+         WHERE a.id = $1 AND a.status <> $2",
     )
     .bind(application_id)
+    .bind(LEAD_STATUS)
     .fetch_optional(&pool)
-    .await;
+    .await?
+    .ok_or(AppError::NotFound("Application"))?;
+
+    if !app.comments_enabled {
+        return Err(AppError::forbidden(
+            "comments_disabled",
+            "Comments are closed for this application",
+        ));
+    }
 
-    let result = sqlx::query_as::<_, Comment>(
-        "INSERT INTO comments (application_id, visitor_name, content) VALUES ($1, $2, $3) RETURNING *",
+    let parent_id = match payload.parent_id {
+        Some(parent_id) => Some(comments::thread_of(&pool, application_id, parent_id).await?),
+        None => None,
+    };
+
+    let assessment = spam
+        .assess(
+            &pool,
+            Source::Comment,
+            ip_hash,
+            &Submission {
+                fields: vec![&payload.visitor_name, &payload.content],
+                free_links: 0,
+                // A filled honeypot is a bot; it is held like any other spam
+                honeypot: payload.bot_field.as_deref().is_some_and(|v| !v.is_empty()),
+                fill_time: payload.fill_time_ms.map(Duration::from_millis),
+            },
+        )
+        .await?;
+    let status = if assessment.verdict.is_suspicious() {
+        CommentStatus::Pending
+    } else {
+        ModerationMode::parse(&app.comment_moderation)
+            .unwrap_or(ModerationMode::Auto)
+            .initial_status()
+    };
+
+    let comment = sqlx::query_as::<_, Comment>(
+        "INSERT INTO comments
+            (application_id, visitor_name, content, status, parent_id, ip_hash, content_hash, spam_score, spam_reasons)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) RETURNING *",
     )
     .bind(application_id)
     .bind(&payload.visitor_name)
     .bind(&payload.content)
+    .bind(status.as_str())
+    .bind(parent_id)
+    .bind(&assessment.ip_hash)
+    .bind(&assessment.content_hash)
+    .bind(assessment.verdict.score)
+    .bind(&assessment.verdict.reasons)
     .fetch_one(&pool)
-    .await;
-
-    match result {
-        Ok(comment) => {
-            // Broadcast event
-            if let Ok(Some(app)) = app_info {
-                let _ = tx.send(AppEvent::CommentCreated {
-                    id: comment.id,
-                    application_id: comment.application_id,
-                    visitor_name: comment.visitor_name.clone(),
-                    company: app.company,
-                    role: app.role,
-                });
-            }
-            (StatusCode::CREATED, Json(comment)).into_response()
-        }
-        Err(e) => {
-            tracing::error!("Failed to create comment: {:?}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
-        }
-    }
+    .await?;
+
+    // Broadcast event
+    let event = match status {
+        CommentStatus::Approved => AppEvent::CommentCreated {
+            id: comment.id,
+            application_id: comment.application_id,
+            visitor_name: comment.visitor_name.clone(),
+            company: app.company,
+            role: app.role,
+        },
+        _ => AppEvent::CommentAwaitingReview {
+            id: comment.id,
+            application_id: comment.application_id,
+        },
+    };
+    let _ = tx.send(event);
+
+    Ok((StatusCode::CREATED, Json(comment)))
 }
 
 pub async fn sse_handler(
@@ -365,46 +432,34 @@ pub async fn sse_handler(
     Sse::new(stream).keep_alive(axum::response::sse::KeepAlive::default())
 }
 
-pub async fn get_recent_comments(State(pool): State<PgPool>, claims: Claims) -> impl IntoResponse {
-    use crate::models::comment::CommentWithContext;
-
-    let user_id = Uuid::parse_str(&claims.sub).map_err(|_| StatusCode::UNAUTHORIZED);
-    let user_id = match user_id {
-        Ok(id) => id,
-        Err(e) => return e.into_response(),
-    };
+pub async fn get_recent_comments(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> AppResult<Json<Vec<CommentWithContext>>> {
+    let user_id = claims.user_id()?;
 
     let comments = sqlx::query_as::<_, CommentWithContext>(
         r#"
         SELECT c.*, a.company, a.role 
         FROM comments c 
         JOIN applications a ON c.application_id = a.id 
-        WHERE a.user_id = $1 
+        WHERE a.user_id = $1 AND c.status <> 'rejected' AND NOT c.is_owner
         ORDER BY c.created_at DESC 
         LIMIT 10
         "#,
     )
     .bind(user_id)
     .fetch_all(&pool)
-    .await;
+    .await?;
 
-    match comments {
-        Ok(comments) => Json(comments).into_response(),
-        Err(e) => {
-            tracing::error!("Failed to fetch recent comments: {:?}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
-        }
-    }
+    Ok(Json(comments))
 }
 
-pub async fn get_dashboard_stats(State(pool): State<PgPool>, claims: Claims) -> impl IntoResponse {
-    use crate::models::application::{DailyCount, DashboardStats, StatusCount};
-
-    let user_id = Uuid::parse_str(&claims.sub).map_err(|_| StatusCode::UNAUTHORIZED);
-    let user_id = match user_id {
-        Ok(id) => id,
-        Err(e) => return e.into_response(),
-    };
+pub async fn get_dashboard_stats(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> AppResult<Json<DashboardStats>> {
+    let user_id = claims.user_id()?;
 
     // 1. Get daily activity for the last 30 days
     let daily_activity = sqlx::query_as::<_, DailyCount>(
@@ -423,7 +478,7 @@ pub async fn get_dashboard_stats(State(pool): State<PgPool>, claims: Claims) ->
     )
     .bind(user_id)
     .fetch_all(&pool)
-    .await;
+    .await?;
 
     // 2. Get status distribution
     let status_distribution = sqlx::query_as::<_, StatusCount>(
@@ -436,21 +491,10 @@ pub async fn get_dashboard_stats(State(pool): State<PgPool>, claims: Claims) ->
     )
     .bind(user_id)
     .fetch_all(&pool)
-    .await;
-
-    match (daily_activity, status_distribution) {
-        (Ok(daily), Ok(status)) => Json(DashboardStats {
-            daily_activity: daily,
-            status_distribution: status,
-        })
-        .into_response(),
-        (Err(e1), _) => {
-            tracing::error!("Daily activity query failed: {:?}", e1);
-            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch stats").into_response()
-        }
-        (_, Err(e2)) => {
-            tracing::error!("Status distribution query failed: {:?}", e2);
-            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch stats").into_response()
-        }
-    }
+    .await?;
+
+    Ok(Json(DashboardStats {
+        daily_activity,
+        status_distribution,
+    }))
 }
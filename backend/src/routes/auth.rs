@@ -1,19 +1,44 @@
+use crate::config::Config;
+use crate::error::{AppError, AppResult};
+use crate::extract::Json;
 use crate::models::user::User;
+use crate::routes::invites::{self, RegistrationMode};
+use crate::routes::two_factor;
+use crate::services::api_tokens;
+use crate::services::cookies;
+use crate::services::email::templates::{self, Locale};
+use crate::services::email::{Email, EmailQueue};
+use crate::services::login_guard::{Failure, LOCKOUT_DURATION, LOCKOUT_THRESHOLD};
+use crate::services::sessions::{self, ClientInfo, TokenPair};
+use crate::AppState;
 use argon2::{
-    password_hash::{PasswordHash, PasswordVerifier},
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordVerifier, SaltString},
     Argon2, PasswordHasher,
 };
-use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
-use jsonwebtoken::{encode, EncodingKey, Header};
+use axum::{
+    extract::{ConnectInfo, FromRef, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{AppendHeaders, IntoResponse, Response},
+};
 use rand::{distributions::Alphanumeric, Rng};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
-use std::env;
+use std::net::SocketAddr;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use uuid::Uuid;
+
+const RESET_TOKEN_MINUTES: i32 = 60;
+pub const VERIFICATION_TOKEN_HOURS: i32 = 48;
+pub const MIN_PASSWORD_LENGTH: usize = 8;
 
 #[derive(Deserialize)]
 pub struct LoginPayload {
     email: String,
     password: String,
+    /// Receive the session as HttpOnly cookies instead of tokens in the body
+    #[serde(default)]
+    use_cookies: bool,
 }
 
 #[derive(Deserialize)]
@@ -25,341 +50,726 @@ pub struct VerifyPayload {
 pub struct RegisterPayload {
     email: String,
     password: String,
+    #[serde(default)]
+    invite_code: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ForgotPasswordPayload {
+    email: String,
+}
+
+#[derive(Deserialize)]
+pub struct ResendVerificationPayload {
+    email: String,
 }
 
-#[derive(Serialize)]
-pub struct LoginResponse {
+#[derive(Deserialize)]
+pub struct ResetPasswordPayload {
     token: String,
+    password: String,
 }
 
-#[derive(Serialize)]
-pub struct ErrorResponse {
-    pub error: String,
+/// Without a `refresh_token` in the body, the refresh cookie is used instead.
+#[derive(Deserialize, Default)]
+pub struct RefreshPayload {
+    #[serde(default)]
+    refresh_token: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
     pub sub: String, // user id
+    pub sid: String, // session id
     pub exp: usize,
+    /// Authenticated via the session cookie rather than an `Authorization` header
+    #[serde(skip)]
+    pub via_cookie: bool,
+}
+
+impl Claims {
+    pub fn user_id(&self) -> AppResult<Uuid> {
+        Uuid::parse_str(&self.sub)
+            .map_err(|_| AppError::unauthorized("invalid_token", "Invalid token"))
+    }
+}
+
+/// Personal API tokens authenticate as their owner, limited to the token's scopes.
+/// They carry no session, so `sid` is left empty.
+async fn claims_from_api_token(
+    parts: &axum::http::request::Parts,
+    pool: &PgPool,
+    token: &str,
+) -> AppResult<Claims> {
+    let (user_id, scopes) = api_tokens::authenticate(pool, token)
+        .await?
+        .ok_or_else(|| AppError::unauthorized("invalid_token", "Invalid token"))?;
+
+    match api_tokens::required_scope(&parts.method, parts.uri.path()) {
+        None => Err(AppError::forbidden(
+            "token_not_allowed",
+            "API tokens cannot be used for this endpoint",
+        )),
+        Some(scope) if !scopes.contains(&scope) => Err(AppError::forbidden(
+            "missing_scope",
+            format!("Token is missing the '{}' scope", scope.as_str()),
+        )),
+        Some(_) => Ok(Claims {
+            sub: user_id.to_string(),
+            sid: String::new(),
+            exp: 0,
+            via_cookie: false,
+        }),
+    }
 }
 
 #[axum::async_trait]
 impl<S> axum::extract::FromRequestParts<S> for Claims
 where
+    PgPool: FromRef<S>,
+    Arc<Config>: FromRef<S>,
     S: Send + Sync,
 {
-    type Rejection = (StatusCode, Json<ErrorResponse>);
+    type Rejection = AppError;
 
     async fn from_request_parts(
         parts: &mut axum::http::request::Parts,
-        _state: &S,
+        state: &S,
     ) -> Result<Self, Self::Rejection> {
+        // An explicit Authorization header wins; the session cookie is the fallback
         let auth_header = parts
             .headers
             .get(axum::http::header::AUTHORIZATION)
-            .and_then(|h| h.to_str().ok())
-            .ok_or((
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Missing authorization header".to_string(),
-                }),
-            ))?;
-
-        if !auth_header.starts_with("Bearer ") {
-            return Err((
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid authorization header".to_string(),
-                }),
-            ));
-        }
+            .and_then(|h| h.to_str().ok());
+
+        let (token, via_cookie) = match auth_header {
+            Some(auth_header) => match auth_header.strip_prefix("Bearer ") {
+                Some(token) => (token.to_string(), false),
+                None => {
+                    return Err(AppError::unauthorized(
+                        "invalid_authorization_header",
+                        "Invalid authorization header",
+                    ))
+                }
+            },
+            None => match cookies::read(&parts.headers, cookies::ACCESS_COOKIE) {
+                Some(token) => (token, true),
+                None => {
+                    return Err(AppError::unauthorized(
+                        "missing_credentials",
+                        "Missing authorization header",
+                    ))
+                }
+            },
+        };
+        let token = token.as_str();
 
-        let token = &auth_header[7..];
-        let secret = env::var("JWT_SECRET").unwrap_or_else(|_| "secret".to_string());
+        if !via_cookie && token.starts_with(api_tokens::TOKEN_PREFIX) {
+            return claims_from_api_token(parts, &PgPool::from_ref(state), token).await;
+        }
 
+        let config = Arc::<Config>::from_ref(state);
         let token_data = jsonwebtoken::decode::<Claims>(
             token,
-            &jsonwebtoken::DecodingKey::from_secret(secret.as_bytes()),
+            &jsonwebtoken::DecodingKey::from_secret(config.jwt_secret.as_bytes()),
             &jsonwebtoken::Validation::default(),
         )
-        .map_err(|_| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid token".to_string(),
-                }),
-            )
-        })?;
+        .map_err(|_| AppError::unauthorized("invalid_token", "Invalid token"))?;
+
+        // The JWT alone stays valid until it expires, so check the session is still live
+        let mut claims = token_data.claims;
+        claims.via_cookie = via_cookie;
+
+        // Browsers attach cookies to cross-site requests too; only our frontend knows the CSRF token
+        if via_cookie && !parts.method.is_safe() {
+            let presented = parts
+                .headers
+                .get(cookies::CSRF_HEADER)
+                .and_then(|h| h.to_str().ok())
+                .unwrap_or_default();
+            if !cookies::csrf_matches(&config.jwt_secret, &claims.sid, presented) {
+                return Err(AppError::forbidden(
+                    "csrf_failed",
+                    "Missing or invalid CSRF token",
+                ));
+            }
+        }
 
-        Ok(token_data.claims)
+        let revoked = || AppError::unauthorized("session_revoked", "Session revoked");
+        let (Ok(user_id), Ok(session_id)) =
+            (Uuid::parse_str(&claims.sub), Uuid::parse_str(&claims.sid))
+        else {
+            return Err(revoked());
+        };
+
+        if sessions::is_active(&PgPool::from_ref(state), session_id, user_id).await? {
+            Ok(claims)
+        } else {
+            Err(revoked())
+        }
     }
 }
 
+/// Hands a new session to the client: as JSON tokens, or as HttpOnly cookies
+/// plus the CSRF token the frontend must echo back.
+pub fn session_response(config: &Config, tokens: TokenPair, use_cookies: bool) -> Response {
+    if !use_cookies {
+        return (StatusCode::OK, Json(tokens)).into_response();
+    }
+
+    let [access, refresh] = cookies::session_cookies(&config.cookies, &tokens);
+    (
+        StatusCode::OK,
+        AppendHeaders([(header::SET_COOKIE, access), (header::SET_COOKIE, refresh)]),
+        Json(serde_json::json!({
+            "csrf_token": cookies::csrf_token(&config.jwt_secret, &tokens.session_id.to_string()),
+            "expires_in": sessions::ACCESS_TOKEN_MINUTES * 60,
+        })),
+    )
+        .into_response()
+}
+
+pub fn password_matches(stored_hash: &str, password: &str) -> bool {
+    PasswordHash::new(stored_hash).is_ok_and(|hash| {
+        Argon2::default()
+            .verify_password(password.as_bytes(), &hash)
+            .is_ok()
+    })
+}
+
+pub fn hash_password(password: &str) -> Result<String, argon2::password_hash::Error> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|h| h.to_string())
+}
+
+/// Hash of a random password, verified against when the email is unknown.
+fn dummy_password_hash() -> &'static str {
+    static HASH: OnceLock<String> = OnceLock::new();
+    HASH.get_or_init(|| {
+        let salt = SaltString::generate(&mut OsRng);
+        let password: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(32)
+            .map(char::from)
+            .collect();
+        Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|h| h.to_string())
+            .unwrap_or_default()
+    })
+}
+
+async fn send_lockout_email(
+    emails: &EmailQueue,
+    config: &Config,
+    user_id: Uuid,
+    to: String,
+    client: &ClientInfo,
+) -> AppResult<()> {
+    // Whoever triggered this may not be the owner, so their browser language is no hint
+    let rendered = templates::render(
+        "lockout",
+        config.email.locale,
+        serde_json::json!({
+            "attempts": LOCKOUT_THRESHOLD,
+            "minutes": LOCKOUT_DURATION.as_secs() / 60,
+            "ip": client.ip_address.as_deref().unwrap_or("unknown address"),
+            "reset_link": format!("{}/admin/forgot", config.frontend_url),
+        }),
+    )?;
+    emails
+        .enqueue(Some(user_id), Email::new(to, rendered))
+        .await?;
+    Ok(())
+}
+
 pub async fn login(
-    State(pool): State<PgPool>,
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(payload): Json<LoginPayload>,
-) -> impl IntoResponse {
-    // 1. Find user by email
+) -> AppResult<Response> {
+    let pool = state.pool;
+    let account_key = payload.email.trim().to_lowercase();
+    let client = ClientInfo::from_headers(&headers);
+
+    // 1. Throttle per client and per account before touching Argon2. The guard counts
+    // this attempt now, so parallel guesses cannot all slip past it.
+    let client_key = sessions::client_ip(&headers, addr, state.config.trust_proxy).to_string();
+    if !state.login_limiter.check(&client_key) {
+        return Err(AppError::TooManyRequests(Some(Duration::from_secs(60))));
+    }
+    let failure = state
+        .login_guard
+        .check(&account_key)
+        .map_err(|wait| AppError::TooManyRequests(Some(wait)))?;
+
+    // 2. Find user by email
     let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE email = $1")
         .bind(&payload.email)
         .fetch_optional(&pool)
-        .await;
+        .await?;
+
+    // 3. Verify password. Unknown emails are checked against a dummy hash so both
+    // cases cost one Argon2 verification and cannot be told apart by timing.
+    let stored_hash = user
+        .as_ref()
+        .map(|u| u.password_hash.as_str())
+        .unwrap_or_else(|| dummy_password_hash());
+    let password_ok = password_matches(stored_hash, &payload.password);
 
     let user = match user {
-        Ok(Some(u)) => {
-            if !u.is_verified {
-                return (
-                    StatusCode::FORBIDDEN,
-                    Json(ErrorResponse {
-                        error: "Email not verified".to_string(),
-                    }),
-                )
-                    .into_response();
+        Some(u) if password_ok => u,
+        user => {
+            if failure == Failure::LockedOut {
+                if let Some(u) = user {
+                    tracing::warn!("AUTH: Account {} locked after repeated failures", u.id);
+                    // Logged, not returned: a 500 here would tell real accounts apart
+                    if let Err(e) =
+                        send_lockout_email(&state.emails, &state.config, u.id, u.email, &client)
+                            .await
+                    {
+                        tracing::error!(
+                            "AUTH: Could not queue lockout email for {}: {:?}",
+                            u.id,
+                            e
+                        );
+                    }
+                }
             }
-            u
-        }
-        Ok(None) => {
-            return (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "Invalid credentials".to_string(),
-                }),
-            )
-                .into_response();
-        }
-        Err(_) => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: "Database error".to_string(),
-                }),
-            )
-                .into_response();
-        }
-    };
-
-    // 2. Verify password
-    let parsed_hash = match PasswordHash::new(&user.password_hash) {
-        Ok(h) => h,
-        Err(_) => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: "Auth error".to_string(),
-                }),
-            )
-                .into_response();
+            return Err(AppError::unauthorized(
+                "invalid_credentials",
+                "Invalid credentials",
+            ));
         }
     };
+    state.login_guard.record_success(&account_key);
+
+    // Only revealed once the password is right, so it does not leak which emails exist
+    if !user.is_verified {
+        return Err(AppError::forbidden(
+            "email_not_verified",
+            "Email not verified",
+        ));
+    }
 
-    if Argon2::default()
-        .verify_password(payload.password.as_bytes(), &parsed_hash)
-        .is_err()
-    {
-        return (
-            StatusCode::UNAUTHORIZED,
-            Json(ErrorResponse {
-                error: "Invalid credentials".to_string(),
-            }),
-        )
-            .into_response();
+    // 4. With 2FA on, the password only earns a short-lived challenge for the second step
+    if user.totp_enabled {
+        let challenge_token = two_factor::issue_challenge(&state.config.jwt_secret, user.id)?;
+        return Ok(Json(serde_json::json!({
+            "two_factor_required": true,
+            "challenge_token": challenge_token,
+        }))
+        .into_response());
     }
 
-    // 3. Generate JWT
-    let secret = env::var("JWT_SECRET").unwrap_or_else(|_| "secret".to_string());
-    let expiration = chrono::Utc::now()
-        .checked_add_signed(chrono::Duration::hours(24))
-        .expect("valid timestamp")
-        .timestamp() as usize;
+    // 5. Open a session (short-lived JWT + rotating refresh token)
+    let tokens =
+        sessions::create_session(&pool, &state.config.jwt_secret, user.id, &client).await?;
+    Ok(session_response(&state.config, tokens, payload.use_cookies))
+}
 
-    let claims = Claims {
-        sub: user.id.to_string(),
-        exp: expiration,
-    };
+/// Rotates the refresh token. Cookie refreshes need no CSRF token: a forged
+/// request can only renew the victim's own session and cannot read the result.
+pub async fn refresh(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    headers: HeaderMap,
+    payload: Option<Json<RefreshPayload>>,
+) -> AppResult<Response> {
+    let client = ClientInfo::from_headers(&headers);
+    let payload = payload.map(|Json(p)| p).unwrap_or_default();
+    let use_cookies = payload.refresh_token.is_none();
+    let refresh_token = payload
+        .refresh_token
+        .or_else(|| cookies::read(&headers, cookies::REFRESH_COOKIE))
+        .ok_or_else(|| AppError::unauthorized("invalid_refresh_token", "Invalid refresh token"))?;
+
+    // On failure the cookies are left alone: a tab losing a refresh race must not wipe the winner's new ones
+    let tokens = sessions::rotate(&pool, &config.jwt_secret, &refresh_token, &client).await?;
+    Ok(session_response(&config, tokens, use_cookies))
+}
 
-    let token = match encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(secret.as_bytes()),
-    ) {
-        Ok(t) => t,
-        Err(_) => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: "Token creation failed".to_string(),
-                }),
-            )
-                .into_response();
-        }
-    };
+pub async fn logout(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    claims: Claims,
+) -> AppResult<Response> {
+    let user_id = claims.user_id()?;
+    let session_id = Uuid::parse_str(&claims.sid)
+        .map_err(|_| AppError::unauthorized("invalid_token", "Invalid token"))?;
+
+    sessions::revoke(&pool, user_id, session_id).await?;
+
+    let [access, refresh] = cookies::clear_cookies(&config.cookies);
+    Ok((
+        StatusCode::NO_CONTENT,
+        AppendHeaders([(header::SET_COOKIE, access), (header::SET_COOKIE, refresh)]),
+    )
+        .into_response())
+}
 
-    (StatusCode::OK, Json(LoginResponse { token })).into_response()
+pub fn generate_verification_token() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect()
+}
+
+async fn send_verification_email(
+    emails: &EmailQueue,
+    config: &Config,
+    locale: Locale,
+    user_id: Uuid,
+    to: String,
+    verification_token: &str,
+) -> AppResult<()> {
+    let verify_link = format!(
+        "{}/admin/verify?token={}",
+        config.frontend_url, verification_token
+    );
+
+    tracing::info!("To: {}", to);
+    tracing::info!("Link: {}", verify_link);
+
+    let rendered = templates::render(
+        "verify_account",
+        locale,
+        serde_json::json!({ "verify_link": verify_link, "hours": VERIFICATION_TOKEN_HOURS }),
+    )?;
+    emails
+        .enqueue(Some(user_id), Email::new(to, rendered))
+        .await?;
+    Ok(())
 }
 
 pub async fn register(
     State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    State(emails): State<EmailQueue>,
+    headers: HeaderMap,
     Json(payload): Json<RegisterPayload>,
-) -> impl IntoResponse {
+) -> AppResult<impl IntoResponse> {
+    let invite_required =
+        || AppError::forbidden("invite_required", "A valid invite code is required");
+
+    // 0. Is registration allowed at all? Checked before anything that reveals whether an email exists
+    let invite_id = match invites::effective_mode(&pool, config.registration_mode).await? {
+        RegistrationMode::Open => None,
+        RegistrationMode::Closed => {
+            return Err(AppError::forbidden(
+                "registration_closed",
+                "Registration is closed",
+            ));
+        }
+        RegistrationMode::InviteOnly => {
+            let code = payload.invite_code.as_deref().unwrap_or("").trim();
+            match invites::find_valid(&pool, code).await? {
+                Some(id) if !code.is_empty() => Some(id),
+                _ => return Err(invite_required()),
+            }
+        }
+    };
+
     // 1. Check if user exists
     let user_exists = sqlx::query("SELECT id FROM users WHERE email = $1")
         .bind(&payload.email)
         .fetch_optional(&pool)
-        .await;
-
-    if let Ok(Some(_)) = user_exists {
-        // Return 200 checks to avoid enumeration attacks, or 400 if we don't care about personal app
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "User already exists".to_string(),
-            }),
-        )
-            .into_response();
+        .await?;
+
+    if user_exists.is_some() {
+        return Err(AppError::conflict("email_taken", "User already exists"));
     }
 
     // 2. Hash password
-    let salt =
-        argon2::password_hash::SaltString::generate(&mut argon2::password_hash::rand_core::OsRng);
-    let argon2 = Argon2::default();
-    let password_hash = argon2
-        .hash_password(payload.password.as_bytes(), &salt)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
-        .unwrap()
-        .to_string();
+    let password_hash = hash_password(&payload.password)?;
 
     // 3. Generate Token
-    let verification_token: String = rand::thread_rng()
-        .sample_iter(&Alphanumeric)
-        .take(32)
-        .map(char::from)
-        .collect();
+    let verification_token = generate_verification_token();
 
-    // 4. Create User
-    let insert_result = sqlx::query(
-        "INSERT INTO users (email, password_hash, verification_token, is_verified) VALUES ($1, $2, $3, false)"
-    )
-    .bind(&payload.email)
-    .bind(password_hash)
-    .bind(&verification_token)
-    .execute(&pool)
-    .await;
+    // 4. Create User, consuming the invite in the same transaction so it can't be used twice
+    let insert_result: Result<Uuid, sqlx::Error> = async {
+        let mut tx = pool.begin().await?;
 
-    match insert_result {
-        Ok(_) => {
-            // 5. "Send" Email (Log it for now)
-            let frontend_url =
-                env::var("FRONTEND_URL").unwrap_or_else(|_| "http://localhost:8080".to_string());
-            let verify_link = format!("{}/admin/verify?token={}", frontend_url, verification_token);
-
-            tracing::info!(">>> ACCOUNT CREATED <<<");
-            tracing::info!("To: {}", payload.email);
-            tracing::info!("Link: {}", verify_link);
-
-            // Send Email via Resend if API key is present and not the placeholder
-            if let Ok(api_key) = env::var("RESEND_API_KEY") {
-                if api_key != "re_123456789" {
-                    let sender = env::var("SENDER_EMAIL")
-                        .unwrap_or_else(|_| "onboarding@resend.dev".to_string());
-                    let client = reqwest::Client::new();
-
-                    let email_body = format!(
-                        r#"
-                        <div style="font-family: sans-serif; max-width: 600px; margin: 0 auto;">
-                            <h2>Welcome to Oisko töitä</h2>
-                            <p>You have been registered as an administrator.</p>
-                            <p>Please click the button below to verify your account:</p>
-                            <a href="{}" style="display: inline-block; background-color: #000; color: #fff; padding: 10px 20px; text-decoration: none; border-radius: 5px; font-weight: bold;">Verify Account</a>
-                            <p style="margin-top: 20px; font-size: 12px; color: #666;">Or copy this link: {}</p>
-                        </div>
-                        "#,
-                        verify_link, verify_link
-                    );
-
-                    let resend_payload = serde_json::json!({
-                        "from": sender,
-                        "to": payload.email,
-                        "subject": "Verify your Oisko töitä Admin Account",
-                        "html": email_body
-                    });
-
-                    // Spawn the email task so we don't block the response
-                    tokio::spawn(async move {
-                        match client
-                            .post("https://api.resend.com/emails")
-                            .header("Authorization", format!("Bearer {}", api_key))
-                            .json(&resend_payload)
-                            .send()
-                            .await
-                        {
-                            Ok(res) => {
-                                if res.status().is_success() {
-                                    tracing::info!("Email sent successfully to {}", payload.email);
-                                } else {
-                                    let status = res.status();
-                                    let text = res.text().await.unwrap_or_default();
-                                    tracing::error!(
-                                        "Failed to send email via Resend. Status: {}, Body: {}",
-                                        status,
-                                        text
-                                    );
-                                }
-                            }
-                            Err(e) => tracing::error!("Failed to execute Resend request: {}", e),
-                        }
-                    });
-                } else {
-                    tracing::warn!("Resend API key is set to placeholder. Email sending skipped.");
-                }
-            } else {
-                tracing::warn!("RESEND_API_KEY not set. Email not sent.");
+        if let Some(invite_id) = invite_id {
+            let claimed = sqlx::query(
+                "UPDATE invite_codes SET used_at = NOW() WHERE id = $1 AND used_at IS NULL AND expires_at > NOW()",
+            )
+            .bind(invite_id)
+            .execute(&mut *tx)
+            .await?;
+            if claimed.rows_affected() == 0 {
+                return Err(sqlx::Error::RowNotFound);
             }
-
-            (StatusCode::CREATED, Json(serde_json::json!({ "message": "Changes saved. Check backend logs for token." }))).into_response()
         }
-        Err(_) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: "Database error".to_string(),
-            }),
+
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, password_hash, verification_token, verification_expires_at, is_verified)
+             VALUES ($1, $2, $3, NOW() + make_interval(hours => $4), false) RETURNING id"
         )
-            .into_response(),
+        .bind(&payload.email)
+        .bind(&password_hash)
+        .bind(&verification_token)
+        .bind(VERIFICATION_TOKEN_HOURS)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if let Some(invite_id) = invite_id {
+            sqlx::query("UPDATE invite_codes SET used_by = $2 WHERE id = $1")
+                .bind(invite_id)
+                .bind(user_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(user_id)
     }
+    .await;
+
+    let user_id = match insert_result {
+        Ok(user_id) => user_id,
+        Err(sqlx::Error::RowNotFound) => return Err(invite_required()),
+        Err(e) => return Err(e.into()),
+    };
+
+    // 5. Send the verification link (also logged for local setups)
+    tracing::info!(">>> ACCOUNT CREATED <<<");
+    let locale = Locale::from_headers(&headers, config.email.locale);
+    send_verification_email(
+        &emails,
+        &config,
+        locale,
+        user_id,
+        payload.email,
+        &verification_token,
+    )
+    .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({ "message": "Changes saved. Check backend logs for token." })),
+    ))
 }
 
 pub async fn verify_email(
     State(pool): State<PgPool>,
     Json(payload): Json<VerifyPayload>,
-) -> impl IntoResponse {
-    let result = sqlx::query("UPDATE users SET is_verified = true, verification_token = NULL WHERE verification_token = $1")
-        .bind(&payload.token)
-        .execute(&pool)
-        .await;
+) -> AppResult<impl IntoResponse> {
+    let result = sqlx::query(
+        "UPDATE users SET is_verified = true, email = COALESCE(pending_email, email), pending_email = NULL,
+             verification_token = NULL, verification_expires_at = NULL
+         WHERE verification_token = $1 AND verification_expires_at > NOW()",
+    )
+    .bind(&payload.token)
+    .execute(&pool)
+    .await;
 
     match result {
-        Ok(res) => {
-            if res.rows_affected() > 0 {
-                (
-                    StatusCode::OK,
-                    Json(serde_json::json!({ "message": "Email verified" })),
-                )
-                    .into_response()
-            } else {
-                (
-                    StatusCode::BAD_REQUEST,
-                    Json(ErrorResponse {
-                        error: "Invalid token".to_string(),
-                    }),
-                )
-                    .into_response()
-            }
+        Ok(res) if res.rows_affected() > 0 => {
+            Ok(Json(serde_json::json!({ "message": "Email verified" })))
         }
-        Err(_) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: "Database error".to_string(),
-            }),
+        Ok(_) => Err(AppError::bad_request(
+            "invalid_link",
+            "Invalid or expired link, request a new one from the login page",
+        )),
+        // An email change whose address was taken by someone else in the meantime
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => Err(AppError::conflict(
+            "email_taken",
+            "That email address is already in use",
+        )),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Issues a fresh verification link. Answers the same whether or not the
+/// account exists or is already verified.
+pub async fn resend_verification(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(payload): Json<ResendVerificationPayload>,
+) -> AppResult<impl IntoResponse> {
+    let email = payload.email.trim().to_lowercase();
+    let client_ip = sessions::client_ip(&headers, addr, state.config.trust_proxy);
+
+    if !state
+        .verification_limiter
+        .check(&format!("email:{}", email))
+        || !state
+            .verification_limiter
+            .check(&format!("ip:{}", client_ip))
+    {
+        return Err(AppError::TooManyRequests(None));
+    }
+
+    let verification_token = generate_verification_token();
+    let updated: Option<(Uuid, String)> = sqlx::query_as(
+        "UPDATE users SET verification_token = $2, verification_expires_at = NOW() + make_interval(hours => $3)
+         WHERE LOWER(email) = $1 AND is_verified = false
+         RETURNING id, email",
+    )
+    .bind(&email)
+    .bind(&verification_token)
+    .bind(VERIFICATION_TOKEN_HOURS)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    if let Some((user_id, address)) = updated {
+        tracing::info!(">>> VERIFICATION RESENT <<<");
+        let locale = Locale::from_headers(&headers, state.config.email.locale);
+        send_verification_email(
+            &state.emails,
+            &state.config,
+            locale,
+            user_id,
+            address,
+            &verification_token,
         )
-            .into_response(),
+        .await?;
+    }
+
+    Ok(Json(serde_json::json!({
+        "message": "If the account is awaiting verification, a new link has been sent."
+    })))
+}
+
+pub async fn forgot_password(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(payload): Json<ForgotPasswordPayload>,
+) -> AppResult<impl IntoResponse> {
+    // Each request sends an email, so cap them per address and per client like resends
+    let email = payload.email.trim().to_lowercase();
+    let client_ip = sessions::client_ip(&headers, addr, state.config.trust_proxy);
+    if !state
+        .verification_limiter
+        .check(&format!("reset-email:{}", email))
+        || !state
+            .verification_limiter
+            .check(&format!("reset-ip:{}", client_ip))
+    {
+        return Err(AppError::TooManyRequests(None));
     }
+    let AppState {
+        pool,
+        config,
+        emails,
+        ..
+    } = state;
+
+    // Same answer whether or not the account exists, so this can't be used to probe emails
+    let response = Json(
+        serde_json::json!({ "message": "If the account exists, a reset link has been sent." }),
+    );
+
+    let user_id: Option<Uuid> = sqlx::query_scalar("SELECT id FROM users WHERE email = $1")
+        .bind(&payload.email)
+        .fetch_optional(&pool)
+        .await?;
+
+    let Some(user_id) = user_id else {
+        tracing::info!("Password reset requested for unknown email");
+        return Ok(response);
+    };
+
+    let reset_token: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(48)
+        .map(char::from)
+        .collect();
+
+    // Only the newest link should work
+    let _ = sqlx::query(
+        "UPDATE password_resets SET used_at = NOW() WHERE user_id = $1 AND used_at IS NULL",
+    )
+    .bind(user_id)
+    .execute(&pool)
+    .await;
+
+    sqlx::query(
+        "INSERT INTO password_resets (user_id, token_hash, expires_at) VALUES ($1, $2, NOW() + make_interval(mins => $3))",
+    )
+    .bind(user_id)
+    .bind(sessions::hash_token(&reset_token))
+    .bind(RESET_TOKEN_MINUTES)
+    .execute(&pool)
+    .await?;
+
+    let reset_link = format!("{}/admin/reset?token={}", config.frontend_url, reset_token);
+
+    tracing::info!(">>> PASSWORD RESET REQUESTED <<<");
+    tracing::info!("To: {}", payload.email);
+    tracing::info!("Link: {}", reset_link);
+
+    let rendered = templates::render(
+        "password_reset",
+        Locale::from_headers(&headers, config.email.locale),
+        serde_json::json!({ "reset_link": reset_link, "minutes": RESET_TOKEN_MINUTES }),
+    )?;
+    emails
+        .enqueue(Some(user_id), Email::new(payload.email, rendered))
+        .await?;
+
+    Ok(response)
+}
+
+pub async fn reset_password(
+    State(pool): State<PgPool>,
+    Json(payload): Json<ResetPasswordPayload>,
+) -> AppResult<impl IntoResponse> {
+    if payload.password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(AppError::invalid(
+            "password",
+            format!(
+                "Password must be at least {} characters",
+                MIN_PASSWORD_LENGTH
+            ),
+        ));
+    }
+
+    let password_hash = hash_password(&payload.password)?;
+
+    let mut tx = pool.begin().await?;
+
+    // Consuming the token and changing the password happen together or not at all
+    let user_id: Option<Uuid> = sqlx::query_scalar(
+        "UPDATE password_resets SET used_at = NOW()
+         WHERE token_hash = $1 AND used_at IS NULL AND expires_at > NOW()
+         RETURNING user_id",
+    )
+    .bind(sessions::hash_token(payload.token.trim()))
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(user_id) = user_id else {
+        return Err(AppError::bad_request(
+            "invalid_link",
+            "Invalid or expired reset link",
+        ));
+    };
+
+    sqlx::query("UPDATE users SET password_hash = $1 WHERE id = $2")
+        .bind(&password_hash)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    // Whoever had the old password may still hold a session
+    match sessions::revoke_all(&pool, user_id).await {
+        Ok(count) => tracing::info!("Password reset for {}, revoked {} sessions", user_id, count),
+        Err(e) => tracing::error!(
+            "Password reset for {}, failed to revoke sessions: {:?}",
+            user_id,
+            e
+        ),
+    }
+
+    Ok(Json(serde_json::json!({ "message": "Password updated" })))
 }
@@ -0,0 +1,221 @@
+use crate::error::{AppError, AppResult};
+use crate::extract::{Json, Path, Query};
+use crate::models::comment::{
+    Comment, CommentStatus, CommentWithContext, ModerationMode, ModerationQuery,
+    ModerationSettings, OwnerReply,
+};
+use crate::models::event::AppEvent;
+use crate::routes::auth::Claims;
+use axum::{extract::State, http::StatusCode};
+use sqlx::PgPool;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+const LIST_LIMIT: i64 = 200;
+const MAX_REPLY_LENGTH: usize = 2000;
+/// Name shown on owner replies; the badge comes from `is_owner`, not from this
+const OWNER_NAME: &str = "Owner";
+
+/// The thread a reply to `parent_id` belongs in. Threads are one level deep,
+/// so answering a reply joins its parent's thread. Hidden and pending comments
+/// can't be answered from the public page.
+pub async fn thread_of(pool: &PgPool, application_id: Uuid, parent_id: Uuid) -> AppResult<Uuid> {
+    sqlx::query_scalar(
+        "SELECT COALESCE(parent_id, id) FROM comments
+         WHERE id = $1 AND application_id = $2 AND status = $3",
+    )
+    .bind(parent_id)
+    .bind(application_id)
+    .bind(CommentStatus::Approved.as_str())
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound("Comment"))
+}
+
+/// Comments on the owner's applications in one state, newest first.
+/// Without a status this is the moderation queue.
+pub async fn list_comments(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Query(params): Query<ModerationQuery>,
+) -> AppResult<Json<Vec<CommentWithContext>>> {
+    let status = params.status.unwrap_or(CommentStatus::Pending);
+    let comments = sqlx::query_as::<_, CommentWithContext>(
+        "SELECT c.*, a.company, a.role
+         FROM comments c
+         JOIN applications a ON c.application_id = a.id
+         WHERE a.user_id = $1 AND c.status = $2
+         ORDER BY c.created_at DESC
+         LIMIT $3",
+    )
+    .bind(claims.user_id()?)
+    .bind(status.as_str())
+    .bind(LIST_LIMIT)
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(comments))
+}
+
+async fn set_status(
+    pool: &PgPool,
+    user_id: Uuid,
+    id: Uuid,
+    status: CommentStatus,
+) -> AppResult<CommentWithContext> {
+    sqlx::query_as::<_, CommentWithContext>(
+        "UPDATE comments c SET status = $3
+         FROM applications a
+         WHERE c.id = $1 AND c.application_id = a.id AND a.user_id = $2
+         RETURNING c.*, a.company, a.role",
+    )
+    .bind(id)
+    .bind(user_id)
+    .bind(status.as_str())
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound("Comment"))
+}
+
+/// Publishes the comment. Open application pages pick it up over SSE.
+pub async fn approve_comment(
+    State(pool): State<PgPool>,
+    State(tx): State<broadcast::Sender<AppEvent>>,
+    claims: Claims,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<CommentWithContext>> {
+    let comment = set_status(&pool, claims.user_id()?, id, CommentStatus::Approved).await?;
+    announce(&tx, &comment);
+    Ok(Json(comment))
+}
+
+fn announce(tx: &broadcast::Sender<AppEvent>, comment: &CommentWithContext) {
+    let _ = tx.send(AppEvent::CommentCreated {
+        id: comment.id,
+        application_id: comment.application_id,
+        visitor_name: comment.visitor_name.clone(),
+        company: comment.company.clone(),
+        role: comment.role.clone(),
+    });
+}
+
+/// Posts a verified owner reply under the comment. Replying to a comment that
+/// is still awaiting review publishes it, since the answer would make no sense
+/// without it; hidden comments have to be approved first.
+pub async fn reply_to_comment(
+    State(pool): State<PgPool>,
+    State(tx): State<broadcast::Sender<AppEvent>>,
+    claims: Claims,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<OwnerReply>,
+) -> AppResult<(StatusCode, Json<Comment>)> {
+    let content = payload.content.trim();
+    if content.is_empty() || content.chars().count() > MAX_REPLY_LENGTH {
+        return Err(AppError::invalid(
+            "content",
+            format!("Reply must be 1-{} characters", MAX_REPLY_LENGTH),
+        ));
+    }
+    let user_id = claims.user_id()?;
+
+    let parent = sqlx::query_as::<_, CommentWithContext>(
+        "SELECT c.*, a.company, a.role
+         FROM comments c JOIN applications a ON c.application_id = a.id
+         WHERE c.id = $1 AND a.user_id = $2",
+    )
+    .bind(id)
+    .bind(user_id)
+    .fetch_optional(&pool)
+    .await?
+    .ok_or(AppError::NotFound("Comment"))?;
+
+    if parent.status == CommentStatus::Rejected.as_str() {
+        return Err(AppError::conflict(
+            "comment_hidden",
+            "Approve the comment before replying to it",
+        ));
+    }
+    if parent.status == CommentStatus::Pending.as_str() {
+        let approved = set_status(&pool, user_id, id, CommentStatus::Approved).await?;
+        announce(&tx, &approved);
+    }
+
+    let thread_id = parent.parent_id.unwrap_or(parent.id);
+    let reply = sqlx::query_as::<_, Comment>(
+        "INSERT INTO comments (application_id, visitor_name, content, status, parent_id, is_owner)
+         VALUES ($1, $2, $3, $4, $5, TRUE) RETURNING *",
+    )
+    .bind(parent.application_id)
+    .bind(OWNER_NAME)
+    .bind(content)
+    .bind(CommentStatus::Approved.as_str())
+    .bind(thread_id)
+    .fetch_one(&pool)
+    .await?;
+
+    let _ = tx.send(AppEvent::CommentReplied {
+        id: reply.id,
+        application_id: reply.application_id,
+        parent_id: thread_id,
+    });
+    Ok((StatusCode::CREATED, Json(reply)))
+}
+
+/// Hides the comment from the public page but keeps it, so it can be approved later.
+pub async fn hide_comment(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<CommentWithContext>> {
+    let comment = set_status(&pool, claims.user_id()?, id, CommentStatus::Rejected).await?;
+    Ok(Json(comment))
+}
+
+pub async fn delete_comment(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<Uuid>,
+) -> AppResult<StatusCode> {
+    let deleted = sqlx::query(
+        "DELETE FROM comments c USING applications a
+         WHERE c.id = $1 AND c.application_id = a.id AND a.user_id = $2",
+    )
+    .bind(id)
+    .bind(claims.user_id()?)
+    .execute(&pool)
+    .await?;
+    if deleted.rows_affected() == 0 {
+        return Err(AppError::NotFound("Comment"));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn get_settings(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> AppResult<Json<ModerationSettings>> {
+    let mode: String = sqlx::query_scalar("SELECT comment_moderation FROM users WHERE id = $1")
+        .bind(claims.user_id()?)
+        .fetch_optional(&pool)
+        .await?
+        .ok_or(AppError::NotFound("User"))?;
+
+    Ok(Json(ModerationSettings {
+        mode: ModerationMode::parse(&mode).unwrap_or(ModerationMode::Auto),
+    }))
+}
+
+/// Only applies to new comments; ones already posted keep their state.
+pub async fn update_settings(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Json(payload): Json<ModerationSettings>,
+) -> AppResult<Json<ModerationSettings>> {
+    sqlx::query("UPDATE users SET comment_moderation = $2 WHERE id = $1")
+        .bind(claims.user_id()?)
+        .bind(payload.mode.as_str())
+        .execute(&pool)
+        .await?;
+
+    Ok(Json(payload))
+}
@@ -1,6 +1,31 @@
-use axum::{http::StatusCode, response::IntoResponse, Json};
+use crate::config::Config;
+use crate::error::{AppError, AppResult};
+use crate::extract::{Json, Path, Query};
+use crate::models::application::{Application, LEAD_STATUS, SOURCE_CONTACT_FORM};
+use crate::models::contact_message::{
+    ContactMessage, ContactMessageQuery, ContactReply, MessageStatus, UpdateContactMessage,
+};
+use crate::models::event::AppEvent;
+use crate::routes::auth::Claims;
+use crate::routes::intel::{self, CompanyIntel};
+use crate::services::email::templates::{self, Locale};
+use crate::services::email::{self, Email, EmailQueue};
+use crate::services::leads;
+use crate::services::spam::{Source, Submission};
+use crate::AppState;
+use axum::{
+    extract::{ConnectInfo, State},
+    http::{HeaderMap, StatusCode},
+};
 use serde::{Deserialize, Serialize};
-use std::env;
+use sqlx::PgPool;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+const MAX_NAME_LENGTH: usize = 100;
+const MAX_MESSAGE_LENGTH: usize = 5000;
 
 #[derive(Deserialize)]
 pub struct ContactPayload {
@@ -8,6 +33,8 @@ pub struct ContactPayload {
     email: String,
     message: String,
     link: Option<String>,
+    /// Milliseconds between the page showing the form and sending it
+    fill_time_ms: Option<u64>,
     // Honeypot field - should be empty for humans
     bot_field: Option<String>,
 }
@@ -17,128 +44,347 @@ pub struct ContactResponse {
     message: String,
 }
 
-#[derive(Serialize)]
-pub struct ErrorResponse {
-    error: String,
-}
+/// Stores the message in the inbox, then forwards it to `OWNER_EMAIL` if a
+/// mail backend is configured. Messages the spam filter doesn't like are kept
+/// in the spam folder instead, without a notification.
+pub async fn send_contact_email(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(payload): Json<ContactPayload>,
+) -> AppResult<Json<ContactResponse>> {
+    let ip_hash = state.spam.ip_hash(&headers, addr);
+    if !state.spam.allow(&ip_hash) {
+        return Err(AppError::TooManyRequests(None));
+    }
 
-pub async fn send_contact_email(Json(payload): Json<ContactPayload>) -> impl IntoResponse {
-    // 1. Bot Detection (Honeypot)
-    if let Some(val) = &payload.bot_field {
-        if !val.is_empty() {
-            // It's a bot. Return success to fool them, but do nothing.
-            return (
-                StatusCode::OK,
-                Json(ContactResponse {
-                    message: "Message sent successfully".to_string(),
-                }),
-            )
-                .into_response();
-        }
+    let name = payload.name.trim();
+    let message = payload.message.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LENGTH {
+        return Err(AppError::invalid(
+            "name",
+            format!("Name must be 1-{} characters", MAX_NAME_LENGTH),
+        ));
+    }
+    if !email::is_valid_address(&payload.email) {
+        return Err(AppError::invalid("email", "Invalid email address"));
+    }
+    if message.is_empty() || message.chars().count() > MAX_MESSAGE_LENGTH {
+        return Err(AppError::invalid(
+            "message",
+            format!("Message must be 1-{} characters", MAX_MESSAGE_LENGTH),
+        ));
     }
-    let api_key = match env::var("RESEND_API_KEY") {
-        Ok(key) => key,
-        Err(_) => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: "Email service not configured".to_string(),
-                }),
-            )
-                .into_response();
-        }
-    };
 
-    let sender = env::var("SENDER_EMAIL").unwrap_or_else(|_| "onboarding@resend.dev".to_string());
-    // In a real app, you might want to send this TO yourself, FROM the system.
-    // The "reply-to" header would be the user's email.
-    // For Resend 'onboarding' you can only send to yourself potentially or verified domains.
-    // Let's assume SENDER_EMAIL is a verified sender.
-    // We want to receive the email. So 'to' should be the owner's email.
-    // Let's use SENDER_EMAIL as the recipient too for simplicity or an ADMIN_EMAIL if it existed.
-    // For now, I'll send it back to the SENDER_EMAIL (acting as owner) or maybe we assume the user puts their own email in ContactPayload?
-    // Wait, the form is "Contact Me". So the user (payload.email) sends a message to ME (owner).
-    // Resend requires 'from' to be a verified domain.
-
-    let owner_email = env::var("OWNER_EMAIL").unwrap_or_else(|_| sender.clone());
-
-    let link_section = if let Some(link) = &payload.link {
-        if !link.is_empty() {
-            format!(
-                "<p><strong>Job Link:</strong> <a href=\"{}\">{}</a></p>",
-                link, link
-            )
-        } else {
-            String::new()
-        }
+    let link = payload
+        .link
+        .as_deref()
+        .map(str::trim)
+        .filter(|link| !link.is_empty());
+    let mut fields = vec![name, payload.email.trim(), message];
+    fields.extend(link);
+    let assessment = state
+        .spam
+        .assess(
+            &state.pool,
+            Source::ContactMessage,
+            ip_hash,
+            &Submission {
+                fields,
+                free_links: 1,
+                // A filled honeypot is a bot, but it still gets the usual answer
+                honeypot: payload.bot_field.as_deref().is_some_and(|v| !v.is_empty()),
+                fill_time: payload.fill_time_ms.map(Duration::from_millis),
+            },
+        )
+        .await?;
+    let held = assessment.verdict.is_suspicious();
+    let status = if held {
+        MessageStatus::Spam
     } else {
-        String::new()
+        MessageStatus::Unread
     };
 
-    let email_body = format!(
-        r#"
-        <div style="font-family: sans-serif; max-width: 600px; margin: 0 auto;">
-            <h2>New Contact Request</h2>
-            <p><strong>Name:</strong> {}</p>
-            <p><strong>Email:</strong> {}</p>
-            {}
-            <p><strong>Message:</strong></p>
-            <blockquote style="background: #f9f9f9; padding: 10px; border-left: 5px solid #ccc;">
-                {}
-            </blockquote>
-        </div>
-        "#,
-        payload.name, payload.email, link_section, payload.message
-    );
+    let stored = sqlx::query_as::<_, ContactMessage>(
+        "INSERT INTO contact_messages
+            (name, email, message, link, locale, status, ip_hash, content_hash, spam_score, spam_reasons)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) RETURNING *",
+    )
+    .bind(name)
+    .bind(payload.email.trim())
+    .bind(message)
+    .bind(link)
+    .bind(Locale::from_headers(&headers, state.config.email.locale).as_str())
+    .bind(status.as_str())
+    .bind(&assessment.ip_hash)
+    .bind(&assessment.content_hash)
+    .bind(assessment.verdict.score)
+    .bind(&assessment.verdict.reasons)
+    .fetch_one(&state.pool)
+    .await?;
 
-    let resend_payload = serde_json::json!({
-        "from": sender,
-        "to": owner_email,
-        "reply_to": payload.email,
-        "subject": format!("Contact Form: {} reached out", payload.name),
-        "html": email_body
-    });
-
-    let client = reqwest::Client::new();
-
-    match client
-        .post("https://api.resend.com/emails")
-        .header("Authorization", format!("Bearer {}", api_key))
-        .json(&resend_payload)
-        .send()
-        .await
-    {
-        Ok(res) => {
-            if res.status().is_success() {
-                (
-                    StatusCode::OK,
-                    Json(ContactResponse {
-                        message: "Message sent successfully".to_string(),
-                    }),
-                )
-                    .into_response()
-            } else {
-                let status = res.status();
-                let text = res.text().await.unwrap_or_default();
-                tracing::error!("Resend API Error: Status: {}, Body: {}", status, text);
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(ErrorResponse {
-                        error: "Failed to send email".to_string(),
-                    }),
-                )
-                    .into_response()
-            }
+    if !held {
+        let _ = state
+            .tx
+            .send(AppEvent::ContactMessageReceived { id: stored.id });
+
+        if state.emails.mailer().delivers() {
+            forward_to_owner(&state.config, &state.emails, &stored).await;
         }
-        Err(e) => {
-            tracing::error!("Failed to execute Resend request: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: "Failed to connect to email service".to_string(),
-                }),
-            )
-                .into_response()
+    }
+
+    Ok(Json(ContactResponse {
+        message: "Message sent successfully".to_string(),
+    }))
+}
+
+/// The message is already in the inbox, so a failure here is only logged.
+async fn forward_to_owner(config: &Config, emails: &EmailQueue, stored: &ContactMessage) {
+    let link = stored.link.as_deref();
+    let rendered = templates::render(
+        "contact_message",
+        config.email.locale,
+        serde_json::json!({
+            "name": stored.name,
+            "email": stored.email,
+            "link": link,
+            // Only web links become clickable; `javascript:` and the like stay text
+            "link_is_web": link.and_then(|l| url::Url::parse(l).ok())
+                .is_some_and(|url| matches!(url.scheme(), "http" | "https")),
+            "message": stored.message,
+        }),
+    );
+
+    let result = match rendered {
+        Ok(rendered) => {
+            let email = Email {
+                reply_to: Some(stored.email.clone()),
+                ..Email::new(config.email.owner_email.clone(), rendered)
+            };
+            emails
+                .enqueue(None, email)
+                .await
+                .map(|_| ())
+                .map_err(AppError::from)
         }
+        Err(e) => Err(e.into()),
+    };
+    if let Err(e) = result {
+        tracing::error!("CONTACT: Could not forward message {}: {:?}", stored.id, e);
+    }
+}
+
+/// Contact messages are addressed to the site, not to any one account: only the
+/// instance owner may read or act on them. That is the account registered with
+/// `OWNER_EMAIL`, or, if there is none, the first account created.
+async fn require_inbox_owner(pool: &PgPool, config: &Config, claims: &Claims) -> AppResult<Uuid> {
+    let user_id = claims.user_id()?;
+    let owner: Option<Uuid> = sqlx::query_scalar(
+        "SELECT id FROM users ORDER BY LOWER(email) = LOWER($1) DESC, created_at, id LIMIT 1",
+    )
+    .bind(&config.email.owner_email)
+    .fetch_optional(pool)
+    .await?;
+
+    if owner != Some(user_id) {
+        return Err(AppError::forbidden(
+            "not_inbox_owner",
+            "Only the site owner can access contact messages",
+        ));
     }
+    Ok(user_id)
+}
+
+async fn find_message(pool: &PgPool, id: Uuid) -> AppResult<ContactMessage> {
+    sqlx::query_as::<_, ContactMessage>("SELECT * FROM contact_messages WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(AppError::NotFound("Message"))
+}
+
+/// The inbox, newest first. Archived and spam messages are only listed when asked for.
+pub async fn list_messages(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    claims: Claims,
+    Query(params): Query<ContactMessageQuery>,
+) -> AppResult<Json<Vec<ContactMessage>>> {
+    let user_id = require_inbox_owner(&pool, &config, &claims).await?;
+
+    let messages = sqlx::query_as::<_, ContactMessage>(
+        "SELECT m.*,
+            (SELECT a.id FROM applications a WHERE a.contact_message_id = m.id AND a.user_id = $2
+             ORDER BY a.created_at LIMIT 1) AS application_id
+         FROM contact_messages m
+         WHERE CASE WHEN $1::TEXT IS NULL THEN m.status NOT IN ('archived', 'spam') ELSE m.status = $1 END
+         ORDER BY m.created_at DESC",
+    )
+    .bind(params.status.map(MessageStatus::as_str))
+    .bind(user_id)
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(messages))
+}
+
+pub async fn update_message(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    claims: Claims,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateContactMessage>,
+) -> AppResult<Json<ContactMessage>> {
+    require_inbox_owner(&pool, &config, &claims).await?;
+
+    let message = sqlx::query_as::<_, ContactMessage>(
+        "UPDATE contact_messages SET status = $2 WHERE id = $1 RETURNING *",
+    )
+    .bind(id)
+    .bind(payload.status.as_str())
+    .fetch_optional(&pool)
+    .await?
+    .ok_or(AppError::NotFound("Message"))?;
+
+    Ok(Json(message))
+}
+
+/// Emails the reply to the sender, quoting their message, in the language
+/// their browser used. Replies to the reply go to `OWNER_EMAIL`.
+pub async fn reply_to_message(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    State(emails): State<EmailQueue>,
+    claims: Claims,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<ContactReply>,
+) -> AppResult<Json<ContactMessage>> {
+    let user_id = require_inbox_owner(&pool, &config, &claims).await?;
+
+    let reply = payload.message.trim();
+    if reply.is_empty() || reply.chars().count() > MAX_MESSAGE_LENGTH {
+        return Err(AppError::invalid(
+            "message",
+            format!("Reply must be 1-{} characters", MAX_MESSAGE_LENGTH),
+        ));
+    }
+    if !emails.mailer().delivers() {
+        return Err(AppError::ServiceUnavailable(
+            "Email service not configured".to_string(),
+        ));
+    }
+
+    let message = find_message(&pool, id).await?;
+    let rendered = templates::render(
+        "contact_reply",
+        Locale::parse(&message.locale).unwrap_or(config.email.locale),
+        serde_json::json!({
+            "name": message.name,
+            "reply": reply,
+            "message": message.message,
+            "sent_on": message.created_at.format("%Y-%m-%d").to_string(),
+        }),
+    )?;
+    let email = Email {
+        reply_to: Some(config.email.owner_email.clone()),
+        ..Email::new(message.email.clone(), rendered)
+    };
+    emails.enqueue(Some(user_id), email).await?;
+
+    let message = sqlx::query_as::<_, ContactMessage>(
+        "UPDATE contact_messages SET replied_at = NOW(),
+             status = CASE WHEN status = 'unread' THEN 'read' ELSE status END
+         WHERE id = $1 RETURNING *",
+    )
+    .bind(id)
+    .fetch_one(&pool)
+    .await?;
+
+    Ok(Json(message))
+}
+
+pub async fn delete_message(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    claims: Claims,
+    Path(id): Path<Uuid>,
+) -> AppResult<StatusCode> {
+    require_inbox_owner(&pool, &config, &claims).await?;
+
+    let deleted = sqlx::query("DELETE FROM contact_messages WHERE id = $1")
+        .bind(id)
+        .execute(&pool)
+        .await?;
+    if deleted.rows_affected() == 0 {
+        return Err(AppError::NotFound("Message"));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Creates a lead from the message: the sender becomes the contact person and
+/// the job link, if any, is run through the intel scanner. A failed scan still
+/// creates the lead, just with less filled in.
+pub async fn create_lead(
+    State(state): State<AppState>,
+    claims: Claims,
+    Path(id): Path<Uuid>,
+) -> AppResult<(StatusCode, Json<Application>)> {
+    let user_id = require_inbox_owner(&state.pool, &state.config, &claims).await?;
+    let message = find_message(&state.pool, id).await?;
+
+    let existing: Option<Uuid> = sqlx::query_scalar(
+        "SELECT id FROM applications WHERE contact_message_id = $1 AND user_id = $2",
+    )
+    .bind(id)
+    .bind(user_id)
+    .fetch_optional(&state.pool)
+    .await?;
+    if existing.is_some() {
+        return Err(AppError::conflict(
+            "lead_exists",
+            "A lead was already created from this message",
+        ));
+    }
+
+    let intel = match leads::web_link(&message) {
+        Some(url) => match intel::scan(&state, &claims.sub, url.as_str(), false).await {
+            Ok(value) => serde_json::from_value::<CompanyIntel>(value).unwrap_or_default(),
+            Err(e) => {
+                tracing::warn!("CONTACT: Scan for lead from {} failed: {:?}", id, e);
+                CompanyIntel::default()
+            }
+        },
+        None => CompanyIntel::default(),
+    };
+    let draft = leads::draft(&message, intel);
+
+    let app = sqlx::query_as::<_, Application>(
+        "INSERT INTO applications (
+            user_id, company, company_website, role, status, salary, contact_person,
+            logo_url, description, source, contact_message_id
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+        RETURNING *",
+    )
+    .bind(user_id)
+    .bind(&draft.company)
+    .bind(&draft.company_website)
+    .bind(&draft.role)
+    .bind(LEAD_STATUS)
+    .bind(&draft.salary)
+    .bind(&draft.contact_person)
+    .bind(&draft.logo_url)
+    .bind(&draft.description)
+    .bind(SOURCE_CONTACT_FORM)
+    .bind(id)
+    .fetch_one(&state.pool)
+    .await?;
+
+    sqlx::query("UPDATE contact_messages SET status = 'read' WHERE id = $1 AND status = 'unread'")
+        .bind(id)
+        .execute(&state.pool)
+        .await?;
+
+    tracing::info!("CONTACT: Created lead {} from message {}", app.id, id);
+    Ok((StatusCode::CREATED, Json(app)))
 }
@@ -0,0 +1,44 @@
+use crate::error::AppResult;
+use crate::extract::{Json, Query};
+use crate::models::document::{DocumentSearchHit, DocumentSearchQuery};
+use crate::routes::auth::Claims;
+use axum::extract::State;
+use sqlx::PgPool;
+
+pub async fn search_documents(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Query(params): Query<DocumentSearchQuery>,
+) -> AppResult<Json<Vec<DocumentSearchHit>>> {
+    let user_id = claims.user_id()?;
+
+    if params.q.trim().is_empty() {
+        return Ok(Json(Vec::new()));
+    }
+
+    let hits = sqlx::query_as::<_, DocumentSearchHit>(
+        r#"
+        SELECT
+            d.file_path,
+            d.original_name,
+            d.thumbnail_path,
+            ts_headline('simple', d.extracted_text, q, 'StartSel=«, StopSel=», MaxFragments=2') AS snippet,
+            a.id AS application_id,
+            a.company,
+            a.role
+        FROM documents d
+        CROSS JOIN websearch_to_tsquery('simple', $2) q
+        LEFT JOIN applications a
+            ON a.user_id = d.user_id AND (a.cv_path = d.file_path OR a.cover_letter_path = d.file_path)
+        WHERE d.user_id = $1 AND d.search_vector @@ q
+        ORDER BY ts_rank(d.search_vector, q) DESC, d.created_at DESC
+        LIMIT 20
+        "#,
+    )
+    .bind(user_id)
+    .bind(params.q.trim())
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(hits))
+}
@@ -0,0 +1,49 @@
+use crate::error::{AppError, AppResult};
+use crate::extract::{Json, Path, Query};
+use crate::routes::auth::Claims;
+use crate::services::email::templates::{self, Locale, Rendered};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+pub struct TemplateSummary {
+    name: &'static str,
+    locales: Vec<&'static str>,
+}
+
+#[derive(Deserialize)]
+pub struct PreviewQuery {
+    locale: Option<String>,
+}
+
+pub async fn list_templates(_claims: Claims) -> Json<Vec<TemplateSummary>> {
+    Json(
+        templates::TEMPLATES
+            .iter()
+            .map(|t| TemplateSummary {
+                name: t.name,
+                locales: Locale::ALL.iter().map(|l| l.as_str()).collect(),
+            })
+            .collect(),
+    )
+}
+
+/// Renders a template with its example values, so wording and layout can be
+/// checked without triggering the real flow.
+pub async fn preview_template(
+    _claims: Claims,
+    Path(name): Path<String>,
+    Query(params): Query<PreviewQuery>,
+) -> AppResult<Json<Rendered>> {
+    let template = templates::find(&name).ok_or(AppError::NotFound("Template"))?;
+    let locale = match params.locale.as_deref() {
+        None => Locale::En,
+        Some(value) => Locale::parse(value)
+            .ok_or_else(|| AppError::invalid("locale", "Locale must be en or fi"))?,
+    };
+
+    Ok(Json(templates::render(
+        template.name,
+        locale,
+        template.sample(),
+    )?))
+}
@@ -0,0 +1,417 @@
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JobPosting {
+    pub title: Option<String>,
+    pub employment_type: Option<String>,
+    pub location: Option<String>,
+    pub remote: bool,
+    pub salary: Option<SalaryRange>,
+    pub date_posted: Option<String>,
+    pub valid_through: Option<String>,
+    pub hiring_organization: Option<String>,
+    pub hiring_organization_logo: Option<String>,
+    pub description: Option<String>,
+}
+
+impl JobPosting {
+    /// Field-wise merge: keeps our values and backfills gaps from `fallback`.
+    pub fn or(self, fallback: JobPosting) -> JobPosting {
+        JobPosting {
+            title: self.title.or(fallback.title),
+            employment_type: self.employment_type.or(fallback.employment_type),
+            location: self.location.or(fallback.location),
+            remote: self.remote || fallback.remote,
+            salary: self.salary.or(fallback.salary),
+            date_posted: self.date_posted.or(fallback.date_posted),
+            valid_through: self.valid_through.or(fallback.valid_through),
+            hiring_organization: self.hiring_organization.or(fallback.hiring_organization),
+            hiring_organization_logo: self
+                .hiring_organization_logo
+                .or(fallback.hiring_organization_logo),
+            description: self.description.or(fallback.description),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SalaryRange {
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub currency: Option<String>,
+    pub unit: Option<String>,
+}
+
+impl SalaryRange {
+    /// e.g. "4000–5200 EUR / MONTH", as the application form shows it
+    pub fn display(&self) -> String {
+        let mut out = match (self.min, self.max) {
+            (Some(min), Some(max)) if min != max => format!("{}–{}", min, max),
+            (Some(v), _) | (None, Some(v)) => v.to_string(),
+            (None, None) => String::new(),
+        };
+        if let Some(currency) = &self.currency {
+            out = format!("{} {}", out, currency);
+        }
+        if let Some(unit) = &self.unit {
+            out = format!("{} / {}", out, unit);
+        }
+        out
+    }
+}
+
+/// Looks for a schema.org `JobPosting`, preferring JSON-LD over microdata.
+pub fn extract_job_posting(doc: &Html) -> Option<JobPosting> {
+    from_json_ld(doc).or_else(|| from_microdata(doc))
+}
+
+// --- JSON-LD ---------------------------------------------------------------
+
+fn from_json_ld(doc: &Html) -> Option<JobPosting> {
+    let selector = Selector::parse("script[type='application/ld+json']").ok()?;
+    doc.select(&selector).find_map(|script| {
+        let raw = script.text().collect::<String>();
+        let value = serde_json::from_str::<Value>(raw.trim()).ok()?;
+        find_typed(&value, "JobPosting").map(job_from_json)
+    })
+}
+
+fn find_typed<'a>(value: &'a Value, type_name: &str) -> Option<&'a Value> {
+    match value {
+        Value::Array(items) => items.iter().find_map(|v| find_typed(v, type_name)),
+        Value::Object(map) => {
+            if is_type(map.get("@type"), type_name) {
+                return Some(value);
+            }
+            map.get("@graph").and_then(|g| find_typed(g, type_name))
+        }
+        _ => None,
+    }
+}
+
+fn is_type(value: Option<&Value>, type_name: &str) -> bool {
+    match value {
+        Some(Value::String(s)) => s == type_name || s.ends_with(&format!("/{}", type_name)),
+        Some(Value::Array(types)) => types.iter().any(|t| is_type(Some(t), type_name)),
+        _ => false,
+    }
+}
+
+fn job_from_json(job: &Value) -> JobPosting {
+    let organization = job.get("hiringOrganization");
+
+    JobPosting {
+        title: json_text(job.get("title")),
+        employment_type: json_list(job.get("employmentType")),
+        location: json_location(job.get("jobLocation")),
+        remote: json_text(job.get("jobLocationType"))
+            .is_some_and(|t| t.eq_ignore_ascii_case("TELECOMMUTE")),
+        salary: json_salary(job.get("baseSalary").or_else(|| job.get("estimatedSalary"))),
+        date_posted: json_text(job.get("datePosted")),
+        valid_through: json_text(job.get("validThrough")),
+        hiring_organization: json_text(organization),
+        hiring_organization_logo: organization.and_then(|o| json_text(o.get("logo"))),
+        description: json_text(job.get("description")).map(|d| strip_html(&d)),
+    }
+}
+
+/// Plain strings, numbers, or `{ "name": .. }` / `{ "@value": .. }` / `{ "url": .. }` nodes.
+fn json_text(value: Option<&Value>) -> Option<String> {
+    let text = match value? {
+        Value::String(s) => s.trim().to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::Object(map) => {
+            return ["name", "@value", "url"]
+                .iter()
+                .find_map(|key| json_text(map.get(*key)));
+        }
+        Value::Array(items) => return items.iter().find_map(|v| json_text(Some(v))),
+        _ => return None,
+    };
+    (!text.is_empty()).then_some(text)
+}
+
+fn json_list(value: Option<&Value>) -> Option<String> {
+    match value? {
+        Value::Array(items) => {
+            let parts: Vec<String> = items.iter().filter_map(|v| json_text(Some(v))).collect();
+            (!parts.is_empty()).then(|| parts.join(", "))
+        }
+        other => json_text(Some(other)),
+    }
+}
+
+fn json_number(value: Option<&Value>) -> Option<f64> {
+    match value? {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.replace([' ', ','], "").parse().ok(),
+        _ => None,
+    }
+}
+
+fn json_location(value: Option<&Value>) -> Option<String> {
+    let places: Vec<String> = match value? {
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|v| json_location(Some(v)))
+            .collect(),
+        Value::Object(place) => {
+            let address = place.get("address");
+            let parts: Vec<String> = match address {
+                Some(Value::Object(addr)) => ["addressLocality", "addressRegion", "addressCountry"]
+                    .iter()
+                    .filter_map(|key| json_text(addr.get(*key)))
+                    .collect(),
+                Some(other) => json_text(Some(other)).into_iter().collect(),
+                None => json_text(place.get("name")).into_iter().collect(),
+            };
+            (!parts.is_empty())
+                .then(|| parts.join(", "))
+                .into_iter()
+                .collect()
+        }
+        other => json_text(Some(other)).into_iter().collect(),
+    };
+    (!places.is_empty()).then(|| places.join(" / "))
+}
+
+fn json_salary(value: Option<&Value>) -> Option<SalaryRange> {
+    let amount = value?;
+    let quantity = amount.get("value");
+
+    let (min, max, unit) = match quantity {
+        Some(q @ Value::Object(_)) => (
+            json_number(q.get("minValue")).or_else(|| json_number(q.get("value"))),
+            json_number(q.get("maxValue")).or_else(|| json_number(q.get("value"))),
+            json_text(q.get("unitText")),
+        ),
+        other => {
+            let v = json_number(other);
+            (v, v, None)
+        }
+    };
+
+    if min.is_none() && max.is_none() {
+        return None;
+    }
+
+    Some(SalaryRange {
+        min,
+        max,
+        currency: json_text(amount.get("currency")),
+        unit: unit.or_else(|| json_text(amount.get("unitText"))),
+    })
+}
+
+// --- Microdata -------------------------------------------------------------
+
+fn from_microdata(doc: &Html) -> Option<JobPosting> {
+    let selector = Selector::parse("[itemscope][itemtype*='schema.org/JobPosting']").ok()?;
+    let root = doc.select(&selector).next()?;
+
+    let salary_min = prop(&root, "[itemprop='baseSalary'] [itemprop='minValue']")
+        .or_else(|| prop(&root, "[itemprop='baseSalary'] [itemprop='value']"))
+        .and_then(|v| v.replace([' ', ','], "").parse().ok());
+    let salary_max = prop(&root, "[itemprop='baseSalary'] [itemprop='maxValue']")
+        .or_else(|| prop(&root, "[itemprop='baseSalary'] [itemprop='value']"))
+        .and_then(|v| v.replace([' ', ','], "").parse().ok());
+    let salary = (salary_min.is_some() || salary_max.is_some()).then(|| SalaryRange {
+        min: salary_min,
+        max: salary_max,
+        currency: prop(&root, "[itemprop='baseSalary'] [itemprop='currency']")
+            .or_else(|| prop(&root, "[itemprop='salaryCurrency']")),
+        unit: prop(&root, "[itemprop='baseSalary'] [itemprop='unitText']"),
+    });
+
+    let location = {
+        let parts: Vec<String> = ["addressLocality", "addressRegion", "addressCountry"]
+            .iter()
+            .filter_map(|p| {
+                prop(
+                    &root,
+                    &format!("[itemprop='jobLocation'] [itemprop='{}']", p),
+                )
+            })
+            .collect();
+        if parts.is_empty() {
+            prop(&root, "[itemprop='jobLocation']")
+        } else {
+            Some(parts.join(", "))
+        }
+    };
+
+    Some(JobPosting {
+        title: prop(&root, "[itemprop='title']"),
+        employment_type: prop(&root, "[itemprop='employmentType']"),
+        location,
+        remote: prop(&root, "[itemprop='jobLocationType']")
+            .is_some_and(|t| t.eq_ignore_ascii_case("TELECOMMUTE")),
+        salary,
+        date_posted: prop(&root, "[itemprop='datePosted']"),
+        valid_through: prop(&root, "[itemprop='validThrough']"),
+        hiring_organization: prop(&root, "[itemprop='hiringOrganization'] [itemprop='name']")
+            .or_else(|| prop(&root, "[itemprop='hiringOrganization']")),
+        hiring_organization_logo: prop(&root, "[itemprop='hiringOrganization'] [itemprop='logo']"),
+        description: prop(&root, "[itemprop='description']"),
+    })
+}
+
+/// Microdata value: `content`, `datetime`, `href`/`src`, then text content.
+fn prop(root: &ElementRef, selector: &str) -> Option<String> {
+    let selector = Selector::parse(selector).ok()?;
+    let el = root.select(&selector).next()?;
+    let value = el.value();
+    let text = value
+        .attr("content")
+        .or_else(|| value.attr("datetime"))
+        .or_else(|| value.attr("href"))
+        .or_else(|| value.attr("src"))
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| collapse_spaces(&el.text().collect::<String>()));
+    let text = text.trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+fn strip_html(html: &str) -> String {
+    let fragment = Html::parse_fragment(html);
+    let text = collapse_spaces(&fragment.root_element().text().collect::<Vec<_>>().join(" "));
+    // Some boards entity-encode the markup inside JSON-LD, so one pass only decodes it
+    if text.contains('<') && text.contains('>') && text != html {
+        strip_html(&text)
+    } else {
+        text
+    }
+}
+
+pub(super) fn collapse_spaces(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(html: &str) -> JobPosting {
+        extract_job_posting(&Html::parse_document(html)).unwrap()
+    }
+
+    fn ld(json: &str) -> String {
+        format!(
+            "<html><head><script type=\"application/ld+json\">{}</script></head></html>",
+            json
+        )
+    }
+
+    #[test]
+    fn finds_job_inside_graph_and_joins_arrays() {
+        let job = parse(&ld(r#"{
+            "@context": "https://schema.org",
+            "@graph": [
+                { "@type": "WebPage", "name": "Careers" },
+                {
+                    "@type": ["JobPosting"],
+                    "title": "Rust Developer",
+                    "employmentType": ["FULL_TIME", "CONTRACTOR"],
+                    "hiringOrganization": { "@type": "Organization", "name": "Acme", "logo": { "url": "https://acme.fi/logo.png" } },
+                    "jobLocation": [
+                        { "address": { "addressLocality": "Helsinki", "addressCountry": "FI" } },
+                        { "address": { "addressLocality": "Tampere", "addressCountry": "FI" } }
+                    ]
+                }
+            ]
+        }"#));
+
+        assert_eq!(job.title.as_deref(), Some("Rust Developer"));
+        assert_eq!(
+            job.employment_type.as_deref(),
+            Some("FULL_TIME, CONTRACTOR")
+        );
+        assert_eq!(job.location.as_deref(), Some("Helsinki, FI / Tampere, FI"));
+        assert_eq!(job.hiring_organization.as_deref(), Some("Acme"));
+        assert_eq!(
+            job.hiring_organization_logo.as_deref(),
+            Some("https://acme.fi/logo.png")
+        );
+        assert!(!job.remote);
+    }
+
+    #[test]
+    fn reads_quantitative_value_salary() {
+        let job = parse(&ld(r#"{
+            "@type": "JobPosting",
+            "title": "Engineer",
+            "baseSalary": {
+                "@type": "MonetaryAmount",
+                "currency": "EUR",
+                "value": { "@type": "QuantitativeValue", "minValue": 4000, "maxValue": "5 200", "unitText": "MONTH" }
+            }
+        }"#));
+
+        let salary = job.salary.unwrap();
+        assert_eq!(salary.min, Some(4000.0));
+        assert_eq!(salary.max, Some(5200.0));
+        assert_eq!(salary.display(), "4000–5200 EUR / MONTH");
+    }
+
+    #[test]
+    fn telecommute_means_remote() {
+        let job = parse(&ld(
+            r#"{ "@type": "JobPosting", "title": "Remote Dev", "jobLocationType": "TELECOMMUTE" }"#,
+        ));
+        assert!(job.remote);
+    }
+
+    #[test]
+    fn decodes_entity_encoded_description() {
+        let job = parse(&ld(r#"{
+            "@type": "JobPosting",
+            "title": "Dev",
+            "description": "&lt;p&gt;Build &lt;strong&gt;fast&lt;/strong&gt; things&lt;/p&gt;&lt;ul&gt;&lt;li&gt;Rust&lt;/li&gt;&lt;/ul&gt;"
+        }"#));
+        assert_eq!(job.description.as_deref(), Some("Build fast things Rust"));
+    }
+
+    #[test]
+    fn falls_back_to_microdata() {
+        let job = parse(
+            r#"<html><body>
+            <div itemscope itemtype="https://schema.org/JobPosting">
+                <h1 itemprop="title">Backend Developer</h1>
+                <meta itemprop="employmentType" content="FULL_TIME">
+                <time itemprop="datePosted" datetime="2026-01-10">10.1.2026</time>
+                <div itemprop="hiringOrganization" itemscope itemtype="https://schema.org/Organization">
+                    <span itemprop="name">Acme Oy</span>
+                </div>
+                <div itemprop="jobLocation" itemscope>
+                    <span itemprop="addressLocality">Oulu</span>
+                    <span itemprop="addressCountry">Finland</span>
+                </div>
+                <div itemprop="baseSalary" itemscope>
+                    <meta itemprop="currency" content="EUR">
+                    <meta itemprop="minValue" content="3500">
+                    <meta itemprop="maxValue" content="4500">
+                </div>
+                <div itemprop="description">Write   Rust
+                    every day.</div>
+            </div>
+            </body></html>"#,
+        );
+
+        assert_eq!(job.title.as_deref(), Some("Backend Developer"));
+        assert_eq!(job.employment_type.as_deref(), Some("FULL_TIME"));
+        assert_eq!(job.date_posted.as_deref(), Some("2026-01-10"));
+        assert_eq!(job.hiring_organization.as_deref(), Some("Acme Oy"));
+        assert_eq!(job.location.as_deref(), Some("Oulu, Finland"));
+        assert_eq!(job.salary.unwrap().display(), "3500–4500 EUR");
+        assert_eq!(job.description.as_deref(), Some("Write Rust every day."));
+    }
+
+    #[test]
+    fn no_posting_on_plain_pages() {
+        let doc = Html::parse_document("<html><body><h1>About us</h1></body></html>");
+        assert!(extract_job_posting(&doc).is_none());
+    }
+}
@@ -0,0 +1,305 @@
+mod job_posting;
+mod parsers;
+
+use crate::error::{AppError, AppResult};
+use crate::extract::{Json, Query};
+use crate::routes::auth::Claims;
+use crate::services::{logos, safe_fetch};
+use crate::AppState;
+use axum::extract::State;
+use job_posting::JobPosting;
+use reqwest::Url;
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::time::Duration;
+
+const MAX_PAGE_BYTES: usize = 2 * 1024 * 1024; // Meta tags live in <head>, no need for more
+const CACHE_TTL_DAYS: i32 = 7;
+
+#[derive(Deserialize)]
+pub struct IntelQuery {
+    pub url: String,
+    /// Skip the cache and scan the site again
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CompanyIntel {
+    pub company_name: Option<String>,
+    pub description: Option<String>,
+    pub logo_url: Option<String>,
+    pub job: Option<JobPosting>,
+    /// Which extractor produced the result ("generic" or a platform parser name)
+    #[serde(skip_deserializing)]
+    pub source: &'static str,
+}
+
+impl CompanyIntel {
+    /// Field-wise merge: keeps our values and backfills gaps from `fallback`.
+    fn or(self, fallback: CompanyIntel) -> CompanyIntel {
+        CompanyIntel {
+            company_name: self.company_name.or(fallback.company_name),
+            description: self.description.or(fallback.description),
+            logo_url: self.logo_url.or(fallback.logo_url),
+            job: match (self.job, fallback.job) {
+                (Some(ours), Some(theirs)) => Some(ours.or(theirs)),
+                (ours, theirs) => ours.or(theirs),
+            },
+            source: self.source,
+        }
+    }
+}
+
+pub async fn get_company_intel(
+    State(state): State<AppState>,
+    claims: Claims,
+    Query(params): Query<IntelQuery>,
+) -> AppResult<Json<serde_json::Value>> {
+    Ok(Json(
+        scan(&state, &claims.sub, &params.url, params.refresh).await?,
+    ))
+}
+
+/// Intel for `url`, from the cache unless `refresh` is set. Fetches count
+/// against `requested_by`'s rate limit. The result deserializes as `CompanyIntel`.
+pub async fn scan(
+    state: &AppState,
+    requested_by: &str,
+    url: &str,
+    refresh: bool,
+) -> AppResult<serde_json::Value> {
+    let Some(key) = cache_key(url) else {
+        return Err(safe_fetch::FetchError::InvalidUrl.into());
+    };
+
+    if !refresh {
+        if let Some(cached) = cached_intel(&state.pool, &key).await {
+            tracing::info!("INTEL: Cache hit for {}", key);
+            return Ok(cached);
+        }
+    }
+
+    // Only real fetches count against the limit, cache hits are free
+    if !state.intel_limiter.check(requested_by) {
+        return Err(AppError::TooManyRequests(Some(Duration::from_secs(60))));
+    }
+
+    let page = safe_fetch::fetch(url, MAX_PAGE_BYTES)
+        .await
+        .inspect_err(|e| tracing::warn!("INTEL: Fetch of {} failed: {}", url, e))?;
+
+    // Html is not Send, so parse in its own scope before awaiting anything else
+    let mut intel = {
+        let body = String::from_utf8_lossy(&page.body);
+        let document = Html::parse_document(&body);
+        extract_intel(&page.final_url, &document)
+    };
+
+    if let Some(remote) = intel.logo_url.as_deref().filter(|u| logos::is_remote(u)) {
+        if let Some(local) = logos::mirror_logo(remote).await {
+            intel.logo_url = Some(local);
+        }
+    }
+
+    let value = serde_json::to_value(&intel)
+        .map_err(|e| AppError::internal(format!("Failed to serialize intel: {}", e)))?;
+    store_intel(&state.pool, &key, &value).await;
+
+    Ok(value)
+}
+
+/// `https://www.Acme.fi/careers/` and `acme.fi/careers` share an entry. The path is
+/// kept because job board postings on the same host are different results, and so
+/// is the query (`?gh_jid=`, `?jobId=`), minus tracking parameters, sorted.
+fn cache_key(raw: &str) -> Option<String> {
+    let url = Url::parse(raw.trim()).ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    let host = url.host_str()?.to_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+    let mut key = format!("{}{}", host, url.path().trim_end_matches('/'));
+
+    let mut params: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(name, _)| !is_tracking_param(name))
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect();
+    params.sort();
+    if !params.is_empty() {
+        let query = url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(params)
+            .finish();
+        key.push('?');
+        key.push_str(&query);
+    }
+    Some(key)
+}
+
+/// Campaign and click-tracking parameters that never change the page content.
+fn is_tracking_param(name: &str) -> bool {
+    const TRACKING: &[&str] = &[
+        "fbclid", "gclid", "msclkid", "mc_cid", "mc_eid", "ref", "source",
+    ];
+    name.starts_with("utm_") || TRACKING.contains(&name)
+}
+
+async fn cached_intel(pool: &PgPool, key: &str) -> Option<serde_json::Value> {
+    sqlx::query_scalar::<_, serde_json::Value>(
+        "SELECT intel FROM intel_cache WHERE cache_key = $1 AND fetched_at > NOW() - make_interval(days => $2)",
+    )
+    .bind(key)
+    .bind(CACHE_TTL_DAYS)
+    .fetch_optional(pool)
+    .await
+    .unwrap_or_else(|e| {
+        tracing::error!("INTEL: Cache lookup failed: {:?}", e);
+        None
+    })
+}
+
+async fn store_intel(pool: &PgPool, key: &str, intel: &serde_json::Value) {
+    if let Err(e) = sqlx::query(
+        "INSERT INTO intel_cache (cache_key, intel, fetched_at) VALUES ($1, $2, NOW())
+         ON CONFLICT (cache_key) DO UPDATE SET intel = EXCLUDED.intel, fetched_at = EXCLUDED.fetched_at",
+    )
+    .bind(key)
+    .bind(intel)
+    .execute(pool)
+    .await
+    {
+        tracing::error!("INTEL: Failed to cache result: {:?}", e);
+    }
+}
+
+/// Runs the platform parser registered for `url` (if any) and backfills
+/// whatever it could not find from the generic meta-tag/JSON-LD pass.
+fn extract_intel(url: &Url, document: &Html) -> CompanyIntel {
+    let generic = extract_generic(document);
+
+    let intel = match parsers::find_parser(url) {
+        Some(parser) => {
+            tracing::info!("INTEL: Using {} parser for {}", parser.name(), url);
+            parser.parse(url, document).or(generic)
+        }
+        None => generic,
+    };
+
+    CompanyIntel {
+        logo_url: intel
+            .logo_url
+            .map(|logo| make_absolute_url(url.as_str(), &logo)),
+        ..intel
+    }
+}
+
+fn extract_generic(document: &Html) -> CompanyIntel {
+    // 0. Structured schema.org JobPosting (JSON-LD or microdata), if the page has one
+    let job = job_posting::extract_job_posting(document);
+
+    // 1. Company Name (hiring organization, og:site_name or title)
+    let company_name = job
+        .as_ref()
+        .and_then(|j| j.hiring_organization.clone())
+        .or_else(|| extract_meta(document, "property", "og:site_name"))
+        .or_else(|| extract_title(document))
+        .map(|s| s.trim().split('|').next().unwrap_or(&s).trim().to_string());
+
+    // 2. Description (og:description or meta description)
+    let description = extract_meta(document, "property", "og:description")
+        .or_else(|| extract_meta(document, "name", "description"));
+
+    // 3. Logo (og:image or favicon or apple-touch-icon)
+    let logo_url = job
+        .as_ref()
+        .and_then(|j| j.hiring_organization_logo.clone())
+        .or_else(|| extract_meta(document, "property", "og:image"))
+        .or_else(|| extract_link_rel(document, "apple-touch-icon"))
+        .or_else(|| extract_link_rel(document, "icon"))
+        .or_else(|| extract_link_rel(document, "shortcut icon"));
+
+    CompanyIntel {
+        company_name,
+        description,
+        logo_url,
+        job,
+        source: "generic",
+    }
+}
+
+fn extract_meta(doc: &Html, attr: &str, value: &str) -> Option<String> {
+    let selector = Selector::parse(&format!("meta[{}='{}']", attr, value)).ok()?;
+    doc.select(&selector)
+        .next()?
+        .value()
+        .attr("content")
+        .map(|s| s.to_string())
+}
+
+fn extract_title(doc: &Html) -> Option<String> {
+    let selector = Selector::parse("title").ok()?;
+    doc.select(&selector)
+        .next()
+        .map(|el| el.text().collect::<Vec<_>>().join(""))
+}
+
+fn extract_link_rel(doc: &Html, rel: &str) -> Option<String> {
+    let selector = Selector::parse(&format!("link[rel='{}']", rel)).ok()?;
+    doc.select(&selector)
+        .next()?
+        .value()
+        .attr("href")
+        .map(|s| s.to_string())
+}
+
+fn make_absolute_url(base: &str, relative: &str) -> String {
+    if relative.starts_with("http") {
+        return relative.to_string();
+    }
+
+    let base_url = reqwest::Url::parse(base).ok();
+    if let Some(base_url) = base_url {
+        if let Ok(abs) = base_url.join(relative) {
+            return abs.to_string();
+        }
+    }
+    relative.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::cache_key;
+
+    #[test]
+    fn cache_key_normalizes_host_and_path() {
+        assert_eq!(
+            cache_key("https://www.Acme.fi/").as_deref(),
+            Some("acme.fi")
+        );
+        assert_eq!(cache_key("http://acme.fi").as_deref(), Some("acme.fi"));
+        assert_eq!(
+            cache_key("https://jobs.lever.co/acme/123/?utm_source=x").as_deref(),
+            Some("jobs.lever.co/acme/123")
+        );
+        assert_eq!(cache_key("ftp://acme.fi"), None);
+        assert_eq!(cache_key("not a url"), None);
+    }
+
+    #[test]
+    fn cache_key_keeps_identifying_query_params() {
+        assert_eq!(
+            cache_key("https://acme.fi/careers?gh_jid=123&utm_campaign=x").as_deref(),
+            Some("acme.fi/careers?gh_jid=123")
+        );
+        assert_ne!(
+            cache_key("https://acme.fi/careers?gh_jid=123"),
+            cache_key("https://acme.fi/careers?gh_jid=456")
+        );
+        assert_eq!(
+            cache_key("https://acme.fi/jobs?jobId=7&lang=fi"),
+            cache_key("https://www.acme.fi/jobs/?lang=fi&jobId=7&fbclid=abc")
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use super::{first_attr, first_text, host_is, parse_salary_text, SiteParser};
+use crate::routes::intel::job_posting::JobPosting;
+use crate::routes::intel::CompanyIntel;
+use reqwest::Url;
+use scraper::Html;
+
+/// `duunitori.fi/tyopaikat/tyo/<slug>`. The page's og: tags describe Duunitori
+/// itself, so everything about the employer has to come from the body.
+pub struct Duunitori;
+
+impl SiteParser for Duunitori {
+    fn name(&self) -> &'static str {
+        "duunitori"
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        host_is(url, "duunitori.fi")
+    }
+
+    fn parse(&self, _url: &Url, doc: &Html) -> CompanyIntel {
+        let company_name = first_text(doc, &[".header__company", ".header__info a"]);
+        let location = first_text(doc, &[".header__location"]);
+
+        CompanyIntel {
+            company_name: company_name.clone(),
+            logo_url: first_attr(doc, &[".header__logo img"], "src"),
+            job: Some(JobPosting {
+                title: first_text(doc, &["h1.header__title", "h1"]),
+                remote: location
+                    .as_deref()
+                    .is_some_and(|l| l.to_lowercase().contains("etätyö")),
+                location,
+                employment_type: first_text(doc, &[".job-info__worktime"]),
+                salary: first_text(doc, &[".job-info__salary"]).and_then(|s| parse_salary_text(&s)),
+                description: first_text(
+                    doc,
+                    &[".description-box .description", ".description-box"],
+                ),
+                hiring_organization: company_name,
+                ..Default::default()
+            }),
+            source: self.name(),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::intel::extract_intel;
+
+    #[test]
+    fn parses_fixture() {
+        let url =
+            Url::parse("https://duunitori.fi/tyopaikat/tyo/ohjelmistokehittaja-18012345").unwrap();
+        let doc = Html::parse_document(include_str!("fixtures/duunitori.html"));
+
+        let intel = Duunitori.parse(&url, &doc);
+        let job = intel.job.unwrap();
+        let salary = job.salary.unwrap();
+
+        assert_eq!(intel.company_name.as_deref(), Some("Reaktor"));
+        assert_eq!(job.title.as_deref(), Some("Ohjelmistokehittäjä"));
+        assert_eq!(job.location.as_deref(), Some("Helsinki"));
+        assert_eq!(job.employment_type.as_deref(), Some("Kokoaikatyö"));
+        assert_eq!((salary.min, salary.max), (Some(4000.0), Some(5500.0)));
+        assert_eq!(salary.unit.as_deref(), Some("MONTH"));
+    }
+
+    #[test]
+    fn employer_logo_wins_over_board_og_image() {
+        let url =
+            Url::parse("https://duunitori.fi/tyopaikat/tyo/ohjelmistokehittaja-18012345").unwrap();
+        let doc = Html::parse_document(include_str!("fixtures/duunitori.html"));
+
+        let intel = extract_intel(&url, &doc);
+
+        assert_eq!(intel.company_name.as_deref(), Some("Reaktor"));
+        assert_eq!(
+            intel.logo_url.as_deref(),
+            Some("https://duunitori.fi/media/logos/reaktor.png")
+        );
+    }
+}
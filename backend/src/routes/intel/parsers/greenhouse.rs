@@ -0,0 +1,78 @@
+use super::{first_attr, first_path_segment, first_text, host_is, humanize_slug, SiteParser};
+use crate::routes::intel::job_posting::JobPosting;
+use crate::routes::intel::CompanyIntel;
+use reqwest::Url;
+use scraper::Html;
+
+/// `boards.greenhouse.io/<company>/jobs/<id>` and the newer `job-boards.greenhouse.io`.
+pub struct Greenhouse;
+
+impl SiteParser for Greenhouse {
+    fn name(&self) -> &'static str {
+        "greenhouse"
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        host_is(url, "greenhouse.io")
+    }
+
+    fn parse(&self, url: &Url, doc: &Html) -> CompanyIntel {
+        let company_name = first_text(doc, &[".company-name", ".job__company"])
+            .map(|name| name.trim_start_matches("at ").trim().to_string())
+            .or_else(|| first_path_segment(url).map(|slug| humanize_slug(&slug)));
+
+        CompanyIntel {
+            company_name: company_name.clone(),
+            logo_url: first_attr(doc, &["#logo img", ".logo img"], "src"),
+            job: Some(JobPosting {
+                title: first_text(
+                    doc,
+                    &["h1.app-title", ".job__title h1", "h1.section-header"],
+                ),
+                location: first_text(doc, &["#header .location", ".job__location"]),
+                description: first_text(doc, &["#content", ".job__description"]),
+                hiring_organization: company_name,
+                ..Default::default()
+            }),
+            source: self.name(),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_fixture() {
+        let url = Url::parse("https://boards.greenhouse.io/acmerobotics/jobs/4012345").unwrap();
+        let doc = Html::parse_document(include_str!("fixtures/greenhouse.html"));
+
+        let intel = Greenhouse.parse(&url, &doc);
+        let job = intel.job.unwrap();
+
+        assert_eq!(intel.company_name.as_deref(), Some("Acme Robotics"));
+        assert_eq!(
+            intel.logo_url.as_deref(),
+            Some("https://s3.amazonaws.com/greenhouse/logos/acme.png")
+        );
+        assert_eq!(job.title.as_deref(), Some("Senior Rust Engineer"));
+        assert_eq!(job.location.as_deref(), Some("Helsinki, Finland"));
+        assert!(job
+            .description
+            .unwrap()
+            .contains("You will own the motion-control stack, written in Rust."));
+    }
+
+    #[test]
+    fn falls_back_to_url_slug_for_company() {
+        let url = Url::parse("https://job-boards.greenhouse.io/acme-robotics/jobs/1").unwrap();
+        let doc = Html::parse_document("<h1 class='section-header'>Dev</h1>");
+
+        let intel = Greenhouse.parse(&url, &doc);
+
+        assert_eq!(intel.company_name.as_deref(), Some("Acme Robotics"));
+        assert_eq!(intel.job.unwrap().title.as_deref(), Some("Dev"));
+    }
+}
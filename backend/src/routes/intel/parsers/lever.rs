@@ -0,0 +1,87 @@
+use super::{first_attr, first_path_segment, first_text, host_is, humanize_slug, SiteParser};
+use crate::routes::intel::job_posting::JobPosting;
+use crate::routes::intel::CompanyIntel;
+use reqwest::Url;
+use scraper::Html;
+
+/// `jobs.lever.co/<company>/<posting-id>` (and the `jobs.eu.lever.co` region).
+pub struct Lever;
+
+impl SiteParser for Lever {
+    fn name(&self) -> &'static str {
+        "lever"
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        host_is(url, "lever.co")
+    }
+
+    fn parse(&self, url: &Url, doc: &Html) -> CompanyIntel {
+        let company_name = first_attr(doc, &[".main-header-logo img"], "alt")
+            .map(|alt| alt.trim_end_matches(" logo").trim().to_string())
+            .or_else(|| first_path_segment(url).map(|slug| humanize_slug(&slug)));
+
+        // Category labels carry a trailing " /" separator in Lever's markup
+        let category = |class: &str| {
+            first_text(doc, &[&format!(".posting-categories .{}", class)])
+                .map(|v| v.trim_end_matches('/').trim().to_string())
+        };
+
+        CompanyIntel {
+            company_name: company_name.clone(),
+            logo_url: first_attr(doc, &[".main-header-logo img"], "src"),
+            job: Some(JobPosting {
+                title: first_text(doc, &[".posting-headline h2"]),
+                location: category("location"),
+                employment_type: category("commitment"),
+                remote: category("workplaceTypes")
+                    .is_some_and(|w| w.to_lowercase().contains("remote")),
+                description: first_text(
+                    doc,
+                    &[
+                        "[data-qa='job-description']",
+                        ".section-wrapper .section.page-centered",
+                    ],
+                ),
+                hiring_organization: company_name,
+                ..Default::default()
+            }),
+            source: self.name(),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_fixture() {
+        let url = Url::parse("https://jobs.lever.co/wolt/6c1e1b1e-0000-4d2f").unwrap();
+        let doc = Html::parse_document(include_str!("fixtures/lever.html"));
+
+        let intel = Lever.parse(&url, &doc);
+        let job = intel.job.unwrap();
+
+        assert_eq!(intel.company_name.as_deref(), Some("Wolt"));
+        assert_eq!(job.title.as_deref(), Some("Backend Engineer, Payments"));
+        assert_eq!(job.location.as_deref(), Some("Helsinki"));
+        assert_eq!(job.employment_type.as_deref(), Some("Full-time"));
+        assert!(!job.remote);
+        assert!(job.description.unwrap().starts_with("We move money"));
+    }
+
+    #[test]
+    fn detects_remote_workplace_type() {
+        let url = Url::parse("https://jobs.eu.lever.co/acme/1").unwrap();
+        let doc = Html::parse_document(
+            "<div class='posting-categories'><div class='workplaceTypes'>Remote</div></div>",
+        );
+
+        let intel = Lever.parse(&url, &doc);
+
+        assert_eq!(intel.company_name.as_deref(), Some("Acme"));
+        assert!(intel.job.unwrap().remote);
+    }
+}
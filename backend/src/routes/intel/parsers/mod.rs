@@ -0,0 +1,199 @@
+mod duunitori;
+mod greenhouse;
+mod lever;
+mod recruitee;
+mod teamtailor;
+mod workday;
+
+use super::job_posting::{collapse_spaces, SalaryRange};
+use super::CompanyIntel;
+use reqwest::Url;
+use scraper::{Html, Selector};
+
+/// A platform-specific extractor. Parsers only fill in what they can read
+/// reliably; anything left as `None` is backfilled by the generic pass.
+pub trait SiteParser: Sync {
+    fn name(&self) -> &'static str;
+    fn matches(&self, url: &Url) -> bool;
+    fn parse(&self, url: &Url, doc: &Html) -> CompanyIntel;
+}
+
+static REGISTRY: &[&dyn SiteParser] = &[
+    &greenhouse::Greenhouse,
+    &lever::Lever,
+    &workday::Workday,
+    &teamtailor::Teamtailor,
+    &recruitee::Recruitee,
+    &duunitori::Duunitori,
+];
+
+pub fn find_parser(url: &Url) -> Option<&'static dyn SiteParser> {
+    REGISTRY.iter().copied().find(|parser| parser.matches(url))
+}
+
+/// True for `domain` itself and any of its subdomains.
+fn host_is(url: &Url, domain: &str) -> bool {
+    url.host_str()
+        .is_some_and(|host| host == domain || host.ends_with(&format!(".{}", domain)))
+}
+
+/// First path segment, e.g. the company slug in `jobs.lever.co/<company>/<id>`.
+fn first_path_segment(url: &Url) -> Option<String> {
+    url.path_segments()?
+        .find(|s| !s.is_empty())
+        .map(|s| s.to_string())
+}
+
+/// First DNS label, e.g. the company slug in `<company>.recruitee.com`.
+fn subdomain(url: &Url) -> Option<String> {
+    let host = url.host_str()?;
+    let (label, _) = host.split_once('.')?;
+    (label != "www").then(|| label.to_string())
+}
+
+/// `acme-corp` -> `Acme Corp`
+fn humanize_slug(slug: &str) -> String {
+    slug.split(['-', '_'])
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Whitespace-collapsed text of the first element matching any of `selectors`.
+fn first_text(doc: &Html, selectors: &[&str]) -> Option<String> {
+    selectors.iter().find_map(|sel| {
+        let selector = Selector::parse(sel).ok()?;
+        let el = doc.select(&selector).next()?;
+        let text = collapse_spaces(&el.text().collect::<Vec<_>>().join(" "));
+        (!text.is_empty()).then_some(text)
+    })
+}
+
+fn first_attr(doc: &Html, selectors: &[&str], attr: &str) -> Option<String> {
+    selectors.iter().find_map(|sel| {
+        let selector = Selector::parse(sel).ok()?;
+        doc.select(&selector)
+            .find_map(|el| el.value().attr(attr))
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    })
+}
+
+/// Parses free-text salary labels like `3 500 - 4 500 EUR / month` or `4 000 – 5 500 € / kk`.
+fn parse_salary_text(text: &str) -> Option<SalaryRange> {
+    let (amount, unit) = match text.split_once('/') {
+        Some((amount, unit)) => (amount, Some(unit.trim())),
+        None => (text, None),
+    };
+
+    // Digit runs may contain thousands separators (space, nbsp, narrow nbsp, comma, dot)
+    let mut numbers = Vec::new();
+    let mut current = String::new();
+    for c in amount.chars().chain(std::iter::once('|')) {
+        if c.is_ascii_digit() {
+            current.push(c);
+        } else if !current.is_empty() && matches!(c, ' ' | '\u{a0}' | '\u{202f}' | ',' | '.') {
+            continue;
+        } else if !current.is_empty() {
+            numbers.extend(current.parse::<f64>().ok());
+            current.clear();
+        }
+    }
+    let min = *numbers.first()?;
+    let max = numbers.get(1).copied().unwrap_or(min);
+
+    let upper = amount.to_uppercase();
+    let currency = [
+        ("EUR", "EUR"),
+        ("€", "EUR"),
+        ("USD", "USD"),
+        ("$", "USD"),
+        ("GBP", "GBP"),
+        ("£", "GBP"),
+        ("SEK", "SEK"),
+    ]
+    .iter()
+    .find(|(needle, _)| upper.contains(needle))
+    .map(|(_, code)| code.to_string());
+
+    let unit = unit
+        .filter(|u| !u.is_empty())
+        .map(|u| match u.to_lowercase().as_str() {
+            "month" | "mo" | "kk" | "kuukausi" => "MONTH".to_string(),
+            "year" | "yr" | "annum" | "v" | "vuosi" => "YEAR".to_string(),
+            "hour" | "h" | "tunti" => "HOUR".to_string(),
+            other => other.to_uppercase(),
+        });
+
+    Some(SalaryRange {
+        min: Some(min),
+        max: Some(max),
+        currency,
+        unit,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_routes_by_host() {
+        let cases = [
+            (
+                "https://boards.greenhouse.io/acme/jobs/1",
+                Some("greenhouse"),
+            ),
+            (
+                "https://job-boards.eu.greenhouse.io/acme/jobs/1",
+                Some("greenhouse"),
+            ),
+            ("https://jobs.lever.co/acme/abc", Some("lever")),
+            (
+                "https://acme.wd3.myworkdayjobs.com/en-US/careers/job/1",
+                Some("workday"),
+            ),
+            ("https://acme.teamtailor.com/jobs/1-dev", Some("teamtailor")),
+            ("https://acme.recruitee.com/o/dev", Some("recruitee")),
+            (
+                "https://duunitori.fi/tyopaikat/tyo/dev-123",
+                Some("duunitori"),
+            ),
+            ("https://example.com/careers", None),
+            ("https://notgreenhouse.io/acme", None),
+        ];
+
+        for (url, expected) in cases {
+            let url = Url::parse(url).unwrap();
+            assert_eq!(find_parser(&url).map(|p| p.name()), expected, "{}", url);
+        }
+    }
+
+    #[test]
+    fn parses_salary_labels() {
+        let range = parse_salary_text("3 500 - 4 500 EUR / month").unwrap();
+        assert_eq!((range.min, range.max), (Some(3500.0), Some(4500.0)));
+        assert_eq!(range.currency.as_deref(), Some("EUR"));
+        assert_eq!(range.unit.as_deref(), Some("MONTH"));
+
+        let range = parse_salary_text("4\u{a0}000 – 5\u{a0}500 € / kk").unwrap();
+        assert_eq!((range.min, range.max), (Some(4000.0), Some(5500.0)));
+        assert_eq!(range.currency.as_deref(), Some("EUR"));
+        assert_eq!(range.unit.as_deref(), Some("MONTH"));
+
+        assert!(parse_salary_text("Competitive").is_none());
+    }
+
+    #[test]
+    fn humanizes_slugs() {
+        assert_eq!(humanize_slug("acme-corp"), "Acme Corp");
+        assert_eq!(humanize_slug("wolt_oy"), "Wolt Oy");
+    }
+}
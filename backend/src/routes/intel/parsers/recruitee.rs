@@ -0,0 +1,66 @@
+use super::{
+    first_attr, first_text, host_is, humanize_slug, parse_salary_text, subdomain, SiteParser,
+};
+use crate::routes::intel::job_posting::JobPosting;
+use crate::routes::intel::CompanyIntel;
+use reqwest::Url;
+use scraper::Html;
+
+/// `<company>.recruitee.com/o/<slug>`
+pub struct Recruitee;
+
+impl SiteParser for Recruitee {
+    fn name(&self) -> &'static str {
+        "recruitee"
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        host_is(url, "recruitee.com")
+    }
+
+    fn parse(&self, url: &Url, doc: &Html) -> CompanyIntel {
+        let company_name = first_attr(doc, &[".header .logo img", "a.logo img"], "alt")
+            .or_else(|| subdomain(url).map(|slug| humanize_slug(&slug)));
+
+        CompanyIntel {
+            company_name: company_name.clone(),
+            logo_url: first_attr(doc, &[".header .logo img", "a.logo img"], "src"),
+            job: Some(JobPosting {
+                title: first_text(doc, &[".job-title", "h1"]),
+                location: first_text(doc, &[".job-location"]),
+                employment_type: first_text(doc, &[".job-employment-type"]),
+                remote: first_text(doc, &[".job-remote"])
+                    .is_some_and(|r| r.to_lowercase().contains("remote")),
+                salary: first_text(doc, &[".job-salary"]).and_then(|s| parse_salary_text(&s)),
+                description: first_text(doc, &[".job-description"]),
+                hiring_organization: company_name,
+                ..Default::default()
+            }),
+            source: self.name(),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_fixture() {
+        let url = Url::parse("https://relex.recruitee.com/o/frontend-developer").unwrap();
+        let doc = Html::parse_document(include_str!("fixtures/recruitee.html"));
+
+        let intel = Recruitee.parse(&url, &doc);
+        let job = intel.job.unwrap();
+        let salary = job.salary.unwrap();
+
+        assert_eq!(intel.company_name.as_deref(), Some("RELEX Solutions"));
+        assert_eq!(job.title.as_deref(), Some("Frontend Developer"));
+        assert_eq!(job.location.as_deref(), Some("Helsinki, Finland"));
+        assert_eq!(job.employment_type.as_deref(), Some("Full-time"));
+        assert!(job.remote);
+        assert_eq!((salary.min, salary.max), (Some(3500.0), Some(4500.0)));
+        assert_eq!(salary.currency.as_deref(), Some("EUR"));
+    }
+}
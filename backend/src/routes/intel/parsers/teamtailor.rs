@@ -0,0 +1,77 @@
+use super::{first_attr, first_text, host_is, humanize_slug, subdomain, SiteParser};
+use crate::routes::intel::job_posting::{collapse_spaces, JobPosting};
+use crate::routes::intel::CompanyIntel;
+use reqwest::Url;
+use scraper::{ElementRef, Html, Selector};
+
+/// `<company>.teamtailor.com/jobs/<id>-<slug>`. Career-site themes vary, but the
+/// job facts are always rendered as a `<dt>label</dt><dd>value</dd>` list.
+pub struct Teamtailor;
+
+impl SiteParser for Teamtailor {
+    fn name(&self) -> &'static str {
+        "teamtailor"
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        host_is(url, "teamtailor.com")
+    }
+
+    fn parse(&self, url: &Url, doc: &Html) -> CompanyIntel {
+        let company_name = first_attr(doc, &["header a[href='/'] img"], "alt")
+            .or_else(|| subdomain(url).map(|slug| humanize_slug(&slug)));
+        let remote_status = definition(doc, "Remote status");
+
+        CompanyIntel {
+            company_name: company_name.clone(),
+            logo_url: first_attr(doc, &["header a[href='/'] img"], "src"),
+            job: Some(JobPosting {
+                title: first_text(doc, &["main h1", "h1"]),
+                location: definition(doc, "Locations").or_else(|| definition(doc, "Location")),
+                employment_type: definition(doc, "Employment type"),
+                // "Hybrid Remote" still means showing up at the office
+                remote: remote_status.is_some_and(|s| s.eq_ignore_ascii_case("Fully Remote")),
+                description: first_text(doc, &[".prose"]),
+                hiring_organization: company_name,
+                ..Default::default()
+            }),
+            source: self.name(),
+            ..Default::default()
+        }
+    }
+}
+
+fn definition(doc: &Html, label: &str) -> Option<String> {
+    let selector = Selector::parse("dt").ok()?;
+    doc.select(&selector)
+        .find(|dt| collapse_spaces(&dt.text().collect::<String>()).eq_ignore_ascii_case(label))
+        .and_then(|dt| dt.next_siblings().find_map(ElementRef::wrap))
+        .map(|dd| collapse_spaces(&dd.text().collect::<Vec<_>>().join(" ")))
+        .filter(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_fixture() {
+        let url = Url::parse("https://supercell.teamtailor.com/jobs/123456-game-server-developer")
+            .unwrap();
+        let doc = Html::parse_document(include_str!("fixtures/teamtailor.html"));
+
+        let intel = Teamtailor.parse(&url, &doc);
+        let job = intel.job.unwrap();
+
+        assert_eq!(intel.company_name.as_deref(), Some("Supercell"));
+        assert!(intel.logo_url.unwrap().ends_with("supercell.png"));
+        assert_eq!(job.title.as_deref(), Some("Game Server Developer"));
+        assert_eq!(job.location.as_deref(), Some("Helsinki"));
+        assert_eq!(job.employment_type.as_deref(), Some("Full-time"));
+        assert!(!job.remote);
+        assert_eq!(
+            job.description.as_deref(),
+            Some("Help us keep millions of players connected.")
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use super::{first_text, host_is, humanize_slug, subdomain, SiteParser};
+use crate::routes::intel::job_posting::JobPosting;
+use crate::routes::intel::CompanyIntel;
+use reqwest::Url;
+use scraper::Html;
+
+/// `<tenant>.wd<N>.myworkdayjobs.com`. Workday renders most of the page client-side,
+/// but the server response keeps `data-automation-id` hooks and a JSON-LD block
+/// (picked up by the generic pass).
+pub struct Workday;
+
+impl SiteParser for Workday {
+    fn name(&self) -> &'static str {
+        "workday"
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        host_is(url, "myworkdayjobs.com")
+    }
+
+    fn parse(&self, url: &Url, doc: &Html) -> CompanyIntel {
+        CompanyIntel {
+            company_name: subdomain(url).map(|tenant| humanize_slug(&tenant)),
+            job: Some(JobPosting {
+                title: first_text(doc, &["[data-automation-id='jobPostingHeader']"]),
+                location: first_text(doc, &["[data-automation-id='locations'] dd"]),
+                employment_type: first_text(doc, &["[data-automation-id='time'] dd"]),
+                description: first_text(doc, &["[data-automation-id='jobPostingDescription']"]),
+                ..Default::default()
+            }),
+            source: self.name(),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::intel::extract_intel;
+
+    #[test]
+    fn parses_fixture() {
+        let url =
+            Url::parse("https://nokia.wd3.myworkdayjobs.com/en-US/careers/job/Espoo/R2026-123")
+                .unwrap();
+        let doc = Html::parse_document(include_str!("fixtures/workday.html"));
+
+        let job = Workday.parse(&url, &doc).job.unwrap();
+
+        assert_eq!(job.title.as_deref(), Some("Data Engineer"));
+        assert_eq!(job.location.as_deref(), Some("Espoo"));
+        assert_eq!(job.employment_type.as_deref(), Some("Full time"));
+        assert_eq!(
+            job.description.as_deref(),
+            Some("Build pipelines for 5G telemetry.")
+        );
+    }
+
+    #[test]
+    fn backfills_from_json_ld() {
+        let url =
+            Url::parse("https://nokia.wd3.myworkdayjobs.com/en-US/careers/job/Espoo/R2026-123")
+                .unwrap();
+        let doc = Html::parse_document(include_str!("fixtures/workday.html"));
+
+        let intel = extract_intel(&url, &doc);
+        let job = intel.job.unwrap();
+
+        assert_eq!(intel.source, "workday");
+        assert_eq!(intel.company_name.as_deref(), Some("Nokia"));
+        assert_eq!(job.date_posted.as_deref(), Some("2026-01-12"));
+        assert_eq!(
+            job.valid_through.as_deref(),
+            Some("2026-02-28T23:59:59.000Z")
+        );
+        assert_eq!(job.hiring_organization.as_deref(), Some("Nokia"));
+    }
+}
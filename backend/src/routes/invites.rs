@@ -0,0 +1,176 @@
+use crate::config::Config;
+use crate::error::{AppError, AppResult};
+use crate::extract::{Json, Path};
+use crate::models::invite::{CreateInvite, CreatedInvite, Invite};
+use crate::routes::auth::Claims;
+use crate::services::sessions::hash_token;
+use axum::{extract::State, http::StatusCode, response::IntoResponse};
+use rand::Rng;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const DEFAULT_INVITE_DAYS: i32 = 7;
+const MAX_INVITE_DAYS: i32 = 90;
+const INVITE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegistrationMode {
+    Open,
+    InviteOnly,
+    Closed,
+}
+
+impl RegistrationMode {
+    /// `open` | `invite` | `closed`
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "open" => Some(RegistrationMode::Open),
+            "invite" => Some(RegistrationMode::InviteOnly),
+            "closed" => Some(RegistrationMode::Closed),
+            _ => None,
+        }
+    }
+}
+
+/// The configured mode, except that an instance without any users stays open
+/// so the first admin can sign up.
+pub async fn effective_mode(
+    pool: &PgPool,
+    configured: RegistrationMode,
+) -> Result<RegistrationMode, sqlx::Error> {
+    let has_users: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM users)")
+        .fetch_one(pool)
+        .await?;
+    Ok(if has_users {
+        configured
+    } else {
+        RegistrationMode::Open
+    })
+}
+
+pub fn normalize_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Returns the invite id if `code` is unused and unexpired. Claiming it happens
+/// later, in the same transaction that creates the user.
+pub async fn find_valid(pool: &PgPool, code: &str) -> Result<Option<Uuid>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT id FROM invite_codes WHERE code_hash = $1 AND used_at IS NULL AND expires_at > NOW()",
+    )
+    .bind(hash_token(&normalize_code(code)))
+    .fetch_optional(pool)
+    .await
+}
+
+fn generate_code() -> String {
+    let mut rng = rand::thread_rng();
+    let raw: String = (0..16)
+        .map(|_| INVITE_ALPHABET[rng.gen_range(0..INVITE_ALPHABET.len())] as char)
+        .collect();
+    raw.as_bytes()
+        .chunks(4)
+        .map(|c| std::str::from_utf8(c).unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Public: lets the register page know whether to ask for a code.
+pub async fn get_registration_mode(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+) -> AppResult<impl IntoResponse> {
+    let mode = effective_mode(&pool, config.registration_mode).await?;
+    Ok(Json(serde_json::json!({ "mode": mode })))
+}
+
+pub async fn list_invites(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> AppResult<Json<Vec<Invite>>> {
+    let user_id = claims.user_id()?;
+
+    let invites = sqlx::query_as::<_, Invite>(
+        "SELECT i.id, i.code_hint, i.note, i.expires_at, i.used_at, u.email AS used_by_email, i.created_at
+         FROM invite_codes i LEFT JOIN users u ON u.id = i.used_by
+         WHERE i.created_by = $1
+         ORDER BY i.created_at DESC",
+    )
+    .bind(user_id)
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(invites))
+}
+
+pub async fn create_invite(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Json(payload): Json<CreateInvite>,
+) -> AppResult<(StatusCode, Json<CreatedInvite>)> {
+    let user_id = claims.user_id()?;
+
+    let days = payload
+        .expires_in_days
+        .unwrap_or(DEFAULT_INVITE_DAYS)
+        .clamp(1, MAX_INVITE_DAYS);
+    let code = generate_code();
+    let normalized = normalize_code(&code);
+
+    let (id, expires_at): (Uuid, chrono::NaiveDateTime) = sqlx::query_as(
+        "INSERT INTO invite_codes (created_by, code_hash, code_hint, note, expires_at)
+         VALUES ($1, $2, $3, $4, NOW() + make_interval(days => $5))
+         RETURNING id, expires_at",
+    )
+    .bind(user_id)
+    .bind(hash_token(&normalized))
+    .bind(&code[..4])
+    .bind(
+        payload
+            .note
+            .as_deref()
+            .map(str::trim)
+            .filter(|n| !n.is_empty()),
+    )
+    .bind(days)
+    .fetch_one(&pool)
+    .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreatedInvite {
+            id,
+            code,
+            expires_at,
+        }),
+    ))
+}
+
+/// Deletes an unused invite. Used ones are kept as a record of who let whom in.
+pub async fn delete_invite(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+) -> AppResult<StatusCode> {
+    let user_id = claims.user_id()?;
+
+    let res = sqlx::query(
+        "DELETE FROM invite_codes WHERE id = $1 AND created_by = $2 AND used_at IS NULL",
+    )
+    .bind(id)
+    .bind(user_id)
+    .execute(&pool)
+    .await?;
+
+    if res.rows_affected() > 0 {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(AppError::NotFound("Unused invite"))
+    }
+}
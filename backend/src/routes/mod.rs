@@ -1,6 +1,15 @@
+pub mod account;
+pub mod api_tokens;
 pub mod applications;
 pub mod auth;
+pub mod comments;
 pub mod contact;
+pub mod documents;
+pub mod email_templates;
 pub mod intel;
+pub mod invites;
+pub mod outbound_emails;
+pub mod sessions;
+pub mod two_factor;
 pub mod upload;
 pub mod visitor;
@@ -0,0 +1,71 @@
+use crate::error::{AppError, AppResult};
+use crate::extract::{Json, Path, Query};
+use crate::models::outbound_email::OutboundEmail;
+use crate::routes::auth::Claims;
+use crate::services::email::queue::Status;
+use crate::services::email::EmailQueue;
+use axum::{extract::State, http::StatusCode};
+use serde::Deserialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+const LIST_LIMIT: i64 = 200;
+
+#[derive(Deserialize)]
+pub struct ListEmailsQuery {
+    status: Option<Status>,
+}
+
+/// The caller's newest emails first, optionally only those in one state (e.g. `dead`).
+pub async fn list_emails(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Query(params): Query<ListEmailsQuery>,
+) -> AppResult<Json<Vec<OutboundEmail>>> {
+    let user_id = claims.user_id()?;
+
+    let emails = sqlx::query_as::<_, OutboundEmail>(
+        "SELECT id, template, recipient, subject, status, attempts, last_error, next_attempt_at, created_at, sent_at
+         FROM outbound_emails
+         WHERE user_id = $1 AND ($2::TEXT IS NULL OR status = $2)
+         ORDER BY created_at DESC
+         LIMIT $3",
+    )
+    .bind(user_id)
+    .bind(params.status.map(Status::as_str))
+    .bind(LIST_LIMIT)
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(emails))
+}
+
+/// Queues the email again with a fresh set of attempts.
+pub async fn resend_email(
+    State(pool): State<PgPool>,
+    State(queue): State<EmailQueue>,
+    claims: Claims,
+    Path(id): Path<Uuid>,
+) -> AppResult<StatusCode> {
+    let user_id = claims.user_id()?;
+
+    if queue.resend(id, user_id).await? {
+        return Ok(StatusCode::NO_CONTENT);
+    }
+
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM outbound_emails WHERE id = $1 AND user_id = $2)",
+    )
+    .bind(id)
+    .bind(user_id)
+    .fetch_one(&pool)
+    .await?;
+    if exists {
+        Err(AppError::conflict(
+            "email_sending",
+            "The email is being sent right now",
+        ))
+    } else {
+        Err(AppError::NotFound("Email"))
+    }
+}
@@ -0,0 +1,55 @@
+use crate::error::{AppError, AppResult};
+use crate::extract::{Json, Path};
+use crate::models::session::SessionInfo;
+use crate::routes::auth::Claims;
+use crate::services::sessions;
+use axum::{extract::State, http::StatusCode};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub async fn list_sessions(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> AppResult<Json<Vec<SessionInfo>>> {
+    let user_id = claims.user_id()?;
+    let current = Uuid::parse_str(&claims.sid).ok();
+
+    let list = sqlx::query_as::<_, SessionInfo>(
+        "SELECT id, user_agent, ip_address, created_at, last_used_at, (id = $2) AS current
+         FROM sessions
+         WHERE user_id = $1 AND revoked_at IS NULL AND expires_at > NOW()
+         ORDER BY last_used_at DESC",
+    )
+    .bind(user_id)
+    .bind(current)
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(list))
+}
+
+pub async fn revoke_session(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+) -> AppResult<StatusCode> {
+    let user_id = claims.user_id()?;
+
+    if sessions::revoke(&pool, user_id, id).await? {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(AppError::NotFound("Session"))
+    }
+}
+
+/// Signs out everywhere, including the session making the request.
+pub async fn revoke_all_sessions(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> AppResult<StatusCode> {
+    let user_id = claims.user_id()?;
+
+    let count = sessions::revoke_all(&pool, user_id).await?;
+    tracing::info!("SESSIONS: Revoked {} sessions for {}", count, user_id);
+    Ok(StatusCode::NO_CONTENT)
+}
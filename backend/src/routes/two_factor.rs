@@ -0,0 +1,329 @@
+use crate::error::{AppError, AppResult};
+use crate::extract::Json;
+use crate::routes::auth::{session_response, Claims};
+use crate::services::sessions::{self, ClientInfo};
+use crate::services::totp;
+use crate::AppState;
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::Response,
+};
+use jsonwebtoken::{encode, EncodingKey, Header};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::time::{SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+
+const CHALLENGE_MINUTES: i64 = 5;
+const CHALLENGE_PURPOSE: &str = "2fa";
+const RECOVERY_CODE_COUNT: usize = 10;
+// No 0/O/1/I so codes survive being written down
+const RECOVERY_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+/// Proof that the password step passed. Deliberately has no `sid`, so it can
+/// never be accepted where `Claims` is expected.
+#[derive(Serialize, Deserialize)]
+struct TwoFactorChallenge {
+    sub: String,
+    purpose: String,
+    exp: usize,
+}
+
+#[derive(Deserialize)]
+pub struct SecondStepPayload {
+    challenge_token: String,
+    code: String,
+    #[serde(default)]
+    use_cookies: bool,
+}
+
+#[derive(Deserialize)]
+pub struct CodePayload {
+    code: String,
+}
+
+#[derive(Serialize)]
+pub struct TwoFactorSetup {
+    secret: String,
+    otpauth_uri: String,
+    qr_svg: String,
+}
+
+#[derive(Serialize)]
+pub struct TwoFactorStatus {
+    enabled: bool,
+    recovery_codes_remaining: i64,
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+pub fn issue_challenge(secret: &str, user_id: Uuid) -> Result<String, jsonwebtoken::errors::Error> {
+    let expiration = chrono::Utc::now()
+        .checked_add_signed(chrono::Duration::minutes(CHALLENGE_MINUTES))
+        .expect("valid timestamp")
+        .timestamp() as usize;
+
+    encode(
+        &Header::default(),
+        &TwoFactorChallenge {
+            sub: user_id.to_string(),
+            purpose: CHALLENGE_PURPOSE.to_string(),
+            exp: expiration,
+        },
+        &EncodingKey::from_secret(secret.as_bytes()),
+    )
+}
+
+fn decode_challenge(secret: &str, token: &str) -> Option<Uuid> {
+    let data = jsonwebtoken::decode::<TwoFactorChallenge>(
+        token,
+        &jsonwebtoken::DecodingKey::from_secret(secret.as_bytes()),
+        &jsonwebtoken::Validation::default(),
+    )
+    .ok()?;
+    if data.claims.purpose != CHALLENGE_PURPOSE {
+        return None;
+    }
+    Uuid::parse_str(&data.claims.sub).ok()
+}
+
+/// `XXXXX-XXXXX`, roughly 50 bits each, so a plain SHA-256 is enough to store them.
+fn generate_recovery_codes() -> Vec<String> {
+    let mut rng = rand::thread_rng();
+    (0..RECOVERY_CODE_COUNT)
+        .map(|_| {
+            let raw: String = (0..10)
+                .map(|_| RECOVERY_ALPHABET[rng.gen_range(0..RECOVERY_ALPHABET.len())] as char)
+                .collect();
+            format!("{}-{}", &raw[..5], &raw[5..])
+        })
+        .collect()
+}
+
+fn hash_recovery_code(code: &str) -> String {
+    let normalized: String = code
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    sessions::hash_token(&normalized)
+}
+
+/// Accepts a current TOTP code (each time step only once) or an unused recovery code.
+async fn verify_second_factor(
+    pool: &PgPool,
+    user_id: Uuid,
+    code: &str,
+) -> Result<bool, sqlx::Error> {
+    let secret: Option<String> =
+        sqlx::query_scalar("SELECT totp_secret FROM users WHERE id = $1 AND totp_enabled = true")
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await?
+            .flatten();
+    let Some(secret) = secret else {
+        return Ok(false);
+    };
+
+    if let Some(step) = totp::verify(&secret, code, unix_now()) {
+        let accepted = sqlx::query(
+            "UPDATE users SET totp_last_step = $2 WHERE id = $1 AND (totp_last_step IS NULL OR totp_last_step < $2)",
+        )
+        .bind(user_id)
+        .bind(step)
+        .execute(pool)
+        .await?;
+        return Ok(accepted.rows_affected() > 0);
+    }
+
+    let used = sqlx::query(
+        "UPDATE recovery_codes SET used_at = NOW() WHERE id = (
+            SELECT id FROM recovery_codes WHERE user_id = $1 AND code_hash = $2 AND used_at IS NULL LIMIT 1
+         )",
+    )
+    .bind(user_id)
+    .bind(hash_recovery_code(code))
+    .execute(pool)
+    .await?;
+    if used.rows_affected() > 0 {
+        tracing::info!("2FA: Recovery code used for {}", user_id);
+    }
+    Ok(used.rows_affected() > 0)
+}
+
+/// Second login step: trades the password challenge plus a code for a session.
+pub async fn login_second_step(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<SecondStepPayload>,
+) -> AppResult<Response> {
+    let user_id =
+        decode_challenge(&state.config.jwt_secret, &payload.challenge_token).ok_or_else(|| {
+            AppError::unauthorized("challenge_expired", "Login expired, sign in again")
+        })?;
+
+    // Six digits are guessable without a cap on attempts
+    if !state.two_factor_limiter.check(&user_id.to_string()) {
+        return Err(AppError::TooManyRequests(None));
+    }
+
+    if !verify_second_factor(&state.pool, user_id, &payload.code).await? {
+        return Err(invalid_code());
+    }
+
+    let client = ClientInfo::from_headers(&headers);
+    let tokens =
+        sessions::create_session(&state.pool, &state.config.jwt_secret, user_id, &client).await?;
+    Ok(session_response(&state.config, tokens, payload.use_cookies))
+}
+
+fn invalid_code() -> AppError {
+    AppError::invalid("code", "Invalid code")
+}
+
+pub async fn get_status(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> AppResult<Json<TwoFactorStatus>> {
+    let user_id = claims.user_id()?;
+
+    let (enabled, remaining): (bool, i64) = sqlx::query_as(
+        "SELECT u.totp_enabled,
+            (SELECT COUNT(*) FROM recovery_codes r WHERE r.user_id = u.id AND r.used_at IS NULL)
+         FROM users u WHERE u.id = $1",
+    )
+    .bind(user_id)
+    .fetch_one(&pool)
+    .await?;
+
+    Ok(Json(TwoFactorStatus {
+        enabled,
+        recovery_codes_remaining: remaining,
+    }))
+}
+
+/// Starts enrolment. The secret is stored but not enforced until `confirm` sees a valid code.
+pub async fn setup(State(pool): State<PgPool>, claims: Claims) -> AppResult<Json<TwoFactorSetup>> {
+    let user_id = claims.user_id()?;
+
+    let user: Option<(String, bool)> =
+        sqlx::query_as("SELECT email, totp_enabled FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_optional(&pool)
+            .await?;
+    let Some((email, enabled)) = user else {
+        return Err(AppError::unauthorized("invalid_token", "Invalid token"));
+    };
+    if enabled {
+        return Err(AppError::conflict(
+            "two_factor_enabled",
+            "Two-factor authentication is already enabled",
+        ));
+    }
+
+    let secret = totp::generate_secret();
+    sqlx::query("UPDATE users SET totp_secret = $1, totp_last_step = NULL WHERE id = $2")
+        .bind(&secret)
+        .bind(user_id)
+        .execute(&pool)
+        .await?;
+
+    let otpauth_uri = totp::otpauth_uri(&secret, &email);
+    let qr_svg =
+        totp::qr_svg(&otpauth_uri).ok_or_else(|| AppError::internal("Failed to render QR code"))?;
+
+    Ok(Json(TwoFactorSetup {
+        secret,
+        otpauth_uri,
+        qr_svg,
+    }))
+}
+
+/// Finishes enrolment with a first code and hands out recovery codes (shown once).
+pub async fn confirm(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Json(payload): Json<CodePayload>,
+) -> AppResult<Json<serde_json::Value>> {
+    let user_id = claims.user_id()?;
+
+    let secret: Option<String> =
+        sqlx::query_scalar("SELECT totp_secret FROM users WHERE id = $1 AND totp_enabled = false")
+            .bind(user_id)
+            .fetch_optional(&pool)
+            .await?
+            .flatten();
+    let Some(secret) = secret else {
+        return Err(AppError::bad_request(
+            "no_pending_setup",
+            "No pending two-factor setup",
+        ));
+    };
+
+    let step = totp::verify(&secret, &payload.code, unix_now()).ok_or_else(invalid_code)?;
+
+    let codes = generate_recovery_codes();
+    let mut tx = pool.begin().await?;
+    sqlx::query("UPDATE users SET totp_enabled = true, totp_last_step = $2 WHERE id = $1")
+        .bind(user_id)
+        .bind(step)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("DELETE FROM recovery_codes WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+    for code in &codes {
+        sqlx::query("INSERT INTO recovery_codes (user_id, code_hash) VALUES ($1, $2)")
+            .bind(user_id)
+            .bind(hash_recovery_code(code))
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+
+    tracing::info!("2FA: Enabled for {}", user_id);
+    Ok(Json(serde_json::json!({ "recovery_codes": codes })))
+}
+
+/// Turning 2FA off needs a valid code, not just a (possibly stolen) session.
+pub async fn disable(
+    State(state): State<AppState>,
+    claims: Claims,
+    Json(payload): Json<CodePayload>,
+) -> AppResult<StatusCode> {
+    let user_id = claims.user_id()?;
+    let pool = state.pool;
+
+    // Same budget as the login step, or a stolen session could brute-force the code
+    if !state.two_factor_limiter.check(&user_id.to_string()) {
+        return Err(AppError::TooManyRequests(None));
+    }
+
+    if !verify_second_factor(&pool, user_id, &payload.code).await? {
+        return Err(invalid_code());
+    }
+
+    let mut tx = pool.begin().await?;
+    sqlx::query(
+        "UPDATE users SET totp_enabled = false, totp_secret = NULL, totp_last_step = NULL WHERE id = $1",
+    )
+    .bind(user_id)
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query("DELETE FROM recovery_codes WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+
+    tracing::info!("2FA: Disabled for {}", user_id);
+    Ok(StatusCode::NO_CONTENT)
+}
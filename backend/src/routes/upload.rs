@@ -1,5 +1,9 @@
-use crate::routes::auth::{Claims, ErrorResponse};
-use axum::{extract::Multipart, http::StatusCode, response::IntoResponse, Json};
+use crate::error::{AppError, AppResult};
+use crate::extract::Json;
+use crate::routes::auth::Claims;
+use crate::services::documents;
+use axum::extract::{Multipart, State};
+use sqlx::PgPool;
 use std::path::Path;
 use tokio::fs;
 use uuid::Uuid;
@@ -8,11 +12,14 @@ const MAX_FILE_SIZE: usize = 10 * 1024 * 1024; // 10MB (Matching the main.rs lim
 const ALLOWED_EXTENSIONS: &[&str] = &["pdf", "docx", "png", "jpg", "jpeg", "webp"];
 
 pub async fn upload_file(
-    _claims: Claims, // Enforce authentication
+    State(pool): State<PgPool>,
+    claims: Claims, // Enforce authentication
     mut multipart: Multipart,
-) -> impl IntoResponse {
+) -> AppResult<Json<serde_json::Value>> {
     tracing::info!("UPLOAD: Handler started");
 
+    let user_id = claims.user_id()?;
+
     loop {
         let field_result = multipart.next_field().await;
 
@@ -39,20 +46,7 @@ pub async fn upload_file(
 
                     tracing::info!("UPLOAD: Extension detected: '{}'", extension);
 
-                    if !ALLOWED_EXTENSIONS.contains(&extension.as_str()) {
-                        tracing::error!(
-                            "UPLOAD: Extension '{}' not allowed. Allowed: {:?}",
-                            extension,
-                            ALLOWED_EXTENSIONS
-                        );
-                        return (
-                            StatusCode::BAD_REQUEST,
-                            Json(ErrorResponse {
-                                error: format!("File type .{} not allowed", extension),
-                            }),
-                        )
-                            .into_response();
-                    }
+                    check_extension(&extension)?;
 
                     let data = match field.bytes().await {
                         Ok(d) => d,
@@ -64,56 +58,58 @@ pub async fn upload_file(
 
                     tracing::info!("UPLOAD: File size: {} bytes", data.len());
 
-                    // 2. Validate size
-                    if data.len() > MAX_FILE_SIZE {
-                        tracing::error!("UPLOAD: File too large ({} bytes)", data.len());
-                        return (
-                            StatusCode::PAYLOAD_TOO_LARGE,
-                            Json(ErrorResponse {
-                                error: "File too large (Max 10MB)".to_string(),
-                            }),
-                        )
-                            .into_response();
-                    }
+                    // 2. Validate size and that the content matches the extension
+                    validate(&extension, &data)?;
 
                     // Create uploads directory if it doesn't exist
                     if !Path::new("uploads").exists() {
                         if let Err(e) = fs::create_dir("uploads").await {
-                            tracing::error!("Failed to create uploads directory: {}", e);
-                            return (
-                                StatusCode::INTERNAL_SERVER_ERROR,
-                                Json(ErrorResponse {
-                                    error: "Server configuration error (Uploads dir)".to_string(),
-                                }),
-                            )
-                                .into_response();
+                            return Err(AppError::internal(format!(
+                                "Failed to create uploads directory: {}",
+                                e
+                            )));
                         }
                     }
 
                     // 3. Sanitize filename (Uuid prefix + extension)
-                    let safe_filename = format!("{}.{}", Uuid::new_v4(), extension);
+                    let stem = Uuid::new_v4().to_string();
+                    let safe_filename = format!("{}.{}", stem, extension);
                     let filepath = format!("uploads/{}", safe_filename);
 
                     match fs::write(&filepath, &data).await {
                         Ok(_) => {
                             tracing::info!("File uploaded successfully: {}", filepath);
-                            return (
-                                StatusCode::OK,
-                                Json(serde_json::json!({
-                                    "url": format!("/uploads/{}", safe_filename)
-                                })),
+                            let url = format!("/uploads/{}", safe_filename);
+
+                            // 4. Extract text + thumbnail (best effort, never fails the upload)
+                            let processed =
+                                documents::process_upload(&filepath, &stem, &extension, &data)
+                                    .await;
+
+                            if let Err(e) = sqlx::query(
+                                "INSERT INTO documents (user_id, file_path, original_name, extracted_text, thumbnail_path) VALUES ($1, $2, $3, $4, $5)",
                             )
-                                .into_response();
+                            .bind(user_id)
+                            .bind(&url)
+                            .bind(&file_name)
+                            .bind(&processed.text)
+                            .bind(&processed.thumbnail_url)
+                            .execute(&pool)
+                            .await
+                            {
+                                tracing::error!("UPLOAD: Failed to index document: {:?}", e);
+                            }
+
+                            return Ok(Json(serde_json::json!({
+                                "url": url,
+                                "thumbnail_url": processed.thumbnail_url,
+                            })));
                         }
                         Err(e) => {
-                            tracing::error!("Failed to write file to disk: {}", e);
-                            return (
-                                StatusCode::INTERNAL_SERVER_ERROR,
-                                Json(ErrorResponse {
-                                    error: format!("Failed to save file: {}", e),
-                                }),
-                            )
-                                .into_response();
+                            return Err(AppError::internal(format!(
+                                "Failed to write file to disk: {}",
+                                e
+                            )));
                         }
                     }
                 } else {
@@ -126,23 +122,80 @@ pub async fn upload_file(
             }
             Err(e) => {
                 tracing::error!("UPLOAD: Multipart error: {}", e);
-                return (
-                    StatusCode::BAD_REQUEST,
-                    Json(ErrorResponse {
-                        error: format!("Multipart structure error: {}", e),
-                    }),
-                )
-                    .into_response();
+                return Err(AppError::bad_request(
+                    "invalid_multipart",
+                    format!("Multipart structure error: {}", e),
+                ));
             }
         }
     }
 
     tracing::error!("UPLOAD: Failed - No valid 'file' field processed");
-    (
-        StatusCode::BAD_REQUEST,
-        Json(ErrorResponse {
-            error: "File upload failed: No 'file' field found in request".to_string(),
-        }),
-    )
-        .into_response()
+    Err(AppError::invalid(
+        "file",
+        "File upload failed: No 'file' field found in request",
+    ))
+}
+
+fn check_extension(extension: &str) -> AppResult<()> {
+    if !ALLOWED_EXTENSIONS.contains(&extension) {
+        tracing::error!(
+            "UPLOAD: Extension '{}' not allowed. Allowed: {:?}",
+            extension,
+            ALLOWED_EXTENSIONS
+        );
+        return Err(AppError::UnsupportedMediaType(format!(
+            "File type .{} not allowed",
+            extension
+        )));
+    }
+    Ok(())
+}
+
+/// Type and size checks shared by everything that writes under `uploads/`.
+/// The leading bytes must match the extension, so a renamed HTML page or
+/// script cannot be served back as a PDF or image.
+pub fn validate(extension: &str, data: &[u8]) -> AppResult<()> {
+    check_extension(extension)?;
+
+    if data.len() > MAX_FILE_SIZE {
+        tracing::error!("UPLOAD: File too large ({} bytes)", data.len());
+        return Err(AppError::PayloadTooLarge(
+            "File too large (Max 10MB)".to_string(),
+        ));
+    }
+
+    let matches = match extension {
+        "pdf" => data.starts_with(b"%PDF-"),
+        "docx" => data.starts_with(b"PK\x03\x04"),
+        "png" => image::guess_format(data).ok() == Some(image::ImageFormat::Png),
+        "jpg" | "jpeg" => image::guess_format(data).ok() == Some(image::ImageFormat::Jpeg),
+        "webp" => image::guess_format(data).ok() == Some(image::ImageFormat::WebP),
+        _ => false,
+    };
+    if !matches {
+        tracing::error!("UPLOAD: Content does not look like a .{} file", extension);
+        return Err(AppError::UnsupportedMediaType(format!(
+            "File content does not match .{}",
+            extension
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate;
+
+    #[test]
+    fn validate_checks_extension_and_signature() {
+        assert!(validate("pdf", b"%PDF-1.7 ...").is_ok());
+        assert!(validate("docx", b"PK\x03\x04rest").is_ok());
+        assert!(validate("png", b"\x89PNG\r\n\x1a\n....").is_ok());
+
+        assert!(validate("pdf", b"<html><script>").is_err());
+        assert!(validate("png", b"%PDF-1.7").is_err());
+        assert!(validate("svg", b"<svg/>").is_err());
+        assert!(validate("pdf", &[b'%'; super::MAX_FILE_SIZE + 1]).is_err());
+    }
 }
@@ -1,14 +1,14 @@
+use crate::error::AppResult;
+use crate::extract::Json;
 use crate::models::visitor::{VisitResponse, Visitor};
 use axum::{
-    Json,
     extract::State,
-    http::{HeaderMap, StatusCode},
-    response::IntoResponse,
+    http::HeaderMap,
 };
 use sha2::{Digest, Sha256};
 use sqlx::PgPool;
 
-pub async fn record_visit(State(pool): State<PgPool>, headers: HeaderMap) -> impl IntoResponse {
+pub async fn record_visit(State(pool): State<PgPool>, headers: HeaderMap) -> AppResult<Json<VisitResponse>> {
     tracing::info!("RECORD_VISIT REQUEST RECEIVED");
 
     // 1. Get IP
@@ -35,7 +35,7 @@ pub async fn record_visit(State(pool): State<PgPool>, headers: HeaderMap) -> imp
     let ip_hash = format!("{:x}", hasher.finalize());
 
     // 4. Upsert into DB
-    let result = sqlx::query_as::<_, Visitor>(
+    let visitor = sqlx::query_as::<_, Visitor>(
         r#"
         INSERT INTO visitors (ip_hash, visit_count, first_seen_at, last_seen_at)
         VALUES ($1, 1, NOW(), NOW())
@@ -48,7 +48,7 @@ pub async fn record_visit(State(pool): State<PgPool>, headers: HeaderMap) -> imp
     )
     .bind(&ip_hash)
     .fetch_one(&pool)
-    .await;
+    .await?;
 
     // 5. Get Counts
     let total_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM visitors")
@@ -67,24 +67,11 @@ pub async fn record_visit(State(pool): State<PgPool>, headers: HeaderMap) -> imp
         tracing::info!("TACTICAL ALERT: This is the first visitor of the day!");
     }
 
-    match result {
-        Ok(visitor) => {
-            tracing::info!("SUCCESS: Intercepted signal from {}", visitor.ip_hash);
-            Json(VisitResponse {
-                is_first_visit: visitor.visit_count == 1,
-                is_first_of_day,
-                total_unique_visitors: total_count,
-                today_visitors: today_count,
-            })
-            .into_response()
-        }
-        Err(e) => {
-            tracing::error!("DATABASE ERROR during visitor log: {:?}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("Intercept Failure: {}", e),
-            )
-                .into_response()
-        }
-    }
+    tracing::info!("SUCCESS: Intercepted signal from {}", visitor.ip_hash);
+    Ok(Json(VisitResponse {
+        is_first_visit: visitor.visit_count == 1,
+        is_first_of_day,
+        total_unique_visitors: total_count,
+        today_visitors: today_count,
+    }))
 }
@@ -0,0 +1,113 @@
+use sqlx::PgPool;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Days between "delete my account" and the actual purge; signing in and
+/// cancelling within this window keeps everything.
+pub const GRACE_DAYS: i32 = 14;
+const PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Maps a public `/uploads/...` URL to its path on disk. Logos are shared and
+/// content-addressed, so they are never treated as belonging to one user.
+fn owned_upload_path(url: &str) -> Option<String> {
+    let relative = url.strip_prefix("/uploads/")?;
+    if relative.is_empty()
+        || relative.starts_with("logos/")
+        || relative
+            .split('/')
+            .any(|part| part.is_empty() || part == "..")
+    {
+        return None;
+    }
+    Some(format!("uploads/{}", relative))
+}
+
+/// Every file under `uploads/` the user uploaded. Only `documents` rows count:
+/// application fields are user input and could name someone else's file.
+async fn upload_files(pool: &PgPool, user_id: Uuid) -> Result<Vec<String>, sqlx::Error> {
+    let urls: Vec<Option<String>> = sqlx::query_scalar(
+        "SELECT file_path FROM documents WHERE user_id = $1
+         UNION SELECT thumbnail_path FROM documents WHERE user_id = $1",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut paths: Vec<String> = urls
+        .iter()
+        .flatten()
+        .filter_map(|url| owned_upload_path(url))
+        .collect();
+    paths.sort();
+    paths.dedup();
+    Ok(paths)
+}
+
+/// Deletes the user row (everything else cascades in SQL), then their files.
+pub async fn purge_user(pool: &PgPool, user_id: Uuid) -> Result<(), sqlx::Error> {
+    let files = upload_files(pool, user_id).await?;
+
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+    for path in files {
+        match tokio::fs::remove_file(&path).await {
+            Ok(_) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => tracing::warn!("ACCOUNT: Could not remove {}: {}", path, e),
+        }
+    }
+
+    tracing::info!("ACCOUNT: Purged user {}", user_id);
+    Ok(())
+}
+
+async fn purge_due_accounts(pool: &PgPool) -> Result<(), sqlx::Error> {
+    let due: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT id FROM users WHERE deletion_requested_at < NOW() - make_interval(days => $1)",
+    )
+    .bind(GRACE_DAYS)
+    .fetch_all(pool)
+    .await?;
+
+    for user_id in due {
+        purge_user(pool, user_id).await?;
+    }
+    Ok(())
+}
+
+/// Background task that purges accounts whose grace period has run out.
+pub fn spawn_purge_task(pool: PgPool) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(PURGE_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = purge_due_accounts(&pool).await {
+                tracing::error!("ACCOUNT: Purge run failed: {:?}", e);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_maps_owned_upload_urls() {
+        assert_eq!(
+            owned_upload_path("/uploads/abc.pdf").as_deref(),
+            Some("uploads/abc.pdf")
+        );
+        assert_eq!(
+            owned_upload_path("/uploads/thumbs/abc.png").as_deref(),
+            Some("uploads/thumbs/abc.png")
+        );
+        assert_eq!(owned_upload_path("/uploads/logos/ff.png"), None);
+        assert_eq!(owned_upload_path("/uploads/../Cargo.toml"), None);
+        assert_eq!(owned_upload_path("https://example.com/logo.png"), None);
+        assert_eq!(owned_upload_path("/uploads/"), None);
+    }
+}
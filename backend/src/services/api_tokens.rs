@@ -0,0 +1,159 @@
+use crate::services::sessions::hash_token;
+use axum::http::Method;
+use rand::{distributions::Alphanumeric, Rng};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Lets the `Claims` extractor tell personal tokens from JWTs at a glance
+pub const TOKEN_PREFIX: &str = "oat_";
+
+/// Account management stays behind a real login, whatever the token's scopes
+const ACCOUNT_PATHS: &[&str] = &[
+    "/api/auth",
+    "/api/account",
+    "/api/invites",
+    "/api/tokens",
+    "/api/emails",
+];
+
+/// What the `read` scope covers. Other GETs can have side effects (`/api/intel`
+/// fetches remote pages and writes the cache), so reads are opted in, not assumed.
+const READ_PATHS: &[&str] = &["/api/applications", "/api/comments", "/api/documents"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Scope {
+    Read,
+    WriteApplications,
+    Upload,
+}
+
+impl Scope {
+    pub const ALL: [Scope; 3] = [Scope::Read, Scope::WriteApplications, Scope::Upload];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Scope::Read => "read",
+            Scope::WriteApplications => "write_applications",
+            Scope::Upload => "upload",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Scope> {
+        Scope::ALL.into_iter().find(|scope| scope.as_str() == s)
+    }
+}
+
+/// The scope a request needs, or `None` if API tokens may not make it at all.
+pub fn required_scope(method: &Method, path: &str) -> Option<Scope> {
+    if ACCOUNT_PATHS
+        .iter()
+        .any(|p| path == *p || path.starts_with(&format!("{}/", p)))
+    {
+        return None;
+    }
+    if *method == Method::GET {
+        return READ_PATHS
+            .iter()
+            .any(|p| path == *p || path.starts_with(&format!("{}/", p)))
+            .then_some(Scope::Read);
+    }
+    if path == "/api/upload" {
+        return Some(Scope::Upload);
+    }
+    if path.starts_with("/api/applications") {
+        return Some(Scope::WriteApplications);
+    }
+    None
+}
+
+pub fn generate_token() -> String {
+    let body: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(40)
+        .map(char::from)
+        .collect();
+    format!("{}{}", TOKEN_PREFIX, body)
+}
+
+/// Looks up a presented token. Returns the owner and the token's scopes.
+pub async fn authenticate(
+    pool: &PgPool,
+    token: &str,
+) -> Result<Option<(Uuid, Vec<Scope>)>, sqlx::Error> {
+    let found: Option<(Uuid, Uuid, Vec<String>)> =
+        sqlx::query_as("SELECT id, user_id, scopes FROM api_tokens WHERE token_hash = $1")
+            .bind(hash_token(token))
+            .fetch_optional(pool)
+            .await?;
+
+    let Some((id, user_id, scopes)) = found else {
+        return Ok(None);
+    };
+
+    // Minute resolution is plenty for "last used", and spares a write per request
+    sqlx::query(
+        "UPDATE api_tokens SET last_used_at = NOW()
+         WHERE id = $1 AND (last_used_at IS NULL OR last_used_at < NOW() - INTERVAL '1 minute')",
+    )
+    .bind(id)
+    .execute(pool)
+    .await?;
+
+    Ok(Some((
+        user_id,
+        scopes.iter().filter_map(|s| Scope::parse(s)).collect(),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_requests_to_scopes() {
+        assert_eq!(
+            required_scope(&Method::GET, "/api/applications"),
+            Some(Scope::Read)
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/api/applications"),
+            Some(Scope::WriteApplications)
+        );
+        assert_eq!(
+            required_scope(&Method::DELETE, "/api/applications/1"),
+            Some(Scope::WriteApplications)
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/api/upload"),
+            Some(Scope::Upload)
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/api/comments/recent"),
+            Some(Scope::Read)
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/api/documents/search"),
+            Some(Scope::Read)
+        );
+        assert_eq!(required_scope(&Method::GET, "/api/intel"), None);
+        assert_eq!(required_scope(&Method::GET, "/api/applicationsx"), None);
+        assert_eq!(required_scope(&Method::GET, "/api/tokens"), None);
+        assert_eq!(required_scope(&Method::GET, "/api/emails"), None);
+        assert_eq!(
+            required_scope(&Method::POST, "/api/emails/abc/resend"),
+            None
+        );
+        assert_eq!(required_scope(&Method::GET, "/api/auth/sessions"), None);
+        assert_eq!(required_scope(&Method::POST, "/api/invites"), None);
+        assert_eq!(required_scope(&Method::GET, "/api/account/export"), None);
+    }
+
+    #[test]
+    fn scopes_round_trip() {
+        for scope in Scope::ALL {
+            assert_eq!(Scope::parse(scope.as_str()), Some(scope));
+        }
+        assert_eq!(Scope::parse("admin"), None);
+        assert!(generate_token().starts_with(TOKEN_PREFIX));
+    }
+}
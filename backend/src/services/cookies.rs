@@ -0,0 +1,123 @@
+//! HttpOnly cookie transport for sessions, as an alternative to bearer tokens
+//! kept in `localStorage`. Cookie-authenticated requests that change state must
+//! echo a CSRF token (bound to the session) in the `X-CSRF-Token` header.
+
+use crate::config::CookieConfig;
+use crate::services::sessions::{TokenPair, ACCESS_TOKEN_MINUTES, REFRESH_TOKEN_DAYS};
+use axum::http::{header, HeaderMap, HeaderValue};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+pub const ACCESS_COOKIE: &str = "oisko_session";
+pub const REFRESH_COOKIE: &str = "oisko_refresh";
+pub const CSRF_HEADER: &str = "x-csrf-token";
+/// The refresh cookie is only sent to the endpoints that need it
+const REFRESH_COOKIE_PATH: &str = "/api/auth";
+
+fn set_cookie(
+    config: &CookieConfig,
+    name: &str,
+    value: &str,
+    path: &str,
+    max_age: i64,
+) -> HeaderValue {
+    HeaderValue::from_str(&format!(
+        "{}={}; Path={}; Max-Age={}; HttpOnly; SameSite={}{}",
+        name,
+        value,
+        path,
+        max_age,
+        config.same_site.as_str(),
+        if config.secure { "; Secure" } else { "" }
+    ))
+    .expect("cookie values are ASCII")
+}
+
+/// Reads one cookie from the request's `Cookie` headers.
+pub fn read(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value.to_string())
+        .filter(|value| !value.is_empty())
+}
+
+pub fn session_cookies(config: &CookieConfig, tokens: &TokenPair) -> [HeaderValue; 2] {
+    [
+        set_cookie(
+            config,
+            ACCESS_COOKIE,
+            &tokens.token,
+            "/",
+            ACCESS_TOKEN_MINUTES * 60,
+        ),
+        set_cookie(
+            config,
+            REFRESH_COOKIE,
+            &tokens.refresh_token,
+            REFRESH_COOKIE_PATH,
+            REFRESH_TOKEN_DAYS as i64 * 24 * 60 * 60,
+        ),
+    ]
+}
+
+pub fn clear_cookies(config: &CookieConfig) -> [HeaderValue; 2] {
+    [
+        set_cookie(config, ACCESS_COOKIE, "", "/", 0),
+        set_cookie(config, REFRESH_COOKIE, "", REFRESH_COOKIE_PATH, 0),
+    ]
+}
+
+/// Stateless CSRF token: an HMAC of the session id, so it survives token
+/// rotation and dies with the session.
+pub fn csrf_token(secret: &str, session_id: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(b"csrf:");
+    mac.update(session_id.as_bytes());
+    format!("{:x}", mac.finalize().into_bytes())
+}
+
+pub fn csrf_matches(secret: &str, session_id: &str, presented: &str) -> bool {
+    // Compare digests so the comparison time does not depend on the shared prefix
+    digest(&csrf_token(secret, session_id)) == digest(presented)
+}
+
+fn digest(value: &str) -> [u8; 32] {
+    use sha2::Digest;
+    Sha256::digest(value.as_bytes()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_cookie_by_name() {
+        let mut headers = HeaderMap::new();
+        headers.append(
+            header::COOKIE,
+            HeaderValue::from_static("theme=dark; oisko_session=abc.def"),
+        );
+        headers.append(
+            header::COOKIE,
+            HeaderValue::from_static("oisko_refresh=xyz"),
+        );
+        assert_eq!(read(&headers, ACCESS_COOKIE).as_deref(), Some("abc.def"));
+        assert_eq!(read(&headers, REFRESH_COOKIE).as_deref(), Some("xyz"));
+        assert_eq!(read(&headers, "missing"), None);
+    }
+
+    #[test]
+    fn csrf_is_bound_to_session() {
+        let token = csrf_token("key", "session-a");
+        assert!(csrf_matches("key", "session-a", &token));
+        assert!(!csrf_matches("key", "session-b", &token));
+        assert!(!csrf_matches("other-key", "session-a", &token));
+        assert!(!csrf_matches("key", "session-a", ""));
+    }
+}
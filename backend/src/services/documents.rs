@@ -0,0 +1,255 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::io::{Cursor, Read};
+use std::time::Duration;
+use tokio::fs;
+use tokio::process::Command;
+
+const THUMBNAIL_DIR: &str = "uploads/thumbs";
+const THUMBNAIL_SIZE: u32 = 320;
+/// Upper bound on the decompressed `word/document.xml`; guards against zip bombs.
+const MAX_XML_BYTES: u64 = 20 * 1024 * 1024;
+/// How long `pdftotext` / `pdftoppm` may run before the child is killed.
+const TOOL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// What we managed to derive from an uploaded file. Either part may be missing
+/// (unsupported type, corrupt file, poppler not installed) without failing the upload.
+#[derive(Debug, Default)]
+pub struct ProcessedDocument {
+    pub text: Option<String>,
+    pub thumbnail_url: Option<String>,
+}
+
+/// Extracts plain text (PDF, DOCX) and renders a first-page preview (PDF, images)
+/// for a file that has already been written to `filepath`.
+pub async fn process_upload(
+    filepath: &str,
+    stem: &str,
+    extension: &str,
+    data: &[u8],
+) -> ProcessedDocument {
+    let text = match extension {
+        "pdf" => extract_pdf_text(filepath).await,
+        "docx" => {
+            let data = data.to_vec();
+            tokio::task::spawn_blocking(move || extract_docx_text(&data))
+                .await
+                .ok()
+                .flatten()
+        }
+        _ => None,
+    }
+    .map(|t| normalize_whitespace(&t))
+    .filter(|t| !t.is_empty());
+
+    if let Err(e) = fs::create_dir_all(THUMBNAIL_DIR).await {
+        tracing::error!("DOCUMENTS: Failed to create thumbnail directory: {}", e);
+        return ProcessedDocument {
+            text,
+            thumbnail_url: None,
+        };
+    }
+
+    let thumbnail_url = match extension {
+        "pdf" => render_pdf_thumbnail(filepath, stem).await,
+        "png" | "jpg" | "jpeg" | "webp" => {
+            let data = data.to_vec();
+            let stem = stem.to_string();
+            tokio::task::spawn_blocking(move || render_image_thumbnail(&data, &stem))
+                .await
+                .ok()
+                .flatten()
+        }
+        _ => None,
+    };
+
+    ProcessedDocument {
+        text,
+        thumbnail_url,
+    }
+}
+
+/// Uses `pdftotext` from poppler-utils; returns `None` if the tool is missing.
+async fn extract_pdf_text(filepath: &str) -> Option<String> {
+    let output = Command::new("pdftotext")
+        .args(["-enc", "UTF-8", filepath, "-"])
+        .kill_on_drop(true)
+        .output();
+    let output = tokio::time::timeout(TOOL_TIMEOUT, output)
+        .await
+        .map_err(|_| tracing::warn!("DOCUMENTS: pdftotext timed out for {}", filepath))
+        .ok()?
+        .map_err(|e| tracing::warn!("DOCUMENTS: pdftotext unavailable: {}", e))
+        .ok()?;
+
+    if !output.status.success() {
+        tracing::warn!(
+            "DOCUMENTS: pdftotext failed for {}: {}",
+            filepath,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Renders page 1 with `pdftoppm` into `uploads/thumbs/<stem>.png`.
+async fn render_pdf_thumbnail(filepath: &str, stem: &str) -> Option<String> {
+    let out_prefix = format!("{}/{}", THUMBNAIL_DIR, stem);
+    let child = Command::new("pdftoppm")
+        .args([
+            "-png",
+            "-f",
+            "1",
+            "-l",
+            "1",
+            "-singlefile",
+            "-scale-to",
+            &THUMBNAIL_SIZE.to_string(),
+            filepath,
+            &out_prefix,
+        ])
+        .kill_on_drop(true)
+        .status();
+    let status = tokio::time::timeout(TOOL_TIMEOUT, child)
+        .await
+        .map_err(|_| tracing::warn!("DOCUMENTS: pdftoppm timed out for {}", filepath))
+        .ok()?
+        .map_err(|e| tracing::warn!("DOCUMENTS: pdftoppm unavailable: {}", e))
+        .ok()?;
+
+    if !status.success() {
+        tracing::warn!("DOCUMENTS: pdftoppm failed for {}", filepath);
+        return None;
+    }
+
+    Some(format!("/uploads/thumbs/{}.png", stem))
+}
+
+fn render_image_thumbnail(data: &[u8], stem: &str) -> Option<String> {
+    let img = image::load_from_memory(data)
+        .map_err(|e| tracing::warn!("DOCUMENTS: Failed to decode image: {}", e))
+        .ok()?;
+
+    let path = format!("{}/{}.png", THUMBNAIL_DIR, stem);
+    img.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+        .save_with_format(&path, image::ImageFormat::Png)
+        .map_err(|e| tracing::error!("DOCUMENTS: Failed to write thumbnail: {}", e))
+        .ok()?;
+
+    Some(format!("/uploads/thumbs/{}.png", stem))
+}
+
+/// Pulls the run text out of `word/document.xml`, keeping paragraph breaks.
+fn extract_docx_text(data: &[u8]) -> Option<String> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(data)).ok()?;
+    let mut xml = String::new();
+    archive
+        .by_name("word/document.xml")
+        .ok()?
+        .take(MAX_XML_BYTES + 1)
+        .read_to_string(&mut xml)
+        .ok()?;
+    if xml.len() as u64 > MAX_XML_BYTES {
+        tracing::warn!(
+            "DOCUMENTS: DOCX body exceeds {} bytes, skipping",
+            MAX_XML_BYTES
+        );
+        return None;
+    }
+
+    let mut reader = Reader::from_str(&xml);
+    let mut text = String::new();
+    let mut in_text_run = false;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) if e.name().as_ref() == b"w:t" => in_text_run = true,
+            Ok(Event::End(e)) => match e.name().as_ref() {
+                b"w:t" => in_text_run = false,
+                b"w:p" => text.push('\n'),
+                _ => {}
+            },
+            Ok(Event::Empty(e)) => match e.name().as_ref() {
+                b"w:tab" => text.push('\t'),
+                b"w:br" | b"w:cr" => text.push('\n'),
+                _ => {}
+            },
+            Ok(Event::Text(t)) if in_text_run => {
+                if let Ok(s) = t.unescape() {
+                    text.push_str(&s);
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                tracing::warn!("DOCUMENTS: Malformed DOCX XML: {}", e);
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    Some(text)
+}
+
+/// Collapses runs of blank lines and trailing spaces left behind by the extractors.
+fn normalize_whitespace(text: &str) -> String {
+    text.lines()
+        .map(str::trim_end)
+        .filter(|line| !line.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    fn docx(document_xml: &[u8]) -> Vec<u8> {
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options =
+            SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+        zip.start_file("word/document.xml", options).unwrap();
+        zip.write_all(document_xml).unwrap();
+        zip.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn docx_keeps_paragraphs_tabs_and_breaks() {
+        let xml = r#"<w:document><w:body>
+            <w:p><w:r><w:t>Maija Meikäläinen</w:t></w:r></w:p>
+            <w:p><w:r><w:t>Rust</w:t><w:tab/><w:t>5 years</w:t></w:r></w:p>
+            <w:p><w:r><w:t>Line one</w:t><w:br/><w:t>Line two &amp; more</w:t></w:r></w:p>
+        </w:body></w:document>"#;
+
+        let text = extract_docx_text(&docx(xml.as_bytes())).unwrap();
+        assert_eq!(
+            normalize_whitespace(&text),
+            "Maija Meikäläinen\nRust\t5 years\nLine one\nLine two & more"
+        );
+    }
+
+    #[test]
+    fn docx_rejects_oversized_body() {
+        let mut xml = b"<w:document><w:body><w:p><w:r><w:t>".to_vec();
+        xml.resize(MAX_XML_BYTES as usize + 1, b'a');
+        assert!(extract_docx_text(&docx(&xml)).is_none());
+    }
+
+    #[test]
+    fn docx_without_document_xml_is_ignored() {
+        assert!(extract_docx_text(b"not a zip").is_none());
+    }
+
+    #[test]
+    fn normalize_whitespace_drops_blank_lines_and_trailing_spaces() {
+        assert_eq!(
+            normalize_whitespace("Title   \n\n  \n\tBody text \t\nEnd\n\n"),
+            "Title\n\tBody text\nEnd"
+        );
+        assert_eq!(normalize_whitespace(" \n\n"), "");
+    }
+}
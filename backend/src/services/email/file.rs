@@ -0,0 +1,86 @@
+use super::{build_message, Email, MailError, Mailer};
+use axum::async_trait;
+use lettre::message::Mailbox;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Writes every email to `dir` as an `.eml` file that any mail client can open.
+pub struct File {
+    dir: PathBuf,
+    from: Mailbox,
+}
+
+impl File {
+    pub fn new(dir: PathBuf, from: Mailbox) -> Self {
+        File { dir, from }
+    }
+}
+
+#[async_trait]
+impl Mailer for File {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    async fn send(&self, email: &Email) -> Result<(), MailError> {
+        let message = build_message(&self.from, email)?;
+
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(|e| MailError::Delivery(format!("{}: {}", self.dir.display(), e)))?;
+
+        // Timestamp first so a directory listing is in sending order
+        let name = format!(
+            "{}-{}.eml",
+            chrono::Utc::now().format("%Y%m%dT%H%M%S"),
+            Uuid::new_v4()
+        );
+        let path = self.dir.join(name);
+        tokio::fs::write(&path, message.formatted())
+            .await
+            .map_err(|e| MailError::Delivery(format!("{}: {}", path.display(), e)))?;
+
+        tracing::info!("MAIL: Wrote {}", path.display());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn writes_one_eml_per_email() {
+        let dir = std::env::temp_dir().join(format!("oisko-mail-{}", Uuid::new_v4()));
+        let mailer = File::new(dir.clone(), "noreply@example.com".parse().unwrap());
+
+        let email = Email {
+            template: "test".to_string(),
+            to: "someone@example.com".to_string(),
+            reply_to: Some("visitor@example.com".to_string()),
+            subject: "Hello".to_string(),
+            html: "<p>Hi</p>".to_string(),
+            text: "Hi".to_string(),
+        };
+        mailer.send(&email).await.unwrap();
+
+        let mut entries = std::fs::read_dir(&dir).unwrap();
+        let path = entries.next().unwrap().unwrap().path();
+        assert!(entries.next().is_none());
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert!(written.contains("To: someone@example.com"));
+        assert!(written.contains("Reply-To: visitor@example.com"));
+        assert!(written.contains("Subject: Hello"));
+
+        let bad = Email {
+            to: "not an address".to_string(),
+            ..email
+        };
+        assert!(matches!(
+            mailer.send(&bad).await,
+            Err(MailError::InvalidAddress(_))
+        ));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,138 @@
+mod file;
+pub mod queue;
+mod resend;
+mod smtp;
+pub mod templates;
+
+use crate::config::{EmailConfig, MailBackend};
+use axum::async_trait;
+use lettre::message::{Mailbox, Message, MultiPart};
+pub use queue::EmailQueue;
+use std::fmt;
+use std::sync::Arc;
+use templates::Rendered;
+
+pub type SharedMailer = Arc<dyn Mailer>;
+
+#[derive(Debug, Clone)]
+pub struct Email {
+    /// Template it was rendered from, shown in the outbound email list
+    pub template: String,
+    pub to: String,
+    pub reply_to: Option<String>,
+    pub subject: String,
+    pub html: String,
+    /// Plain-text alternative for clients that don't show HTML
+    pub text: String,
+}
+
+impl Email {
+    pub fn new(to: String, rendered: Rendered) -> Self {
+        Email {
+            template: rendered.template,
+            to,
+            reply_to: None,
+            subject: rendered.subject,
+            html: rendered.html,
+            text: rendered.text,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum MailError {
+    /// A recipient or reply-to address that can't be parsed. Retrying won't help.
+    InvalidAddress(String),
+    /// The provider or SMTP server refused the message or could not be reached
+    Delivery(String),
+}
+
+impl fmt::Display for MailError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MailError::InvalidAddress(address) => write!(f, "Invalid email address: {}", address),
+            MailError::Delivery(detail) => write!(f, "Delivery failed: {}", detail),
+        }
+    }
+}
+
+/// A way of getting an email to its recipient. Picked once at startup from
+/// `MAIL_BACKEND`; handlers only see the trait object.
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    /// Short name for logs, e.g. "smtp"
+    fn name(&self) -> &'static str;
+
+    /// False for the log-only backend, which drops every message
+    fn delivers(&self) -> bool {
+        true
+    }
+
+    async fn send(&self, email: &Email) -> Result<(), MailError>;
+}
+
+pub fn from_config(config: &EmailConfig) -> Result<SharedMailer, MailError> {
+    let from: Mailbox = config
+        .sender_email
+        .parse()
+        .expect("SENDER_EMAIL is validated at startup");
+
+    Ok(match &config.backend {
+        MailBackend::Log => Arc::new(LogOnly),
+        MailBackend::Resend { api_key } => Arc::new(resend::Resend::new(api_key.clone(), from)),
+        MailBackend::Smtp(smtp) => Arc::new(smtp::Smtp::new(smtp, from)?),
+        MailBackend::File { dir } => Arc::new(file::File::new(dir.clone(), from)),
+    })
+}
+
+/// Used when no backend is configured, so local setups still see what would have been sent.
+struct LogOnly;
+
+#[async_trait]
+impl Mailer for LogOnly {
+    fn name(&self) -> &'static str {
+        "log"
+    }
+
+    fn delivers(&self) -> bool {
+        false
+    }
+
+    async fn send(&self, email: &Email) -> Result<(), MailError> {
+        tracing::warn!(
+            "MAIL_BACKEND not configured. Email to {} not sent: {}",
+            email.to,
+            email.subject
+        );
+        Ok(())
+    }
+}
+
+fn parse_mailbox(address: &str) -> Result<Mailbox, MailError> {
+    address
+        .parse()
+        .map_err(|_| MailError::InvalidAddress(address.to_string()))
+}
+
+/// Whether `address` can be used as a recipient or reply-to. Checked before
+/// queueing, since a bad address would only fail later in the worker.
+pub fn is_valid_address(address: &str) -> bool {
+    parse_mailbox(address).is_ok()
+}
+
+/// The RFC 5322 message shared by the SMTP and file backends.
+fn build_message(from: &Mailbox, email: &Email) -> Result<Message, MailError> {
+    let mut builder = Message::builder()
+        .from(from.clone())
+        .to(parse_mailbox(&email.to)?)
+        .subject(&email.subject);
+    if let Some(reply_to) = &email.reply_to {
+        builder = builder.reply_to(parse_mailbox(reply_to)?);
+    }
+    builder
+        .multipart(MultiPart::alternative_plain_html(
+            email.text.clone(),
+            email.html.clone(),
+        ))
+        .map_err(|e| MailError::Delivery(e.to_string()))
+}
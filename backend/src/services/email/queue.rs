@@ -0,0 +1,310 @@
+//! Outbound email queue in the `outbound_emails` table.
+//!
+//! Handlers only insert a row; a background worker delivers it, retrying with
+//! exponential backoff. After `MAX_ATTEMPTS` failures, or an error that no
+//! retry can fix, the row is marked `dead` and waits for an admin to resend it.
+
+use super::{Email, MailError, SharedMailer};
+use serde::Deserialize;
+use sqlx::{FromRow, PgPool};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+use uuid::Uuid;
+
+pub const MAX_ATTEMPTS: i32 = 8;
+/// Delay before the first retry; doubled for each one after it
+const BASE_RETRY_DELAY: Duration = Duration::from_secs(60);
+/// Enqueueing wakes the worker at once, so this only matters for retries
+const POLL_INTERVAL: Duration = Duration::from_secs(15);
+const BATCH_SIZE: i64 = 10;
+/// Delivered emails are kept this long for the admin view
+const KEEP_SENT_DAYS: i32 = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Pending,
+    Sending,
+    Sent,
+    Dead,
+}
+
+impl Status {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Status::Pending => "pending",
+            Status::Sending => "sending",
+            Status::Sent => "sent",
+            Status::Dead => "dead",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct EmailQueue {
+    pool: PgPool,
+    mailer: SharedMailer,
+    wake: Arc<Notify>,
+}
+
+#[derive(FromRow)]
+struct QueuedEmail {
+    id: Uuid,
+    template: String,
+    recipient: String,
+    reply_to: Option<String>,
+    subject: String,
+    html: String,
+    text_body: String,
+    attempts: i32,
+}
+
+#[derive(Debug, PartialEq)]
+enum Outcome {
+    Sent,
+    Retry { after: Duration, error: String },
+    Dead(String),
+}
+
+/// Backoff before the next attempt, given how many attempts have been made.
+fn retry_delay(attempts: i32) -> Duration {
+    BASE_RETRY_DELAY * 2u32.pow(attempts.clamp(1, MAX_ATTEMPTS) as u32 - 1)
+}
+
+fn outcome(result: Result<(), MailError>, delivers: bool, attempts: i32) -> Outcome {
+    match result {
+        // The log-only backend drops the message, so it must not count as sent
+        Ok(()) if !delivers => Outcome::Dead("No mail backend configured".to_string()),
+        Ok(()) => Outcome::Sent,
+        Err(e @ MailError::InvalidAddress(_)) => Outcome::Dead(e.to_string()),
+        Err(e) if attempts >= MAX_ATTEMPTS => Outcome::Dead(e.to_string()),
+        Err(e) => Outcome::Retry {
+            after: retry_delay(attempts),
+            error: e.to_string(),
+        },
+    }
+}
+
+impl EmailQueue {
+    pub fn new(pool: PgPool, mailer: SharedMailer) -> Self {
+        EmailQueue {
+            pool,
+            mailer,
+            wake: Arc::new(Notify::new()),
+        }
+    }
+
+    pub fn mailer(&self) -> &SharedMailer {
+        &self.mailer
+    }
+
+    /// Stores the email for delivery. Once this returns, the email survives restarts.
+    pub async fn enqueue(&self, user_id: Option<Uuid>, email: Email) -> Result<Uuid, sqlx::Error> {
+        let id: Uuid = sqlx::query_scalar(
+            "INSERT INTO outbound_emails (user_id, template, recipient, reply_to, subject, html, text_body)
+             VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING id",
+        )
+        .bind(user_id)
+        .bind(&email.template)
+        .bind(&email.to)
+        .bind(&email.reply_to)
+        .bind(&email.subject)
+        .bind(&email.html)
+        .bind(&email.text)
+        .fetch_one(&self.pool)
+        .await?;
+
+        self.wake.notify_one();
+        Ok(id)
+    }
+
+    /// Puts an email back in line with a fresh set of attempts. Returns false if
+    /// there is no such email or it is being sent right now.
+    pub async fn resend(&self, id: Uuid, user_id: Uuid) -> Result<bool, sqlx::Error> {
+        let updated = sqlx::query(
+            "UPDATE outbound_emails
+             SET status = 'pending', attempts = 0, last_error = NULL, next_attempt_at = NOW(), sent_at = NULL
+             WHERE id = $1 AND user_id = $2 AND status <> 'sending'",
+        )
+        .bind(id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+
+        if updated > 0 {
+            self.wake.notify_one();
+        }
+        Ok(updated > 0)
+    }
+
+    /// Background task that delivers due emails and prunes old sent ones.
+    pub fn spawn_worker(&self) {
+        let queue = self.clone();
+        tokio::spawn(async move {
+            // Rows still marked `sending` were interrupted by a restart and never confirmed
+            if let Err(e) = sqlx::query(
+                "UPDATE outbound_emails SET status = 'pending' WHERE status = 'sending'",
+            )
+            .execute(&queue.pool)
+            .await
+            {
+                tracing::error!("MAIL: Could not requeue interrupted emails: {:?}", e);
+            }
+
+            let mut interval = tokio::time::interval(POLL_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => queue.prune_sent().await,
+                    _ = queue.wake.notified() => {}
+                }
+                loop {
+                    match queue.deliver_due().await {
+                        Ok(0) => break,
+                        Ok(_) => {}
+                        Err(e) => {
+                            tracing::error!("MAIL: Queue run failed: {:?}", e);
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    /// Claims and sends one batch of due emails. Returns how many were claimed.
+    async fn deliver_due(&self) -> Result<usize, sqlx::Error> {
+        let batch = sqlx::query_as::<_, QueuedEmail>(
+            "UPDATE outbound_emails SET status = 'sending', attempts = attempts + 1
+             WHERE id IN (
+                 SELECT id FROM outbound_emails
+                 WHERE status = 'pending' AND next_attempt_at <= NOW()
+                 ORDER BY next_attempt_at
+                 LIMIT $1
+                 FOR UPDATE SKIP LOCKED
+             )
+             RETURNING id, template, recipient, reply_to, subject, html, text_body, attempts",
+        )
+        .bind(BATCH_SIZE)
+        .fetch_all(&self.pool)
+        .await?;
+
+        for queued in &batch {
+            let email = Email {
+                template: queued.template.clone(),
+                to: queued.recipient.clone(),
+                reply_to: queued.reply_to.clone(),
+                subject: queued.subject.clone(),
+                html: queued.html.clone(),
+                text: queued.text_body.clone(),
+            };
+            let result = self.mailer.send(&email).await;
+            self.record(
+                queued,
+                outcome(result, self.mailer.delivers(), queued.attempts),
+            )
+            .await?;
+        }
+        Ok(batch.len())
+    }
+
+    async fn record(&self, queued: &QueuedEmail, outcome: Outcome) -> Result<(), sqlx::Error> {
+        let query = match &outcome {
+            Outcome::Sent => {
+                tracing::info!(
+                    "MAIL: Sent {} via {} to {}",
+                    queued.template,
+                    self.mailer.name(),
+                    queued.recipient
+                );
+                sqlx::query(
+                    "UPDATE outbound_emails SET status = 'sent', sent_at = NOW(), last_error = NULL
+                     WHERE id = $1",
+                )
+                .bind(queued.id)
+            }
+            Outcome::Retry { after, error } => {
+                tracing::warn!(
+                    "MAIL: Attempt {} of {} to {} failed, retrying in {:?}: {}",
+                    queued.attempts,
+                    queued.template,
+                    queued.recipient,
+                    after,
+                    error
+                );
+                sqlx::query(
+                    "UPDATE outbound_emails
+                     SET status = 'pending', last_error = $2, next_attempt_at = NOW() + make_interval(secs => $3)
+                     WHERE id = $1",
+                )
+                .bind(queued.id)
+                .bind(error)
+                .bind(after.as_secs_f64())
+            }
+            Outcome::Dead(error) => {
+                tracing::error!(
+                    "MAIL: Giving up on {} to {} after {} attempt(s): {}",
+                    queued.template,
+                    queued.recipient,
+                    queued.attempts,
+                    error
+                );
+                sqlx::query(
+                    "UPDATE outbound_emails SET status = 'dead', last_error = $2 WHERE id = $1",
+                )
+                .bind(queued.id)
+                .bind(error)
+            }
+        };
+        query.execute(&self.pool).await?;
+        Ok(())
+    }
+
+    async fn prune_sent(&self) {
+        if let Err(e) = sqlx::query(
+            "DELETE FROM outbound_emails WHERE status = 'sent' AND sent_at < NOW() - make_interval(days => $1)",
+        )
+        .bind(KEEP_SENT_DAYS)
+        .execute(&self.pool)
+        .await
+        {
+            tracing::error!("MAIL: Could not prune sent emails: {:?}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_delay_doubles_each_attempt() {
+        assert_eq!(retry_delay(1), Duration::from_secs(60));
+        assert_eq!(retry_delay(2), Duration::from_secs(2 * 60));
+        assert_eq!(retry_delay(4), Duration::from_secs(8 * 60));
+        assert_eq!(retry_delay(MAX_ATTEMPTS), Duration::from_secs(128 * 60));
+        assert_eq!(retry_delay(0), retry_delay(1));
+    }
+
+    #[test]
+    fn failures_retry_until_the_last_attempt() {
+        let delivery = || Err(MailError::Delivery("connection refused".to_string()));
+
+        assert_eq!(outcome(Ok(()), true, 1), Outcome::Sent);
+        assert!(matches!(
+            outcome(delivery(), true, 1),
+            Outcome::Retry { after, .. } if after == retry_delay(1)
+        ));
+        assert!(matches!(
+            outcome(delivery(), true, MAX_ATTEMPTS),
+            Outcome::Dead(_)
+        ));
+        // A bad address or a backend that drops mail won't get better by waiting
+        assert!(matches!(
+            outcome(Err(MailError::InvalidAddress("x".to_string())), true, 1),
+            Outcome::Dead(_)
+        ));
+        assert!(matches!(outcome(Ok(()), false, 1), Outcome::Dead(_)));
+    }
+}
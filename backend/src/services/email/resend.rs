@@ -0,0 +1,60 @@
+use super::{Email, MailError, Mailer};
+use axum::async_trait;
+use lettre::message::Mailbox;
+
+const API_URL: &str = "https://api.resend.com/emails";
+
+pub struct Resend {
+    client: reqwest::Client,
+    api_key: String,
+    from: Mailbox,
+}
+
+impl Resend {
+    pub fn new(api_key: String, from: Mailbox) -> Self {
+        Resend {
+            client: reqwest::Client::new(),
+            api_key,
+            from,
+        }
+    }
+}
+
+#[async_trait]
+impl Mailer for Resend {
+    fn name(&self) -> &'static str {
+        "resend"
+    }
+
+    async fn send(&self, email: &Email) -> Result<(), MailError> {
+        let mut payload = serde_json::json!({
+            "from": self.from.to_string(),
+            "to": email.to,
+            "subject": email.subject,
+            "html": email.html,
+            "text": email.text,
+        });
+        if let Some(reply_to) = &email.reply_to {
+            payload["reply_to"] = serde_json::json!(reply_to);
+        }
+
+        let res = self
+            .client
+            .post(API_URL)
+            .bearer_auth(&self.api_key)
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| MailError::Delivery(e.to_string()))?;
+
+        if !res.status().is_success() {
+            let status = res.status();
+            let text = res.text().await.unwrap_or_default();
+            return Err(MailError::Delivery(format!(
+                "Resend answered {}: {}",
+                status, text
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+use super::{build_message, Email, MailError, Mailer};
+use crate::config::{SmtpConfig, SmtpTls};
+use axum::async_trait;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Tokio1Executor};
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(15);
+
+pub struct Smtp {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl Smtp {
+    /// Fails only when TLS is requested for a host name that can't carry a certificate.
+    pub fn new(config: &SmtpConfig, from: Mailbox) -> Result<Self, MailError> {
+        let invalid_host = |e: lettre::transport::smtp::Error| {
+            MailError::Delivery(format!("SMTP_HOST {}: {}", config.host, e))
+        };
+        let mut builder = match config.tls {
+            SmtpTls::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host),
+            SmtpTls::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)
+                .map_err(invalid_host)?,
+            SmtpTls::Tls => {
+                AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host).map_err(invalid_host)?
+            }
+        }
+        .port(config.port)
+        .timeout(Some(TIMEOUT));
+
+        if let (Some(username), Some(password)) = (&config.username, &config.password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        Ok(Smtp {
+            transport: builder.build(),
+            from,
+        })
+    }
+}
+
+#[async_trait]
+impl Mailer for Smtp {
+    fn name(&self) -> &'static str {
+        "smtp"
+    }
+
+    async fn send(&self, email: &Email) -> Result<(), MailError> {
+        let message = build_message(&self.from, email)?;
+        self.transport
+            .send(message)
+            .await
+            .map(|_| ())
+            .map_err(|e| MailError::Delivery(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    /// Just enough of an SMTP server to accept one message. Returns what came after DATA.
+    async fn catch_one_message(listener: TcpListener) -> String {
+        let (socket, _) = listener.accept().await.unwrap();
+        let (read, mut write) = socket.into_split();
+        let mut lines = BufReader::new(read).lines();
+        write.write_all(b"220 catcher ESMTP\r\n").await.unwrap();
+
+        let mut data = String::new();
+        while let Some(line) = lines.next_line().await.unwrap() {
+            let command = line.to_uppercase();
+            let reply: &[u8] = if command.starts_with("EHLO") || command.starts_with("HELO") {
+                b"250 catcher\r\n"
+            } else if command.starts_with("DATA") {
+                write.write_all(b"354 go ahead\r\n").await.unwrap();
+                while let Some(line) = lines.next_line().await.unwrap() {
+                    if line == "." {
+                        break;
+                    }
+                    data.push_str(&line);
+                    data.push('\n');
+                }
+                b"250 queued\r\n"
+            } else if command.starts_with("QUIT") {
+                write.write_all(b"221 bye\r\n").await.unwrap();
+                break;
+            } else {
+                b"250 ok\r\n"
+            };
+            write.write_all(reply).await.unwrap();
+        }
+        data
+    }
+
+    #[tokio::test]
+    async fn delivers_to_a_plain_smtp_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(catch_one_message(listener));
+
+        let config = SmtpConfig {
+            host: "127.0.0.1".to_string(),
+            port,
+            username: None,
+            password: None,
+            tls: SmtpTls::None,
+        };
+        let mailer = Smtp::new(&config, "noreply@example.com".parse().unwrap()).unwrap();
+        mailer
+            .send(&Email {
+                template: "test".to_string(),
+                to: "someone@example.com".to_string(),
+                reply_to: None,
+                subject: "Verify your account".to_string(),
+                html: "<p>Welcome</p>".to_string(),
+                text: "Welcome".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let data = server.await.unwrap();
+        assert!(data.contains("Subject: Verify your account"));
+        assert!(data.contains("To: someone@example.com"));
+        assert!(data.contains("<p>Welcome</p>"));
+    }
+}
@@ -0,0 +1,269 @@
+//! Named email templates under `templates/email/`, compiled into the binary.
+//!
+//! Every template has an HTML and a plain-text file per locale, e.g.
+//! `verify_account.fi.html` and `verify_account.fi.txt`. HTML files are
+//! auto-escaped, so user input can't inject markup. The first line of the text
+//! file is `Subject: ...`, followed by a blank line and the body.
+
+use axum::http::{header, HeaderMap};
+use minijinja::{context, Environment, Value};
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    En,
+    Fi,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::Fi];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Fi => "fi",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Locale> {
+        match value.to_lowercase().as_str() {
+            "en" => Some(Locale::En),
+            "fi" => Some(Locale::Fi),
+            _ => None,
+        }
+    }
+
+    /// The first supported language in `Accept-Language`, else `fallback`.
+    /// Browsers list languages by preference, so q-values are not needed.
+    pub fn from_headers(headers: &HeaderMap, fallback: Locale) -> Locale {
+        headers
+            .get(header::ACCEPT_LANGUAGE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| {
+                v.split(',')
+                    .filter_map(|tag| tag.split(';').next()?.trim().split('-').next())
+                    .find_map(Locale::parse)
+            })
+            .unwrap_or(fallback)
+    }
+}
+
+pub struct TemplateInfo {
+    pub name: &'static str,
+    /// Example values for the preview endpoint and tests
+    sample: fn() -> Value,
+}
+
+pub const TEMPLATES: &[TemplateInfo] = &[
+    TemplateInfo {
+        name: "verify_account",
+        sample: || context! { verify_link => "https://example.com/admin/verify?token=abc", hours => 24 },
+    },
+    TemplateInfo {
+        name: "password_reset",
+        sample: || context! { reset_link => "https://example.com/admin/reset?token=abc", minutes => 30 },
+    },
+    TemplateInfo {
+        name: "password_changed",
+        sample: || context! {},
+    },
+    TemplateInfo {
+        name: "confirm_new_email",
+        sample: || context! { verify_link => "https://example.com/admin/verify?token=abc", hours => 24 },
+    },
+    TemplateInfo {
+        name: "email_change_requested",
+        sample: || context! { new_email => "new@example.com" },
+    },
+    TemplateInfo {
+        name: "deletion_scheduled",
+        sample: || context! { scheduled_for => "2026-01-31 12:00" },
+    },
+    TemplateInfo {
+        name: "lockout",
+        sample: || {
+            context! {
+                attempts => 5,
+                minutes => 15,
+                ip => "203.0.113.7",
+                reset_link => "https://example.com/admin/forgot",
+            }
+        },
+    },
+    TemplateInfo {
+        name: "contact_message",
+        sample: || {
+            context! {
+                name => "Maija Meikäläinen",
+                email => "maija@example.com",
+                link => "https://example.com/jobs/42",
+                link_is_web => true,
+                message => "Hei!\n<script>alert(1)</script> is shown as text.",
+            }
+        },
+    },
+    TemplateInfo {
+        name: "contact_reply",
+        sample: || {
+            context! {
+                name => "Maija Meikäläinen",
+                reply => "Thanks for reaching out! Tuesday works for me.",
+                message => "Would you have time for a call next week?",
+                sent_on => "2026-01-20",
+            }
+        },
+    },
+];
+
+impl TemplateInfo {
+    pub fn sample(&self) -> Value {
+        (self.sample)()
+    }
+}
+
+pub fn find(name: &str) -> Option<&'static TemplateInfo> {
+    TEMPLATES.iter().find(|t| t.name == name)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Rendered {
+    /// Name of the template, kept with the queued email
+    pub template: String,
+    pub subject: String,
+    pub html: String,
+    pub text: String,
+}
+
+/// Includes both formats of both locales for one template.
+macro_rules! sources {
+    ($($name:literal),* $(,)?) => {
+        &[
+            ("_layout.html", include_str!("../../../templates/email/_layout.html")),
+            ("_macros.html", include_str!("../../../templates/email/_macros.html")),
+            $(
+                (concat!($name, ".en.html"), include_str!(concat!("../../../templates/email/", $name, ".en.html"))),
+                (concat!($name, ".en.txt"), include_str!(concat!("../../../templates/email/", $name, ".en.txt"))),
+                (concat!($name, ".fi.html"), include_str!(concat!("../../../templates/email/", $name, ".fi.html"))),
+                (concat!($name, ".fi.txt"), include_str!(concat!("../../../templates/email/", $name, ".fi.txt"))),
+            )*
+        ]
+    };
+}
+
+const SOURCES: &[(&str, &str)] = sources!(
+    "verify_account",
+    "password_reset",
+    "password_changed",
+    "confirm_new_email",
+    "email_change_requested",
+    "deletion_scheduled",
+    "lockout",
+    "contact_message",
+    "contact_reply",
+);
+
+fn environment() -> &'static Environment<'static> {
+    static ENV: OnceLock<Environment<'static>> = OnceLock::new();
+    ENV.get_or_init(|| {
+        let mut env = Environment::new();
+        env.set_trim_blocks(true);
+        env.set_lstrip_blocks(true);
+        for (name, source) in SOURCES {
+            env.add_template(name, source)
+                .unwrap_or_else(|e| panic!("email template {} does not compile: {}", name, e));
+        }
+        env
+    })
+}
+
+/// Renders `name` in `locale`. `context` is available to both formats along with `locale`.
+pub fn render(
+    name: &str,
+    locale: Locale,
+    context: impl Serialize,
+) -> Result<Rendered, minijinja::Error> {
+    let env = environment();
+    let context = context! { locale => locale.as_str(), ..Value::from_serialize(context) };
+
+    let html = env
+        .get_template(&format!("{}.{}.html", name, locale.as_str()))?
+        .render(&context)?;
+    let text = env
+        .get_template(&format!("{}.{}.txt", name, locale.as_str()))?
+        .render(&context)?;
+
+    // The header ends at the first blank line, so a value with a line break stays in the subject
+    let (header, body) = text.split_once("\n\n").unwrap_or((&text, ""));
+    let subject = header.strip_prefix("Subject:").ok_or_else(|| {
+        minijinja::Error::new(
+            minijinja::ErrorKind::InvalidOperation,
+            format!(
+                "{}.{}.txt must start with a Subject: header",
+                name,
+                locale.as_str()
+            ),
+        )
+    })?;
+
+    Ok(Rendered {
+        template: name.to_string(),
+        // Values such as a visitor's name must not be able to start a new header
+        subject: subject.split_whitespace().collect::<Vec<_>>().join(" "),
+        html,
+        text: body.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_template_renders_in_every_locale() {
+        for template in TEMPLATES {
+            for locale in Locale::ALL {
+                let rendered = render(template.name, locale, template.sample())
+                    .unwrap_or_else(|e| panic!("{} ({}): {}", template.name, locale.as_str(), e));
+                assert!(!rendered.subject.is_empty(), "{}", template.name);
+                assert!(rendered.html.contains("<h2>"), "{}", template.name);
+                assert!(!rendered.text.contains("<p>"), "{}", template.name);
+            }
+        }
+    }
+
+    #[test]
+    fn html_is_escaped_and_text_is_not() {
+        let rendered = render(
+            "contact_message",
+            Locale::En,
+            context! {
+                name => "Eve\nBcc: victim@example.com",
+                email => "eve@example.com",
+                message => "<script>alert(1)</script> & more",
+            },
+        )
+        .unwrap();
+        assert!(rendered
+            .html
+            .contains("&lt;script&gt;alert(1)&lt;&#x2f;script&gt; &amp; more"));
+        assert!(!rendered.html.contains("<script>"));
+        assert!(rendered.text.contains("<script>alert(1)</script> & more"));
+        assert_eq!(
+            rendered.subject,
+            "Contact Form: Eve Bcc: victim@example.com reached out"
+        );
+    }
+
+    #[test]
+    fn locale_follows_accept_language() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(Locale::from_headers(&headers, Locale::Fi), Locale::Fi);
+        headers.insert(
+            header::ACCEPT_LANGUAGE,
+            "sv-FI, fi-FI;q=0.8, en;q=0.5".parse().unwrap(),
+        );
+        assert_eq!(Locale::from_headers(&headers, Locale::En), Locale::Fi);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::models::contact_message::ContactMessage;
+use crate::routes::intel::CompanyIntel;
+use reqwest::Url;
+
+/// Application fields prefilled from a contact message and, if its link could
+/// be scanned, the intel for that link.
+#[derive(Debug, PartialEq)]
+pub struct LeadDraft {
+    pub company: String,
+    pub company_website: Option<String>,
+    pub role: String,
+    pub salary: Option<String>,
+    pub contact_person: String,
+    pub description: Option<String>,
+    pub logo_url: Option<String>,
+}
+
+/// The link, if it is a web page the intel scanner can fetch.
+pub fn web_link(message: &ContactMessage) -> Option<Url> {
+    let url = Url::parse(message.link.as_deref()?.trim()).ok()?;
+    matches!(url.scheme(), "http" | "https").then_some(url)
+}
+
+/// `careers.acme.fi` -> `acme.fi`; the best company name we have without intel.
+fn host_name(host: &str) -> String {
+    host.trim_start_matches("www.")
+        .trim_start_matches("careers.")
+        .trim_start_matches("jobs.")
+        .to_string()
+}
+
+pub fn draft(message: &ContactMessage, intel: CompanyIntel) -> LeadDraft {
+    let link = web_link(message);
+    let job = intel.job.unwrap_or_default();
+
+    // The sender's own domain is a decent guess when the link is a job board
+    let company = intel
+        .company_name
+        .or(job.hiring_organization)
+        .or_else(|| {
+            message
+                .email
+                .rsplit_once('@')
+                .map(|(_, domain)| host_name(domain))
+        })
+        .or_else(|| link.as_ref().and_then(|l| l.host_str()).map(host_name))
+        .unwrap_or_else(|| message.name.clone());
+
+    LeadDraft {
+        company,
+        company_website: link.map(|l| l.to_string()),
+        role: job.title.unwrap_or_else(|| "Open position".to_string()),
+        salary: job.salary.map(|s| s.display()).filter(|s| !s.is_empty()),
+        contact_person: format!("{} <{}>", message.name, message.email),
+        // The message itself is private; applications are shown publicly once they leave Lead
+        description: job.description.or(intel.description),
+        logo_url: intel.logo_url,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(email: &str, link: Option<&str>) -> ContactMessage {
+        ContactMessage {
+            id: uuid::Uuid::new_v4(),
+            name: "Riikka Rekrytoija".to_string(),
+            email: email.to_string(),
+            message: "We have a role for you".to_string(),
+            link: link.map(str::to_string),
+            locale: "fi".to_string(),
+            status: "unread".to_string(),
+            replied_at: None,
+            spam_score: 0,
+            spam_reasons: Vec::new(),
+            created_at: chrono::Utc::now().naive_utc(),
+            application_id: None,
+        }
+    }
+
+    #[test]
+    fn falls_back_to_the_sender_without_intel() {
+        let draft = draft(
+            &message("riikka@acme.fi", Some("javascript:alert(1)")),
+            CompanyIntel::default(),
+        );
+        assert_eq!(draft.company, "acme.fi");
+        assert_eq!(draft.company_website, None);
+        assert_eq!(draft.role, "Open position");
+        assert_eq!(draft.contact_person, "Riikka Rekrytoija <riikka@acme.fi>");
+        assert_eq!(draft.description, None);
+    }
+
+    #[test]
+    fn prefers_scanned_job_posting() {
+        let intel: CompanyIntel = serde_json::from_value(serde_json::json!({
+            "company_name": "Acme Oy",
+            "description": "We make anvils",
+            "logo_url": "/uploads/logos/acme.png",
+            "job": {
+                "title": "Rust Developer",
+                "salary": { "min": 4000.0, "max": 5200.0, "currency": "EUR", "unit": "MONTH" },
+                "description": "Build backends"
+            },
+            "source": "generic"
+        }))
+        .unwrap();
+
+        let draft = draft(
+            &message("riikka@recruiting.example", Some("https://jobs.acme.fi/1")),
+            intel,
+        );
+        assert_eq!(draft.company, "Acme Oy");
+        assert_eq!(
+            draft.company_website.as_deref(),
+            Some("https://jobs.acme.fi/1")
+        );
+        assert_eq!(draft.role, "Rust Developer");
+        assert_eq!(draft.salary.as_deref(), Some("4000–5200 EUR / MONTH"));
+        assert_eq!(draft.description.as_deref(), Some("Build backends"));
+        assert_eq!(draft.logo_url.as_deref(), Some("/uploads/logos/acme.png"));
+    }
+}
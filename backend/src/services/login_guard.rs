@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Failures allowed before any delay kicks in (typos happen)
+const FREE_ATTEMPTS: u32 = 3;
+/// Failure count at which the account is locked and the owner is emailed
+pub const LOCKOUT_THRESHOLD: u32 = 10;
+pub const LOCKOUT_DURATION: Duration = Duration::from_secs(15 * 60);
+/// A quiet hour wipes the slate clean
+const FORGET_AFTER: Duration = Duration::from_secs(60 * 60);
+
+struct Attempts {
+    failures: u32,
+    last_failure: Instant,
+    blocked_until: Option<Instant>,
+}
+
+/// Tracks failed logins per key (normalized email) with exponential backoff and
+/// a temporary lockout. Every attempt is counted up front, under the same lock as
+/// the check, and only a successful login takes it back; otherwise a burst of
+/// parallel requests would all pass the check before any failure was recorded.
+/// Unknown and existing emails are tracked the same way, so the lockout itself
+/// does not reveal which accounts exist. In-memory like `RateLimiter`, so it
+/// resets on restart.
+#[derive(Clone, Default)]
+pub struct LoginGuard {
+    attempts: Arc<Mutex<HashMap<String, Attempts>>>,
+}
+
+/// What an admitted attempt counts as if the password turns out to be wrong.
+#[derive(Debug, PartialEq)]
+pub enum Failure {
+    Counted,
+    /// This attempt crossed the lockout threshold; notify the account owner
+    LockedOut,
+}
+
+fn backoff(failures: u32) -> Option<Duration> {
+    if failures >= LOCKOUT_THRESHOLD {
+        Some(LOCKOUT_DURATION)
+    } else if failures >= FREE_ATTEMPTS {
+        Some(Duration::from_secs(1 << (failures - FREE_ATTEMPTS)))
+    } else {
+        None
+    }
+}
+
+impl LoginGuard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `Err(wait)` if `key` must wait before trying again. Otherwise the attempt
+    /// is counted as a failure until `record_success` clears it.
+    pub fn check(&self, key: &str) -> Result<Failure, Duration> {
+        self.check_at(key, Instant::now())
+    }
+
+    pub fn record_success(&self, key: &str) {
+        self.lock().remove(key);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Attempts>> {
+        self.attempts.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn check_at(&self, key: &str, now: Instant) -> Result<Failure, Duration> {
+        let mut attempts = self.lock();
+
+        if let Some(until) = attempts.get(key).and_then(|a| a.blocked_until) {
+            if until > now {
+                return Err(until - now);
+            }
+        }
+
+        if attempts.len() > 10_000 {
+            attempts.retain(|_, a| now.duration_since(a.last_failure) < FORGET_AFTER);
+        }
+
+        let entry = attempts.entry(key.to_string()).or_insert(Attempts {
+            failures: 0,
+            last_failure: now,
+            blocked_until: None,
+        });
+        if now.duration_since(entry.last_failure) >= FORGET_AFTER {
+            entry.failures = 0;
+        }
+
+        entry.failures += 1;
+        entry.last_failure = now;
+        entry.blocked_until = backoff(entry.failures).map(|d| now + d);
+
+        Ok(if entry.failures == LOCKOUT_THRESHOLD {
+            Failure::LockedOut
+        } else {
+            Failure::Counted
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_then_locks() {
+        assert_eq!(backoff(FREE_ATTEMPTS - 1), None);
+        assert_eq!(backoff(FREE_ATTEMPTS), Some(Duration::from_secs(1)));
+        assert_eq!(backoff(FREE_ATTEMPTS + 3), Some(Duration::from_secs(8)));
+        assert_eq!(backoff(LOCKOUT_THRESHOLD), Some(LOCKOUT_DURATION));
+        assert_eq!(backoff(LOCKOUT_THRESHOLD + 5), Some(LOCKOUT_DURATION));
+    }
+
+    #[test]
+    fn locks_out_once_at_threshold() {
+        let guard = LoginGuard::new();
+        let start = Instant::now();
+        let mut locked = 0;
+        for i in 0..LOCKOUT_THRESHOLD + 2 {
+            // Wait out each backoff so every attempt is a real one
+            let now = start + Duration::from_secs(1000 * i as u64);
+            if guard.check_at("a@b.c", now) == Ok(Failure::LockedOut) {
+                locked += 1;
+                assert_eq!(i + 1, LOCKOUT_THRESHOLD);
+                assert!(guard
+                    .check_at("a@b.c", now + Duration::from_secs(60))
+                    .is_err());
+            }
+        }
+        assert_eq!(locked, 1);
+    }
+
+    #[test]
+    fn parallel_attempts_are_counted_before_verification() {
+        let guard = LoginGuard::new();
+        let now = Instant::now();
+        let admitted = (0..50).filter(|_| guard.check_at("k", now).is_ok()).count();
+        assert_eq!(admitted as u32, FREE_ATTEMPTS);
+    }
+
+    #[test]
+    fn success_and_quiet_period_reset() {
+        let guard = LoginGuard::new();
+        let now = Instant::now();
+        for _ in 0..FREE_ATTEMPTS {
+            assert!(guard.check_at("k", now).is_ok());
+        }
+        assert!(guard.check_at("k", now).is_err());
+        guard.record_success("k");
+        assert!(guard.check_at("k", now).is_ok());
+        guard.record_success("k");
+
+        for _ in 0..FREE_ATTEMPTS - 1 {
+            assert!(guard.check_at("k", now).is_ok());
+        }
+        assert!(guard.check_at("k", now + FORGET_AFTER).is_ok());
+        assert!(guard.check_at("k", now + FORGET_AFTER).is_ok());
+    }
+}
@@ -0,0 +1,88 @@
+use crate::routes::upload;
+use crate::services::safe_fetch;
+use image::ImageFormat;
+use sha2::{Digest, Sha256};
+use std::io::Cursor;
+use std::path::Path;
+use tokio::fs;
+
+const MAX_LOGO_BYTES: usize = 2 * 1024 * 1024;
+const LOGO_DIR: &str = "uploads/logos";
+const LOGO_SIZE: u32 = 256;
+
+/// Downloads a remote logo, validates that it decodes as an image and stores it
+/// re-encoded as PNG under `uploads/logos/`. Returns the local `/uploads/...` URL.
+///
+/// Files are content-addressed so the same logo scanned by several users is stored
+/// once and is not tied to any one account's uploads.
+pub async fn mirror_logo(url: &str) -> Option<String> {
+    let fetched = match safe_fetch::fetch(url, MAX_LOGO_BYTES).await {
+        Ok(f) => f,
+        Err(e) => {
+            tracing::warn!("LOGO: Fetch of {} failed: {}", url, e);
+            return None;
+        }
+    };
+    if fetched.body.len() >= MAX_LOGO_BYTES {
+        tracing::warn!("LOGO: {} is larger than {} bytes", url, MAX_LOGO_BYTES);
+        return None;
+    }
+
+    let png = tokio::task::spawn_blocking(move || normalize(&fetched.body))
+        .await
+        .ok()
+        .flatten();
+    let Some(png) = png else {
+        tracing::warn!("LOGO: {} is not a supported image", url);
+        return None;
+    };
+    if let Err(e) = upload::validate("png", &png) {
+        tracing::warn!("LOGO: {} rejected by upload validation: {:?}", url, e);
+        return None;
+    }
+
+    let name = format!("{:x}.png", Sha256::digest(&png));
+    let filepath = format!("{}/{}", LOGO_DIR, name);
+
+    if !Path::new(&filepath).exists() {
+        if let Err(e) = fs::create_dir_all(LOGO_DIR).await {
+            tracing::error!("LOGO: Failed to create {}: {}", LOGO_DIR, e);
+            return None;
+        }
+        if let Err(e) = fs::write(&filepath, &png).await {
+            tracing::error!("LOGO: Failed to write {}: {}", filepath, e);
+            return None;
+        }
+    }
+
+    tracing::info!("LOGO: Mirrored {} to {}", url, filepath);
+    Some(format!("/uploads/logos/{}", name))
+}
+
+/// Decoding and re-encoding drops anything that is not pixel data (SVG scripts,
+/// HTML error pages served as images, trailing payloads) and caps the size.
+fn normalize(data: &[u8]) -> Option<Vec<u8>> {
+    let img = image::load_from_memory(data).ok()?;
+    let img = if img.width() > LOGO_SIZE || img.height() > LOGO_SIZE {
+        img.thumbnail(LOGO_SIZE, LOGO_SIZE)
+    } else {
+        img
+    };
+
+    let mut out = Cursor::new(Vec::new());
+    img.write_to(&mut out, ImageFormat::Png).ok()?;
+    Some(out.into_inner())
+}
+
+/// True for logos that still point at a third-party host.
+pub fn is_remote(url: &str) -> bool {
+    url.starts_with("http://") || url.starts_with("https://")
+}
+
+/// Swaps a remote logo URL for its mirrored copy, keeping the original if mirroring fails.
+pub async fn localize(url: Option<String>) -> Option<String> {
+    match url {
+        Some(remote) if is_remote(&remote) => mirror_logo(&remote).await.or(Some(remote)),
+        other => other,
+    }
+}
@@ -0,0 +1,13 @@
+pub mod account_deletion;
+pub mod api_tokens;
+pub mod cookies;
+pub mod documents;
+pub mod email;
+pub mod leads;
+pub mod login_guard;
+pub mod logos;
+pub mod rate_limit;
+pub mod safe_fetch;
+pub mod sessions;
+pub mod spam;
+pub mod totp;
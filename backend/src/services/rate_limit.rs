@@ -0,0 +1,52 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// In-memory sliding-window limiter keyed by an arbitrary string (user id, ip hash, ...).
+/// State is per process and resets on restart, which is fine for a single-instance deployment.
+#[derive(Clone)]
+pub struct RateLimiter {
+    hits: Arc<Mutex<HashMap<String, VecDeque<Instant>>>>,
+    max: usize,
+    window: Duration,
+}
+
+impl RateLimiter {
+    pub fn new(max: usize, window: Duration) -> Self {
+        Self {
+            hits: Arc::new(Mutex::new(HashMap::new())),
+            max,
+            window,
+        }
+    }
+
+    /// Records an attempt for `key`. Returns `false` if the key is over its limit.
+    pub fn check(&self, key: &str) -> bool {
+        let now = Instant::now();
+        let mut hits = self.hits.lock().unwrap_or_else(|e| e.into_inner());
+
+        // Keep the map from growing without bound when many distinct keys show up
+        if hits.len() > 10_000 {
+            hits.retain(|_, times| {
+                times
+                    .back()
+                    .is_some_and(|t| now.duration_since(*t) < self.window)
+            });
+        }
+
+        let times = hits.entry(key.to_string()).or_default();
+        while times
+            .front()
+            .is_some_and(|t| now.duration_since(*t) >= self.window)
+        {
+            times.pop_front();
+        }
+
+        if times.len() >= self.max {
+            return false;
+        }
+
+        times.push_back(now);
+        true
+    }
+}
@@ -0,0 +1,244 @@
+use reqwest::{redirect::Policy, Client, Url};
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+use url::Host;
+
+const USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36";
+const MAX_REDIRECTS: usize = 5;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug)]
+pub enum FetchError {
+    InvalidUrl,
+    BlockedDestination,
+    TooManyRedirects,
+    Timeout,
+    Upstream(String),
+}
+
+impl FetchError {
+    pub fn message(&self) -> &'static str {
+        match self {
+            FetchError::InvalidUrl => "Invalid URL (only http and https are allowed)",
+            FetchError::BlockedDestination => "URL points to a private or reserved address",
+            FetchError::TooManyRedirects => "Too many redirects",
+            FetchError::Timeout => "Remote site timed out",
+            FetchError::Upstream(_) => "Failed to reach URL",
+        }
+    }
+}
+
+impl std::fmt::Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FetchError::Upstream(detail) => write!(f, "{}: {}", self.message(), detail),
+            _ => f.write_str(self.message()),
+        }
+    }
+}
+
+impl From<reqwest::Error> for FetchError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            FetchError::Timeout
+        } else {
+            FetchError::Upstream(e.to_string())
+        }
+    }
+}
+
+pub struct FetchedResource {
+    pub final_url: Url,
+    pub body: Vec<u8>,
+}
+
+/// GETs a user-supplied URL without letting it reach internal hosts.
+///
+/// Every hop (including redirects, which are followed manually) is resolved up
+/// front, rejected if any address is private/loopback/link-local/reserved, and the
+/// connection is pinned to the vetted address so DNS cannot be rebound in between.
+/// The body is read up to `max_bytes`; anything beyond that is dropped.
+pub async fn fetch(url: &str, max_bytes: usize) -> Result<FetchedResource, FetchError> {
+    let mut url = Url::parse(url.trim()).map_err(|_| FetchError::InvalidUrl)?;
+
+    for _ in 0..=MAX_REDIRECTS {
+        let addr = resolve_public(&url).await?;
+
+        let mut builder = Client::builder()
+            .user_agent(USER_AGENT)
+            .redirect(Policy::none())
+            .no_proxy()
+            .timeout(REQUEST_TIMEOUT)
+            .connect_timeout(CONNECT_TIMEOUT);
+        if let Some(Host::Domain(domain)) = url.host() {
+            builder = builder.resolve(domain, addr);
+        }
+        let client = builder.build()?;
+
+        let mut resp = client.get(url.clone()).send().await?;
+
+        if resp.status().is_redirection() {
+            let location = resp
+                .headers()
+                .get(reqwest::header::LOCATION)
+                .and_then(|v| v.to_str().ok())
+                .ok_or_else(|| FetchError::Upstream("Redirect without location".to_string()))?;
+            url = url.join(location).map_err(|_| FetchError::InvalidUrl)?;
+            continue;
+        }
+
+        if !resp.status().is_success() {
+            return Err(FetchError::Upstream(format!("HTTP {}", resp.status())));
+        }
+
+        let mut body = Vec::new();
+        while let Some(chunk) = resp.chunk().await? {
+            let remaining = max_bytes - body.len();
+            if chunk.len() >= remaining {
+                body.extend_from_slice(&chunk[..remaining]);
+                break;
+            }
+            body.extend_from_slice(&chunk);
+        }
+
+        return Ok(FetchedResource {
+            final_url: url,
+            body,
+        });
+    }
+
+    Err(FetchError::TooManyRedirects)
+}
+
+/// Checks the scheme and resolves the host, returning the address to connect to.
+async fn resolve_public(url: &Url) -> Result<SocketAddr, FetchError> {
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(FetchError::InvalidUrl);
+    }
+    let port = url.port_or_known_default().ok_or(FetchError::InvalidUrl)?;
+
+    let addrs: Vec<SocketAddr> = match url.host().ok_or(FetchError::InvalidUrl)? {
+        Host::Ipv4(ip) => vec![SocketAddr::new(IpAddr::V4(ip), port)],
+        Host::Ipv6(ip) => vec![SocketAddr::new(IpAddr::V6(ip), port)],
+        Host::Domain(domain) => tokio::net::lookup_host((domain, port))
+            .await
+            .map_err(|e| FetchError::Upstream(e.to_string()))?
+            .collect(),
+    };
+
+    if addrs.is_empty() {
+        return Err(FetchError::Upstream("Host did not resolve".to_string()));
+    }
+    if addrs.iter().any(|a| is_forbidden(a.ip())) {
+        tracing::warn!("SAFE_FETCH: Blocked fetch to {} ({:?})", url, addrs);
+        return Err(FetchError::BlockedDestination);
+    }
+
+    Ok(addrs[0])
+}
+
+fn is_forbidden(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            v4.is_private()
+                || v4.is_loopback()
+                || v4.is_link_local() // includes 169.254.169.254 cloud metadata
+                || v4.is_broadcast()
+                || v4.is_documentation()
+                || v4.is_unspecified()
+                || v4.is_multicast()
+                || a == 0
+                || a >= 240
+                || (a == 100 && (b & 0xc0) == 64) // 100.64.0.0/10 carrier-grade NAT
+                || (a == 198 && (b & 0xfe) == 18) // 198.18.0.0/15 benchmarking
+        }
+        IpAddr::V6(v6) => {
+            let segments = v6.segments();
+            if let Some(v4) = v6.to_ipv4_mapped() {
+                return is_forbidden(IpAddr::V4(v4));
+            }
+            v6.is_loopback()
+                || v6.is_unspecified()
+                || v6.is_multicast()
+                || (segments[0] & 0xfe00) == 0xfc00 // unique local
+                || (segments[0] & 0xffc0) == 0xfe80 // link-local
+                || (segments[0] == 0x2001 && segments[1] == 0x0db8) // documentation
+                || (segments[0] == 0x64 && segments[1] == 0xff9b) // NAT64
+                || segments[0] == 0x2002 // 6to4 tunnels to an arbitrary IPv4 host
+                || segments[..6].iter().all(|&s| s == 0) // deprecated IPv4-compatible ::a.b.c.d
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn forbidden(ip: &str) -> bool {
+        is_forbidden(ip.parse().unwrap())
+    }
+
+    #[test]
+    fn blocks_internal_ipv4_ranges() {
+        for ip in [
+            "127.0.0.1",
+            "10.0.0.1",
+            "172.16.5.4",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+        ] {
+            assert!(forbidden(ip), "{} should be blocked", ip);
+        }
+        assert!(!forbidden("93.184.216.34"));
+    }
+
+    #[test]
+    fn blocks_internal_ipv6_ranges() {
+        for ip in [
+            "::1",
+            "::",
+            "fe80::1",
+            "fc00::1",
+            "fd12:3456::1",
+            "::ffff:127.0.0.1",
+            "::ffff:169.254.169.254",
+            "2002:7f00:1::1",
+            "2002:5db8:d822::1",
+            "::127.0.0.1",
+            "::10.0.0.1",
+        ] {
+            assert!(forbidden(ip), "{} should be blocked", ip);
+        }
+        assert!(!forbidden("2606:2800:220:1:248:1893:25c8:1946"));
+        assert!(!forbidden("::ffff:93.184.216.34"));
+    }
+
+    #[tokio::test]
+    async fn resolve_public_rejects_literal_internal_hosts() {
+        for url in [
+            "http://127.0.0.1/",
+            "http://169.254.169.254/latest/meta-data/",
+            "http://[::ffff:10.0.0.1]/",
+            "http://[2002:a00:1::1]/",
+        ] {
+            let url = Url::parse(url).unwrap();
+            assert!(matches!(
+                resolve_public(&url).await,
+                Err(FetchError::BlockedDestination)
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn resolve_public_rejects_other_schemes() {
+        let url = Url::parse("file:///etc/passwd").unwrap();
+        assert!(matches!(
+            resolve_public(&url).await,
+            Err(FetchError::InvalidUrl)
+        ));
+    }
+}
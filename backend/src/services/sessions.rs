@@ -0,0 +1,280 @@
+use crate::routes::auth::Claims;
+use axum::http::HeaderMap;
+use jsonwebtoken::{encode, EncodingKey, Header};
+use rand::{distributions::Alphanumeric, Rng};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::net::{IpAddr, SocketAddr};
+use uuid::Uuid;
+
+pub const ACCESS_TOKEN_MINUTES: i64 = 15;
+pub const REFRESH_TOKEN_DAYS: i32 = 30;
+/// Two tabs refreshing at once present the same token; the loser should not kill the session
+const REUSE_GRACE_SECONDS: i32 = 30;
+
+#[derive(Serialize)]
+pub struct TokenPair {
+    pub token: String,
+    pub refresh_token: String,
+    #[serde(skip)]
+    pub session_id: Uuid,
+}
+
+#[derive(Debug)]
+pub enum SessionError {
+    InvalidToken,
+    /// A refresh token that was already rotated away came back: treat it as stolen
+    Reused,
+    Database(sqlx::Error),
+    Jwt(jsonwebtoken::errors::Error),
+}
+
+impl std::fmt::Display for SessionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SessionError::InvalidToken => f.write_str("Invalid refresh token"),
+            SessionError::Reused => f.write_str("Refresh token reused"),
+            SessionError::Database(e) => write!(f, "Database error: {}", e),
+            SessionError::Jwt(e) => write!(f, "Token creation failed: {}", e),
+        }
+    }
+}
+
+impl From<sqlx::Error> for SessionError {
+    fn from(e: sqlx::Error) -> Self {
+        SessionError::Database(e)
+    }
+}
+
+impl From<jsonwebtoken::errors::Error> for SessionError {
+    fn from(e: jsonwebtoken::errors::Error) -> Self {
+        SessionError::Jwt(e)
+    }
+}
+
+/// Device details shown on the sessions page. Both come straight from request
+/// headers, so they are for display only; key limits on [`client_ip`] instead.
+pub struct ClientInfo {
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+impl ClientInfo {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let user_agent = headers
+            .get(axum::http::header::USER_AGENT)
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.chars().take(512).collect());
+        let ip_address = headers
+            .get("x-forwarded-for")
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.split(',').next().unwrap_or(s).trim().to_string());
+
+        Self {
+            user_agent,
+            ip_address,
+        }
+    }
+}
+
+/// The address rate limits and spam hashes are keyed on. `X-Forwarded-For` is only
+/// believed when `trust_proxy` is set, and then only its last hop: the one our own
+/// proxy appended. Earlier entries are whatever the client chose to send.
+pub fn client_ip(headers: &HeaderMap, peer: SocketAddr, trust_proxy: bool) -> IpAddr {
+    if trust_proxy {
+        let forwarded = headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .last()
+            .and_then(|hop| hop.trim().parse().ok());
+        if let Some(ip) = forwarded {
+            return ip;
+        }
+    }
+    peer.ip()
+}
+
+pub fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+fn generate_refresh_token() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(64)
+        .map(char::from)
+        .collect()
+}
+
+pub fn issue_access_token(
+    secret: &str,
+    user_id: Uuid,
+    session_id: Uuid,
+) -> Result<String, jsonwebtoken::errors::Error> {
+    let expiration = chrono::Utc::now()
+        .checked_add_signed(chrono::Duration::minutes(ACCESS_TOKEN_MINUTES))
+        .expect("valid timestamp")
+        .timestamp() as usize;
+
+    let claims = Claims {
+        sub: user_id.to_string(),
+        sid: session_id.to_string(),
+        exp: expiration,
+        via_cookie: false,
+    };
+
+    encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(secret.as_bytes()),
+    )
+}
+
+/// Opens a new session after a successful login.
+pub async fn create_session(
+    pool: &PgPool,
+    secret: &str,
+    user_id: Uuid,
+    client: &ClientInfo,
+) -> Result<TokenPair, SessionError> {
+    let refresh_token = generate_refresh_token();
+
+    let session_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO sessions (user_id, refresh_token_hash, user_agent, ip_address, expires_at)
+         VALUES ($1, $2, $3, $4, NOW() + make_interval(days => $5)) RETURNING id",
+    )
+    .bind(user_id)
+    .bind(hash_token(&refresh_token))
+    .bind(&client.user_agent)
+    .bind(&client.ip_address)
+    .bind(REFRESH_TOKEN_DAYS)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(TokenPair {
+        token: issue_access_token(secret, user_id, session_id)?,
+        refresh_token,
+        session_id,
+    })
+}
+
+/// Swaps a refresh token for a new pair. The old refresh token stops working immediately.
+pub async fn rotate(
+    pool: &PgPool,
+    secret: &str,
+    refresh_token: &str,
+    client: &ClientInfo,
+) -> Result<TokenPair, SessionError> {
+    let presented = hash_token(refresh_token);
+    let next = generate_refresh_token();
+
+    let rotated: Option<(Uuid, Uuid)> = sqlx::query_as(
+        "UPDATE sessions
+         SET previous_token_hash = refresh_token_hash, refresh_token_hash = $2, last_used_at = NOW(),
+             user_agent = COALESCE($3, user_agent), ip_address = COALESCE($4, ip_address)
+         WHERE refresh_token_hash = $1 AND revoked_at IS NULL AND expires_at > NOW()
+         RETURNING id, user_id",
+    )
+    .bind(&presented)
+    .bind(hash_token(&next))
+    .bind(&client.user_agent)
+    .bind(&client.ip_address)
+    .fetch_optional(pool)
+    .await?;
+
+    if let Some((session_id, user_id)) = rotated {
+        return Ok(TokenPair {
+            token: issue_access_token(secret, user_id, session_id)?,
+            refresh_token: next,
+            session_id,
+        });
+    }
+
+    let reused: Option<Uuid> = sqlx::query_scalar(
+        "UPDATE sessions SET revoked_at = NOW()
+         WHERE previous_token_hash = $1 AND revoked_at IS NULL
+           AND last_used_at < NOW() - make_interval(secs => $2)
+         RETURNING id",
+    )
+    .bind(&presented)
+    .bind(REUSE_GRACE_SECONDS as f64)
+    .fetch_optional(pool)
+    .await?;
+
+    match reused {
+        Some(session_id) => {
+            tracing::warn!(
+                "SESSIONS: Refresh token reuse on session {}, revoked",
+                session_id
+            );
+            Err(SessionError::Reused)
+        }
+        None => Err(SessionError::InvalidToken),
+    }
+}
+
+/// Checked on every authenticated request so revocation takes effect before the JWT expires.
+pub async fn is_active(
+    pool: &PgPool,
+    session_id: Uuid,
+    user_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM sessions WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL AND expires_at > NOW())",
+    )
+    .bind(session_id)
+    .bind(user_id)
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn revoke(pool: &PgPool, user_id: Uuid, session_id: Uuid) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE sessions SET revoked_at = NOW() WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL",
+    )
+    .bind(session_id)
+    .bind(user_id)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn revoke_all<'e>(
+    executor: impl sqlx::PgExecutor<'e>,
+    user_id: Uuid,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE sessions SET revoked_at = NOW() WHERE user_id = $1 AND revoked_at IS NULL",
+    )
+    .bind(user_id)
+    .execute(executor)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::client_ip;
+    use axum::http::HeaderMap;
+    use std::net::SocketAddr;
+
+    #[test]
+    fn client_ip_ignores_forwarded_for_unless_trusted() {
+        let peer: SocketAddr = "10.0.0.2:51000".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", "1.2.3.4, 203.0.113.7".parse().unwrap());
+
+        assert_eq!(client_ip(&headers, peer, false).to_string(), "10.0.0.2");
+        assert_eq!(client_ip(&headers, peer, true).to_string(), "203.0.113.7");
+
+        headers.insert("x-forwarded-for", "garbage".parse().unwrap());
+        assert_eq!(client_ip(&headers, peer, true).to_string(), "10.0.0.2");
+        assert_eq!(
+            client_ip(&HeaderMap::new(), peer, true).to_string(),
+            "10.0.0.2"
+        );
+    }
+}
@@ -0,0 +1,431 @@
+//! Spam scoring for public submissions: comments and contact messages.
+//!
+//! Every signal adds points, and a submission at `SUSPICIOUS_SCORE` or above is
+//! held for moderation instead of being published. Nothing is dropped, so a
+//! false positive costs the owner a click rather than a lost message. Only a
+//! flood from one address is refused outright.
+
+use crate::config::Config;
+use crate::services::rate_limit::RateLimiter;
+use crate::services::sessions;
+use axum::http::HeaderMap;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+pub const SUSPICIOUS_SCORE: i32 = 50;
+const HONEYPOT_POINTS: i32 = 100;
+const RATE_LIMITED_POINTS: i32 = 50;
+const DUPLICATE_POINTS: i32 = 50;
+const BLOCKED_DOMAIN_POINTS: i32 = 60;
+const BLOCKED_WORD_POINTS: i32 = 40;
+/// Per link beyond the ones the form expects
+const LINK_POINTS: i32 = 20;
+const TOO_FAST_POINTS: i32 = 40;
+/// Scripts posting straight to the API don't report a fill time
+const NO_TIMING_POINTS: i32 = 10;
+
+/// Faster than this, nobody typed the message
+const MIN_FILL_TIME: Duration = Duration::from_secs(3);
+/// Submissions per address before more of them count as suspicious
+const SOFT_LIMIT: usize = 3;
+const SOFT_WINDOW: Duration = Duration::from_secs(10 * 60);
+/// Beyond this a request is refused, to keep floods out of the database
+const HARD_LIMIT: usize = 20;
+const HARD_WINDOW: Duration = Duration::from_secs(60 * 60);
+const DUPLICATE_WINDOW_HOURS: i32 = 24;
+
+/// Used when `SPAM_BLOCKED_WORDS` / `SPAM_BLOCKED_DOMAINS` are not set
+pub const DEFAULT_BLOCKED_WORDS: &[&str] = &[
+    "viagra",
+    "cialis",
+    "casino",
+    "backlinks",
+    "seo services",
+    "crypto giveaway",
+];
+pub const DEFAULT_BLOCKED_DOMAINS: &[&str] = &["bit.ly", "tinyurl.com"];
+
+/// Words and phrases match whole words, case-insensitively. Domains also match
+/// their subdomains.
+#[derive(Debug, Clone)]
+pub struct Blocklist {
+    words: Vec<String>,
+    domains: Vec<String>,
+}
+
+impl Blocklist {
+    pub fn new<S: AsRef<str>>(words: &[S], domains: &[S]) -> Self {
+        Blocklist {
+            words: words
+                .iter()
+                .map(|w| normalize(w.as_ref()))
+                .filter(|w| !w.is_empty())
+                .collect(),
+            domains: domains
+                .iter()
+                .map(|d| d.as_ref().trim().trim_start_matches('.').to_lowercase())
+                .filter(|d| !d.is_empty())
+                .collect(),
+        }
+    }
+}
+
+impl Default for Blocklist {
+    fn default() -> Self {
+        Blocklist::new(DEFAULT_BLOCKED_WORDS, DEFAULT_BLOCKED_DOMAINS)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Submission<'a> {
+    /// Everything the sender filled in
+    pub fields: Vec<&'a str>,
+    /// Links the form asks for, like the contact form's job link
+    pub free_links: usize,
+    pub honeypot: bool,
+    /// Time from the form being shown to it being sent, as reported by the page
+    pub fill_time: Option<Duration>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Verdict {
+    pub score: i32,
+    /// Shown to the owner in moderation, e.g. "3 links"
+    pub reasons: Vec<String>,
+}
+
+impl Verdict {
+    fn add(&mut self, points: i32, reason: impl Into<String>) {
+        self.score += points;
+        self.reasons.push(reason.into());
+    }
+
+    pub fn is_suspicious(&self) -> bool {
+        self.score >= SUSPICIOUS_SCORE
+    }
+}
+
+/// Lower-case words separated by single spaces.
+fn normalize(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Same for messages that differ only in case, spacing or punctuation.
+pub fn fingerprint(fields: &[&str]) -> String {
+    format!("{:x}", Sha256::digest(normalize(&fields.join(" "))))
+}
+
+/// Hosts of everything that looks like a link: `https://...`, `www....` and
+/// bare `name.tld` words.
+fn hosts(text: &str) -> Vec<(String, bool)> {
+    text.split(|c: char| c.is_whitespace() || "<>\"'()[]{},;".contains(c))
+        .filter_map(|token| {
+            let token = token.trim_end_matches(['.', '!', '?', ':']);
+            let lower = token.to_lowercase();
+            let (rest, is_link) = match lower.split_once("://") {
+                Some((_, rest)) => (rest, true),
+                None => (lower.as_str(), lower.starts_with("www.")),
+            };
+            let host = rest.split(['/', '?', '#']).next()?.split(':').next()?;
+            let plausible = host.contains('.')
+                && host.rsplit('.').next().is_some_and(|tld| {
+                    tld.len() >= 2 && tld.chars().all(|c| c.is_ascii_alphabetic())
+                });
+            plausible.then(|| (host.to_string(), is_link))
+        })
+        .collect()
+}
+
+/// Everything except the lookups that need state: whether the same text was
+/// seen recently and whether the sender is over the soft rate limit.
+pub fn score(
+    submission: &Submission,
+    blocklist: &Blocklist,
+    duplicate: bool,
+    rate_limited: bool,
+) -> Verdict {
+    let mut verdict = Verdict::default();
+    let text = submission.fields.join("\n");
+
+    if submission.honeypot {
+        verdict.add(HONEYPOT_POINTS, "honeypot filled");
+    }
+    if rate_limited {
+        verdict.add(RATE_LIMITED_POINTS, "too many submissions");
+    }
+    if duplicate {
+        verdict.add(DUPLICATE_POINTS, "duplicate content");
+    }
+
+    let hosts = hosts(&text);
+    let links = hosts.iter().filter(|(_, is_link)| *is_link).count();
+    let extra_links = links.saturating_sub(submission.free_links);
+    if extra_links > 0 {
+        verdict.add(
+            LINK_POINTS * extra_links as i32,
+            format!("{} link(s)", links),
+        );
+    }
+
+    for domain in &blocklist.domains {
+        let suffix = format!(".{}", domain);
+        if hosts
+            .iter()
+            .any(|(host, _)| host == domain || host.ends_with(&suffix))
+        {
+            verdict.add(BLOCKED_DOMAIN_POINTS, format!("blocked domain {}", domain));
+        }
+    }
+
+    let words = format!(" {} ", normalize(&text));
+    for word in &blocklist.words {
+        if words.contains(&format!(" {} ", word)) {
+            verdict.add(BLOCKED_WORD_POINTS, format!("blocked word \"{}\"", word));
+        }
+    }
+
+    match submission.fill_time {
+        Some(time) if time < MIN_FILL_TIME => verdict.add(
+            TOO_FAST_POINTS,
+            format!("sent {}s after opening", time.as_secs()),
+        ),
+        Some(_) => {}
+        None => verdict.add(NO_TIMING_POINTS, "no fill time"),
+    }
+
+    verdict
+}
+
+/// Which table a submission goes to, for duplicate detection.
+#[derive(Debug, Clone, Copy)]
+pub enum Source {
+    Comment,
+    ContactMessage,
+}
+
+impl Source {
+    fn table(self) -> &'static str {
+        match self {
+            Source::Comment => "comments",
+            Source::ContactMessage => "contact_messages",
+        }
+    }
+}
+
+pub struct Assessment {
+    pub verdict: Verdict,
+    pub ip_hash: String,
+    pub content_hash: String,
+}
+
+/// Rate limits and blocklist shared by the public forms.
+#[derive(Clone)]
+pub struct SpamFilter {
+    blocklist: Blocklist,
+    /// Derived from the JWT secret; the secret itself never keys anything else
+    ip_key: Vec<u8>,
+    trust_proxy: bool,
+    soft_limit: RateLimiter,
+    hard_limit: RateLimiter,
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+impl SpamFilter {
+    pub fn new(config: &Config) -> Self {
+        SpamFilter {
+            blocklist: config.spam_blocklist.clone(),
+            ip_key: hmac(config.jwt_secret.as_bytes(), b"spam-ip"),
+            trust_proxy: config.trust_proxy,
+            soft_limit: RateLimiter::new(SOFT_LIMIT, SOFT_WINDOW),
+            hard_limit: RateLimiter::new(HARD_LIMIT, HARD_WINDOW),
+        }
+    }
+
+    /// Keyed hash of the client address, so stored rows can be grouped by
+    /// sender without keeping the address. Same address rule as login throttling.
+    pub fn ip_hash(&self, headers: &HeaderMap, addr: SocketAddr) -> String {
+        let ip = sessions::client_ip(headers, addr, self.trust_proxy);
+        let digest = hmac(&self.ip_key, ip.to_string().as_bytes());
+        digest.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// False once the address is over the hard limit; the caller should refuse the request.
+    pub fn allow(&self, ip_hash: &str) -> bool {
+        self.hard_limit.check(ip_hash)
+    }
+
+    pub async fn assess(
+        &self,
+        pool: &PgPool,
+        source: Source,
+        ip_hash: String,
+        submission: &Submission<'_>,
+    ) -> Result<Assessment, sqlx::Error> {
+        let content_hash = fingerprint(&submission.fields);
+        let duplicate: bool = sqlx::query_scalar(&format!(
+            "SELECT EXISTS(SELECT 1 FROM {} WHERE content_hash = $1
+             AND created_at > NOW() - make_interval(hours => $2))",
+            source.table()
+        ))
+        .bind(&content_hash)
+        .bind(DUPLICATE_WINDOW_HOURS)
+        .fetch_one(pool)
+        .await?;
+        let rate_limited = !self.soft_limit.check(&ip_hash);
+
+        let verdict = score(submission, &self.blocklist, duplicate, rate_limited);
+        if verdict.is_suspicious() {
+            tracing::info!(
+                "SPAM: Holding {} from {} (score {}): {}",
+                source.table(),
+                &ip_hash[..12],
+                verdict.score,
+                verdict.reasons.join(", ")
+            );
+        }
+        Ok(Assessment {
+            verdict,
+            ip_hash,
+            content_hash,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submission(text: &str) -> Submission<'_> {
+        Submission {
+            fields: vec!["Maija", text],
+            fill_time: Some(Duration::from_secs(40)),
+            ..Default::default()
+        }
+    }
+
+    fn filter(trust_proxy: bool) -> SpamFilter {
+        SpamFilter {
+            blocklist: Blocklist::default(),
+            ip_key: hmac(b"0123456789abcdef0123456789abcdef", b"spam-ip"),
+            trust_proxy,
+            soft_limit: RateLimiter::new(SOFT_LIMIT, SOFT_WINDOW),
+            hard_limit: RateLimiter::new(HARD_LIMIT, HARD_WINDOW),
+        }
+    }
+
+    #[test]
+    fn ip_hash_ignores_forwarded_for_unless_trusted() {
+        let peer: SocketAddr = "198.51.100.1:40000".parse().unwrap();
+        let mut spoofed = HeaderMap::new();
+        spoofed.insert("x-forwarded-for", "203.0.113.9".parse().unwrap());
+
+        let direct = filter(false);
+        assert_eq!(
+            direct.ip_hash(&spoofed, peer),
+            direct.ip_hash(&HeaderMap::new(), peer)
+        );
+
+        let proxied = filter(true);
+        assert_ne!(
+            proxied.ip_hash(&spoofed, peer),
+            proxied.ip_hash(&HeaderMap::new(), peer)
+        );
+        assert_eq!(direct.ip_hash(&spoofed, peer).len(), 64);
+    }
+
+    #[test]
+    fn ordinary_feedback_passes() {
+        let verdict = score(
+            &submission("Great cover letter! Good luck with the interview at Acme."),
+            &Blocklist::default(),
+            false,
+            false,
+        );
+        assert_eq!(verdict, Verdict::default());
+    }
+
+    #[test]
+    fn counts_links_beyond_the_free_ones() {
+        let text = "see https://a.example/x and www.b.example, also <a href=\"http://c.example\">";
+        let verdict = score(&submission(text), &Blocklist::default(), false, false);
+        assert_eq!(verdict.score, 3 * LINK_POINTS);
+        assert!(verdict.is_suspicious());
+
+        let contact = Submission {
+            free_links: 1,
+            ..submission("My posting: https://jobs.acme.fi/42.")
+        };
+        assert!(score(&contact, &Blocklist::default(), false, false)
+            .reasons
+            .is_empty());
+    }
+
+    #[test]
+    fn blocklist_matches_whole_words_and_subdomains() {
+        let blocklist = Blocklist::new(&["Seo Services", "cash"], &["spam.example"]);
+
+        let verdict = score(
+            &submission("Cheap SEO-services at promo.spam.example"),
+            &blocklist,
+            false,
+            false,
+        );
+        assert_eq!(verdict.score, BLOCKED_WORD_POINTS + BLOCKED_DOMAIN_POINTS);
+
+        // "cashier" is not "cash", and notspam.example is another domain
+        let verdict = score(
+            &submission("Cashier role via notspam.example"),
+            &blocklist,
+            false,
+            false,
+        );
+        assert_eq!(verdict.score, 0);
+    }
+
+    #[test]
+    fn timing_duplicates_and_honeypot_add_up() {
+        let rushed = Submission {
+            fill_time: Some(Duration::from_millis(800)),
+            ..submission("hello")
+        };
+        assert_eq!(
+            score(&rushed, &Blocklist::default(), false, false).score,
+            TOO_FAST_POINTS
+        );
+
+        let scripted = Submission {
+            fill_time: None,
+            ..submission("hello")
+        };
+        let verdict = score(&scripted, &Blocklist::default(), true, false);
+        assert_eq!(verdict.score, NO_TIMING_POINTS + DUPLICATE_POINTS);
+        assert!(verdict.is_suspicious());
+
+        let bot = Submission {
+            honeypot: true,
+            ..submission("hello")
+        };
+        assert!(score(&bot, &Blocklist::default(), false, false).is_suspicious());
+    }
+
+    #[test]
+    fn fingerprint_ignores_case_and_punctuation() {
+        assert_eq!(
+            fingerprint(&["Buy   NOW!!", "cheap"]),
+            fingerprint(&["buy now", "Cheap."])
+        );
+        assert_ne!(fingerprint(&["buy now"]), fingerprint(&["buy later"]));
+    }
+}
@@ -0,0 +1,24 @@
+use crate::services::application_service::BASE_URL;
+use dioxus::prelude::*;
+
+/// First-page thumbnail linking to the uploaded file; falls back to the path
+/// when the backend could not render a preview (e.g. DOCX).
+#[component]
+pub fn DocumentPreview(path: String, thumbnail_url: Option<String>) -> Element {
+    rsx! {
+        a {
+            href: "{BASE_URL}{path}",
+            target: "_blank",
+            class: "mt-2 block group",
+            if let Some(thumb) = thumbnail_url {
+                img {
+                    src: "{BASE_URL}{thumb}",
+                    class: "w-24 max-h-32 object-cover object-top rounded border bg-white/5 group-hover:opacity-80 transition-opacity",
+                    style: "border-color: var(--glass-border);",
+                }
+            } else {
+                div { class: "text-[8px] font-mono truncate opacity-40 uppercase", "{path}" }
+            }
+        }
+    }
+}
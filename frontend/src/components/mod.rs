@@ -1,5 +1,6 @@
 pub mod charts;
 pub mod data_counter;
+pub mod document_preview;
 pub mod kanban_board;
 pub mod sector_map;
 pub mod terminal_text;
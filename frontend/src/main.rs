@@ -6,15 +6,25 @@ mod models;
 mod pages;
 mod services;
 
+use pages::admin::account::Account;
+use pages::admin::api_tokens::ApiTokens;
 use pages::admin::application_create::ApplicationCreate;
 use pages::admin::application_edit::ApplicationEdit;
 use pages::admin::applications_list::ApplicationsList;
 use pages::admin::dashboard::AdminDashboard;
+use pages::admin::emails::Emails;
+use pages::admin::inbox::Inbox;
+use pages::admin::invites::Invites;
 use pages::admin::layout::AdminLayout;
+use pages::admin::moderation::Moderation;
+use pages::admin::security::Security;
+use pages::admin::sessions::Sessions;
 use pages::admin_login::AdminLogin;
 use pages::admin_register::AdminRegister;
 use pages::application_detail::ApplicationDetail;
 use pages::contact::Contact;
+use pages::forgot_password::ForgotPassword;
+use pages::reset_password::ResetPassword;
 use pages::verify_email::VerifyEmail;
 
 use components::data_counter::DataCounter;
@@ -40,6 +50,10 @@ pub enum Route {
     AdminRegister {},
     #[route("/admin/verify")]
     VerifyEmail {},
+    #[route("/admin/forgot")]
+    ForgotPassword {},
+    #[route("/admin/reset")]
+    ResetPassword {},
 
     #[route("/:..segments")]
     PageNotFound { segments: Vec<String> },
@@ -53,6 +67,22 @@ pub enum Route {
         ApplicationCreate {},
         #[route("/admin/applications/:id/edit")]
         ApplicationEdit { id: String },
+        #[route("/admin/inbox")]
+        Inbox {},
+        #[route("/admin/moderation")]
+        Moderation {},
+        #[route("/admin/sessions")]
+        Sessions {},
+        #[route("/admin/security")]
+        Security {},
+        #[route("/admin/invites")]
+        Invites {},
+        #[route("/admin/tokens")]
+        ApiTokens {},
+        #[route("/admin/emails")]
+        Emails {},
+        #[route("/admin/account")]
+        Account {},
 }
 
 #[component]
@@ -403,7 +433,7 @@ fn Home() -> Element {
                                                             div { class: "flex justify-between items-start mb-8",
                                                                 div { class: "flex items-start gap-4",
                                                                     if let Some(logo) = &app.logo_url {
-                                                                        img { src: "{logo}", class: "w-12 h-12 rounded bg-white/5 object-contain border border-white/10 p-1" }
+                                                                        img { src: crate::services::application_service::asset_url(logo), class: "w-12 h-12 rounded bg-white/5 object-contain border border-white/10 p-1" }
                                                                     } else {
                                                                         div { class: "w-12 h-12 rounded bg-white/5 border border-white/10 flex items-center justify-center text-xl font-bold opacity-30", "?" }
                                                                     }
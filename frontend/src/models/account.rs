@@ -0,0 +1,18 @@
+use chrono::NaiveDateTime;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AccountInfo {
+    pub email: String,
+    pub pending_email: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub totp_enabled: bool,
+    pub deletion_scheduled_for: Option<NaiveDateTime>,
+}
+
+/// What the backend returns alongside fresh session cookies
+#[derive(Debug, Clone, Deserialize)]
+pub struct CookieSession {
+    pub csrf_token: String,
+    pub expires_in: i64,
+}
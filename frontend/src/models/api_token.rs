@@ -0,0 +1,34 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// (scope id, label) pairs offered when creating a token
+pub const SCOPES: [(&str, &str); 3] = [
+    ("read", "Read"),
+    ("write_applications", "Write applications"),
+    ("upload", "Upload files"),
+];
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ApiToken {
+    pub id: Uuid,
+    pub name: String,
+    pub token_hint: String,
+    pub scopes: Vec<String>,
+    pub created_at: NaiveDateTime,
+    pub last_used_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateApiTokenPayload {
+    pub name: String,
+    pub scopes: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CreatedApiToken {
+    pub id: Uuid,
+    pub name: String,
+    pub token: String,
+    pub scopes: Vec<String>,
+}
@@ -19,8 +19,19 @@ pub struct Application {
     pub cover_letter_path: Option<String>,
     pub logo_url: Option<String>,
     pub description: Option<String>,
+    /// "contact_form" when created from a contact message
+    #[serde(default)]
+    pub source: Option<String>,
+    #[serde(default)]
+    pub contact_message_id: Option<Uuid>,
+    #[serde(default = "enabled")]
+    pub comments_enabled: bool,
     #[serde(default)]
     pub comment_count: Option<i64>,
+    #[serde(default)]
+    pub cv_thumbnail_url: Option<String>,
+    #[serde(default)]
+    pub cover_letter_thumbnail_url: Option<String>,
     pub created_at: chrono::NaiveDateTime,
     pub updated_at: chrono::NaiveDateTime,
 }
@@ -55,6 +66,7 @@ pub struct UpdateApplicationPayload {
     pub cover_letter_path: Option<String>,
     pub logo_url: Option<String>,
     pub description: Option<String>,
+    pub comments_enabled: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -78,17 +90,43 @@ pub struct PublicApplicationDetail {
     pub salary: Option<String>,
     pub cover_letter: Option<String>,
     pub cv_path: Option<String>,
+    #[serde(default)]
+    pub cv_thumbnail_url: Option<String>,
     pub logo_url: Option<String>,
     pub description: Option<String>,
+    #[serde(default)]
+    pub source: Option<String>,
+    #[serde(default = "enabled")]
+    pub comments_enabled: bool,
     pub created_at: chrono::NaiveDateTime,
 }
 
+fn enabled() -> bool {
+    true
+}
+
+/// (status id, label) pairs for the moderation filter
+pub const COMMENT_STATUSES: [(&str, &str); 3] = [
+    ("pending", "Awaiting Review"),
+    ("approved", "Published"),
+    ("rejected", "Hidden"),
+];
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Comment {
     pub id: Uuid,
     pub application_id: Uuid,
     pub visitor_name: String,
     pub content: String,
+    /// "pending" until approved when the owner reviews comments
+    #[serde(default)]
+    pub status: String,
+    /// The top-level comment this replies to
+    #[serde(default)]
+    pub parent_id: Option<Uuid>,
+    /// Written by the signed-in owner
+    #[serde(default)]
+    pub is_owner: bool,
     pub created_at: chrono::NaiveDateTime,
 }
 
@@ -96,6 +134,14 @@ pub struct Comment {
 pub struct CreateComment {
     pub visitor_name: String,
     pub content: String,
+    pub parent_id: Option<Uuid>,
+    /// Milliseconds between the page loading and the comment being sent
+    pub fill_time_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct OwnerReply {
+    pub content: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -104,11 +150,26 @@ pub struct CommentWithContext {
     pub application_id: Uuid,
     pub visitor_name: String,
     pub content: String,
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub parent_id: Option<Uuid>,
+    #[serde(default)]
+    pub is_owner: bool,
+    /// Why the spam filter held the comment, if it did
+    #[serde(default)]
+    pub spam_reasons: Vec<String>,
     pub created_at: chrono::NaiveDateTime,
     pub company: String,
     pub role: String,
 }
 
+/// "auto" publishes new comments at once, "review" holds them for approval
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ModerationSettings {
+    pub mode: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct DashboardStats {
     pub daily_activity: Vec<DailyCount>,
@@ -132,4 +193,64 @@ pub struct CompanyIntel {
     pub company_name: Option<String>,
     pub description: Option<String>,
     pub logo_url: Option<String>,
+    #[serde(default)]
+    pub job: Option<JobPosting>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct JobPosting {
+    pub title: Option<String>,
+    pub employment_type: Option<String>,
+    pub location: Option<String>,
+    pub remote: bool,
+    pub salary: Option<SalaryRange>,
+    pub date_posted: Option<String>,
+    pub valid_through: Option<String>,
+    pub hiring_organization: Option<String>,
+    pub hiring_organization_logo: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SalaryRange {
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub currency: Option<String>,
+    pub unit: Option<String>,
+}
+
+impl SalaryRange {
+    /// e.g. "4000–5200 EUR / MONTH"
+    pub fn display(&self) -> String {
+        let amount = match (self.min, self.max) {
+            (Some(min), Some(max)) if min != max => format!("{}–{}", min, max),
+            (Some(v), _) | (None, Some(v)) => v.to_string(),
+            (None, None) => String::new(),
+        };
+        let mut out = amount;
+        if let Some(currency) = &self.currency {
+            out = format!("{} {}", out, currency);
+        }
+        if let Some(unit) = &self.unit {
+            out = format!("{} / {}", out, unit);
+        }
+        out
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UploadedFile {
+    pub url: String,
+    pub thumbnail_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DocumentSearchHit {
+    pub file_path: String,
+    pub original_name: String,
+    pub thumbnail_path: Option<String>,
+    pub snippet: String,
+    pub application_id: Option<Uuid>,
+    pub company: Option<String>,
+    pub role: Option<String>,
 }
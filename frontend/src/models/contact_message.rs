@@ -0,0 +1,32 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ContactMessage {
+    pub id: Uuid,
+    pub name: String,
+    pub email: String,
+    pub message: String,
+    pub link: Option<String>,
+    pub locale: String,
+    pub status: String,
+    pub replied_at: Option<NaiveDateTime>,
+    /// Why the spam filter held the message, if it did
+    #[serde(default)]
+    pub spam_reasons: Vec<String>,
+    pub created_at: NaiveDateTime,
+    /// Lead created from this message, if any
+    #[serde(default)]
+    pub application_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateContactMessage {
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ContactReply {
+    pub message: String,
+}
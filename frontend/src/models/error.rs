@@ -0,0 +1,52 @@
+use serde::Deserialize;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+/// The backend's `application/problem+json` error body.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ApiError {
+    pub status: u16,
+    /// Stable machine-readable code, e.g. `email_not_verified`
+    pub code: String,
+    pub title: String,
+    pub detail: String,
+    #[serde(default)]
+    pub errors: Vec<FieldError>,
+}
+
+impl ApiError {
+    /// Reads the problem body of a failed response. Anything else (a proxy's
+    /// HTML error page, an empty body) becomes `fallback` plus the status.
+    pub async fn from_response(res: reqwest::Response, fallback: &str) -> ApiError {
+        let status = res.status();
+        match res.json::<ApiError>().await {
+            Ok(err) => err,
+            Err(_) => ApiError {
+                status: status.as_u16(),
+                code: "unknown".to_string(),
+                title: status.canonical_reason().unwrap_or("Error").to_string(),
+                detail: format!("{}: {}", fallback, status),
+                errors: Vec::new(),
+            },
+        }
+    }
+
+    /// The message for one form field, if the server rejected it.
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.errors
+            .iter()
+            .find(|e| e.field == name)
+            .map(|e| e.message.as_str())
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.detail)
+    }
+}
@@ -16,4 +16,16 @@ pub enum AppEvent {
         company: String,
         status: String,
     },
+    CommentReplied {
+        id: Uuid,
+        application_id: Uuid,
+        parent_id: Uuid,
+    },
+    CommentAwaitingReview {
+        id: Uuid,
+        application_id: Uuid,
+    },
+    ContactMessageReceived {
+        id: Uuid,
+    },
 }
@@ -0,0 +1,27 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Invite {
+    pub id: Uuid,
+    pub code_hint: String,
+    pub note: Option<String>,
+    pub expires_at: NaiveDateTime,
+    pub used_at: Option<NaiveDateTime>,
+    pub used_by_email: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateInvitePayload {
+    pub note: Option<String>,
+    pub expires_in_days: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CreatedInvite {
+    pub id: Uuid,
+    pub code: String,
+    pub expires_at: NaiveDateTime,
+}
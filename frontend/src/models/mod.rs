@@ -1,3 +1,11 @@
+pub mod account;
+pub mod api_token;
 pub mod application;
+pub mod contact_message;
+pub mod error;
 pub mod event;
+pub mod invite;
+pub mod outbound_email;
+pub mod session;
+pub mod two_factor;
 pub mod visitor;
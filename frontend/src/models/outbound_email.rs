@@ -0,0 +1,25 @@
+use chrono::NaiveDateTime;
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// (status id, label) pairs for the filter on the emails page
+pub const STATUSES: [(&str, &str); 4] = [
+    ("pending", "Pending"),
+    ("sending", "Sending"),
+    ("sent", "Sent"),
+    ("dead", "Failed"),
+];
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OutboundEmail {
+    pub id: Uuid,
+    pub template: String,
+    pub recipient: String,
+    pub subject: String,
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+    pub sent_at: Option<NaiveDateTime>,
+}
@@ -0,0 +1,52 @@
+use chrono::NaiveDateTime;
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SessionInfo {
+    pub id: Uuid,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub last_used_at: NaiveDateTime,
+    pub current: bool,
+}
+
+impl SessionInfo {
+    /// Rough "Firefox on Linux" style label from the user agent.
+    pub fn device_label(&self) -> String {
+        let Some(ua) = self.user_agent.as_deref() else {
+            return "Unknown device".to_string();
+        };
+
+        let browser = [
+            ("Edg/", "Edge"),
+            ("OPR/", "Opera"),
+            ("Firefox/", "Firefox"),
+            ("Chrome/", "Chrome"),
+            ("Safari/", "Safari"),
+        ]
+        .iter()
+        .find(|(needle, _)| ua.contains(needle))
+        .map(|(_, name)| *name);
+
+        let os = [
+            ("Android", "Android"),
+            ("iPhone", "iOS"),
+            ("iPad", "iPadOS"),
+            ("Windows", "Windows"),
+            ("Mac OS X", "macOS"),
+            ("Linux", "Linux"),
+        ]
+        .iter()
+        .find(|(needle, _)| ua.contains(needle))
+        .map(|(_, name)| *name);
+
+        match (browser, os) {
+            (Some(b), Some(o)) => format!("{} on {}", b, o),
+            (Some(b), None) => b.to_string(),
+            (None, Some(o)) => o.to_string(),
+            (None, None) => ua.chars().take(60).collect(),
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use crate::services::auth_service;
+use dioxus::prelude::*;
+
+const DATE_FMT: &str = "%Y-%m-%d %H:%M";
+
+#[component]
+pub fn Account() -> Element {
+    let mut account = use_resource(move || async move { auth_service::get_account().await });
+    let mut password = use_signal(|| "".to_string());
+    let mut confirming = use_signal(|| false);
+    let mut error_msg = use_signal(|| "".to_string());
+    let mut info_msg = use_signal(|| "".to_string());
+    let mut new_email = use_signal(|| "".to_string());
+    let mut email_password = use_signal(|| "".to_string());
+    let mut current_password = use_signal(|| "".to_string());
+    let mut new_password = use_signal(|| "".to_string());
+    let mut confirm_password = use_signal(|| "".to_string());
+
+    let on_change_email = move |evt: FormEvent| async move {
+        evt.prevent_default();
+        error_msg.set("".to_string());
+        info_msg.set("".to_string());
+        match auth_service::change_email(&new_email(), &email_password()).await {
+            Ok(_) => {
+                info_msg.set(format!("Check {} for a confirmation link.", new_email()));
+                new_email.set("".to_string());
+                email_password.set("".to_string());
+                account.restart();
+            }
+            Err(e) => error_msg.set(e),
+        }
+    };
+
+    let on_change_password = move |evt: FormEvent| async move {
+        evt.prevent_default();
+        error_msg.set("".to_string());
+        info_msg.set("".to_string());
+        if new_password() != confirm_password() {
+            error_msg.set("Passwords do not match".to_string());
+            return;
+        }
+        match auth_service::change_password(&current_password(), &new_password()).await {
+            Ok(_) => {
+                info_msg.set("Password changed. Other devices have been signed out.".to_string());
+                current_password.set("".to_string());
+                new_password.set("".to_string());
+                confirm_password.set("".to_string());
+            }
+            Err(e) => error_msg.set(e),
+        }
+    };
+
+    let on_export = move |_| async move {
+        error_msg.set("".to_string());
+        if let Err(e) = auth_service::download_account_export().await {
+            error_msg.set(e);
+        }
+    };
+
+    let on_delete = move |evt: FormEvent| async move {
+        evt.prevent_default();
+        error_msg.set("".to_string());
+        match auth_service::request_account_deletion(&password()).await {
+            Ok(_) => {
+                password.set("".to_string());
+                confirming.set(false);
+                account.restart();
+            }
+            Err(e) => error_msg.set(e),
+        }
+    };
+
+    let on_cancel = move |_| async move {
+        error_msg.set("".to_string());
+        match auth_service::cancel_account_deletion().await {
+            Ok(_) => account.restart(),
+            Err(e) => error_msg.set(e),
+        }
+    };
+
+    rsx! {
+        div { class: "max-w-4xl mx-auto space-y-8",
+            div { class: "border-b pb-8",
+                style: "border-color: var(--glass-border);",
+                h2 { class: "text-4xl font-black tracking-tighter uppercase",
+                    style: "color: var(--text-color); text-shadow: 0 0 10px var(--accent-glow);",
+                    "Account"
+                }
+                p { class: "text-[10px] font-mono mt-2 uppercase tracking-[0.2em] opacity-40",
+                    style: "color: var(--accent-color)",
+                    ":: Profile, credentials and your data ::"
+                }
+            }
+
+            if !error_msg().is_empty() {
+                p { class: "text-red-500 text-[10px] font-black uppercase tracking-widest", "{error_msg}" }
+            }
+            if !info_msg().is_empty() {
+                p { class: "text-green-400 text-[10px] font-black uppercase tracking-widest", "{info_msg}" }
+            }
+
+            match &*account.read() {
+                Some(Ok(info)) => rsx! {
+                    div { class: "noir-card p-6 space-y-1",
+                        p { class: "font-bold", "{info.email}" }
+                        p { class: "text-[10px] font-mono opacity-50 tracking-wider",
+                            "MEMBER SINCE {info.created_at.format(DATE_FMT)}"
+                        }
+                        if let Some(pending) = &info.pending_email {
+                            p { class: "text-[10px] font-mono tracking-wider", style: "color: var(--accent-color)",
+                                "PENDING CHANGE TO {pending} · CONFIRM VIA THE EMAILED LINK"
+                            }
+                        }
+                    }
+
+                    form { class: "noir-card p-6 space-y-3", onsubmit: on_change_email,
+                        h3 { class: "text-sm font-black uppercase tracking-widest", "Change email" }
+                        input {
+                            r#type: "email",
+                            required: true,
+                            autocomplete: "email",
+                            placeholder: "New email address",
+                            class: "w-full bg-[var(--hover-bg)] border border-[var(--glass-border)] rounded px-4 py-3 text-xs font-mono focus:border-[var(--accent-color)] outline-none transition-all tracking-wider text-white",
+                            value: "{new_email}",
+                            oninput: move |e| new_email.set(e.value())
+                        }
+                        input {
+                            r#type: "password",
+                            required: true,
+                            autocomplete: "current-password",
+                            placeholder: "Current password",
+                            class: "w-full bg-[var(--hover-bg)] border border-[var(--glass-border)] rounded px-4 py-3 text-xs font-mono focus:border-[var(--accent-color)] outline-none transition-all tracking-wider text-white",
+                            value: "{email_password}",
+                            oninput: move |e| email_password.set(e.value())
+                        }
+                        button { r#type: "submit", class: "noir-btn px-6 py-3 text-[10px]", "Send Confirmation" }
+                    }
+
+                    form { class: "noir-card p-6 space-y-3", onsubmit: on_change_password,
+                        h3 { class: "text-sm font-black uppercase tracking-widest", "Change password" }
+                        input {
+                            r#type: "password",
+                            required: true,
+                            autocomplete: "current-password",
+                            placeholder: "Current password",
+                            class: "w-full bg-[var(--hover-bg)] border border-[var(--glass-border)] rounded px-4 py-3 text-xs font-mono focus:border-[var(--accent-color)] outline-none transition-all tracking-wider text-white",
+                            value: "{current_password}",
+                            oninput: move |e| current_password.set(e.value())
+                        }
+                        input {
+                            r#type: "password",
+                            required: true,
+                            autocomplete: "new-password",
+                            placeholder: "New password (min. 8 characters)",
+                            class: "w-full bg-[var(--hover-bg)] border border-[var(--glass-border)] rounded px-4 py-3 text-xs font-mono focus:border-[var(--accent-color)] outline-none transition-all tracking-wider text-white",
+                            value: "{new_password}",
+                            oninput: move |e| new_password.set(e.value())
+                        }
+                        input {
+                            r#type: "password",
+                            required: true,
+                            autocomplete: "new-password",
+                            placeholder: "Repeat new password",
+                            class: "w-full bg-[var(--hover-bg)] border border-[var(--glass-border)] rounded px-4 py-3 text-xs font-mono focus:border-[var(--accent-color)] outline-none transition-all tracking-wider text-white",
+                            value: "{confirm_password}",
+                            oninput: move |e| confirm_password.set(e.value())
+                        }
+                        button { r#type: "submit", class: "noir-btn px-6 py-3 text-[10px]", "Change Password" }
+                    }
+
+                    div { class: "noir-card p-6 space-y-4",
+                        h3 { class: "text-sm font-black uppercase tracking-widest", "Export your data" }
+                        p { class: "text-xs opacity-60",
+                            "Download everything stored about you as JSON: applications, comments, documents, sessions, tokens and visitor statistics."
+                        }
+                        button { class: "noir-btn px-6 py-3 text-[10px]", onclick: on_export, "Download Export" }
+                    }
+
+                    div { class: "noir-card p-6 space-y-4",
+                        style: "border-color: var(--status-rejected);",
+                        h3 { class: "text-sm font-black uppercase tracking-widest", style: "color: var(--status-rejected);",
+                            "Delete account"
+                        }
+                        if let Some(at) = info.deletion_scheduled_for {
+                            p { class: "text-xs",
+                                "Your account and all its data will be permanently deleted on {at.format(DATE_FMT)} UTC."
+                            }
+                            button { class: "noir-btn px-6 py-3 text-[10px]", onclick: on_cancel, "Cancel Deletion" }
+                        } else if confirming() {
+                            form { class: "space-y-3", onsubmit: on_delete,
+                                p { class: "text-xs opacity-60",
+                                    "Confirm with your password. You can still cancel during the grace period; after that your applications, comments, uploaded files and sessions are removed for good."
+                                }
+                                input {
+                                    r#type: "password",
+                                    required: true,
+                                    autocomplete: "current-password",
+                                    placeholder: "Password",
+                                    class: "w-full bg-[var(--hover-bg)] border border-[var(--glass-border)] rounded px-4 py-3 text-xs font-mono focus:border-[var(--accent-color)] outline-none transition-all tracking-wider text-white",
+                                    value: "{password}",
+                                    oninput: move |e| password.set(e.value())
+                                }
+                                div { class: "flex gap-3",
+                                    button {
+                                        r#type: "submit",
+                                        class: "px-6 py-3 rounded border text-[10px] font-black uppercase tracking-widest hover:bg-red-500/10 transition-all",
+                                        style: "color: var(--status-rejected); border-color: var(--status-rejected);",
+                                        "Delete My Account"
+                                    }
+                                    button {
+                                        r#type: "button",
+                                        class: "px-6 py-3 text-[10px] font-black uppercase tracking-widest opacity-60 hover:opacity-100",
+                                        onclick: move |_| {
+                                            confirming.set(false);
+                                            password.set("".to_string());
+                                        },
+                                        "Keep It"
+                                    }
+                                }
+                            }
+                        } else {
+                            p { class: "text-xs opacity-60",
+                                "Schedules your account for deletion. Export your data first if you want to keep it."
+                            }
+                            button {
+                                class: "px-6 py-3 rounded border text-[10px] font-black uppercase tracking-widest hover:bg-red-500/10 transition-all",
+                                style: "color: var(--status-rejected); border-color: var(--status-rejected);",
+                                onclick: move |_| confirming.set(true),
+                                "Delete Account…"
+                            }
+                        }
+                    }
+                },
+                Some(Err(e)) => rsx! {
+                    p { class: "text-red-500 text-[10px] font-black uppercase tracking-widest", "{e}" }
+                },
+                None => rsx! {
+                    div { class: "text-center py-8 font-mono text-[10px] tracking-widest uppercase opacity-30 animate-pulse",
+                        "// SCANNING..."
+                    }
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use crate::models::api_token::{CreateApiTokenPayload, CreatedApiToken, SCOPES};
+use crate::services::auth_service;
+use dioxus::prelude::*;
+
+const DATE_FMT: &str = "%Y-%m-%d %H:%M";
+
+#[component]
+pub fn ApiTokens() -> Element {
+    let mut tokens = use_resource(move || async move { auth_service::list_api_tokens().await });
+    let mut name = use_signal(|| "".to_string());
+    let mut scopes = use_signal(|| vec!["read".to_string()]);
+    let mut created = use_signal(|| None::<CreatedApiToken>);
+    let mut error_msg = use_signal(|| "".to_string());
+
+    let on_create = move |evt: FormEvent| async move {
+        evt.prevent_default();
+        error_msg.set("".to_string());
+        let payload = CreateApiTokenPayload {
+            name: name(),
+            scopes: scopes(),
+        };
+        match auth_service::create_api_token(payload).await {
+            Ok(token) => {
+                created.set(Some(token));
+                name.set("".to_string());
+                tokens.restart();
+            }
+            Err(e) => error_msg.set(e),
+        }
+    };
+
+    rsx! {
+        div { class: "max-w-4xl mx-auto space-y-8",
+            div { class: "border-b pb-8",
+                style: "border-color: var(--glass-border);",
+                h2 { class: "text-4xl font-black tracking-tighter uppercase",
+                    style: "color: var(--text-color); text-shadow: 0 0 10px var(--accent-glow);",
+                    "API Tokens"
+                }
+                p { class: "text-[10px] font-mono mt-2 uppercase tracking-[0.2em] opacity-40",
+                    style: "color: var(--accent-color)",
+                    ":: Personal access tokens for scripts and extensions ::"
+                }
+            }
+
+            form { class: "noir-card p-6 space-y-4", onsubmit: on_create,
+                div { class: "space-y-2",
+                    label { class: "text-[10px] font-black uppercase tracking-widest opacity-60", "Name" }
+                    input {
+                        r#type: "text",
+                        required: true,
+                        placeholder: "Browser extension",
+                        class: "w-full bg-[var(--hover-bg)] border border-[var(--glass-border)] rounded px-4 py-3 text-xs font-mono focus:border-[var(--accent-color)] outline-none transition-all tracking-wider text-white",
+                        value: "{name}",
+                        oninput: move |e| name.set(e.value())
+                    }
+                }
+                div { class: "flex flex-wrap gap-6",
+                    for (id, label) in SCOPES {
+                        label { key: "{id}", class: "flex items-center gap-2 text-xs cursor-pointer",
+                            input {
+                                r#type: "checkbox",
+                                checked: scopes().iter().any(|s| s == id),
+                                onchange: move |e| {
+                                    let mut list = scopes();
+                                    list.retain(|s| s != id);
+                                    if e.checked() {
+                                        list.push(id.to_string());
+                                    }
+                                    scopes.set(list);
+                                }
+                            }
+                            "{label}"
+                        }
+                    }
+                }
+                button { r#type: "submit", class: "noir-btn px-6 py-3 text-[10px]", "Create Token" }
+            }
+
+            if !error_msg().is_empty() {
+                p { class: "text-red-500 text-[10px] font-black uppercase tracking-widest", "{error_msg}" }
+            }
+
+            if let Some(token) = created() {
+                div { class: "noir-card p-6 space-y-3",
+                    p { class: "text-xs opacity-60", "Copy the token for \"{token.name}\" now. It is not shown again." }
+                    p { class: "font-mono text-sm break-all select-all", style: "color: var(--accent-color)", "{token.token}" }
+                    p { class: "text-[10px] font-mono opacity-50", "Send it as: Authorization: Bearer <token>" }
+                }
+            }
+
+            match &*tokens.read() {
+                Some(Ok(list)) if list.is_empty() => rsx! {
+                    div { class: "text-center py-8 font-mono text-[10px] tracking-widest uppercase opacity-30",
+                        "// NO TOKENS ISSUED"
+                    }
+                },
+                Some(Ok(list)) => rsx! {
+                    div { class: "space-y-3",
+                        for token in list.iter().cloned() {
+                            div {
+                                key: "{token.id}",
+                                class: "noir-card p-5 flex items-center justify-between gap-6",
+                                div { class: "space-y-1 min-w-0",
+                                    div { class: "flex items-center gap-3",
+                                        span { class: "font-bold truncate", "{token.name}" }
+                                        span { class: "font-mono text-[10px] opacity-50", "{token.token_hint}…" }
+                                    }
+                                    p { class: "text-[10px] font-mono opacity-50 tracking-wider uppercase",
+                                        "{token.scopes.join(\" · \")}"
+                                    }
+                                    p { class: "text-[10px] font-mono opacity-50 tracking-wider",
+                                        {
+                                            match token.last_used_at {
+                                                Some(at) => format!("LAST USED {}", at.format(DATE_FMT)),
+                                                None => format!("CREATED {} · NEVER USED", token.created_at.format(DATE_FMT)),
+                                            }
+                                        }
+                                    }
+                                }
+                                button {
+                                    class: "px-4 py-2 rounded border text-[10px] font-black uppercase tracking-widest hover:bg-red-500/10 transition-all",
+                                    style: "color: var(--status-rejected); border-color: var(--status-rejected);",
+                                    onclick: move |_| {
+                                        let id = token.id.to_string();
+                                        async move {
+                                            match auth_service::revoke_api_token(&id).await {
+                                                Ok(_) => tokens.restart(),
+                                                Err(e) => error_msg.set(e),
+                                            }
+                                        }
+                                    },
+                                    "Revoke"
+                                }
+                            }
+                        }
+                    }
+                },
+                Some(Err(e)) => rsx! {
+                    p { class: "text-red-500 text-[10px] font-black uppercase tracking-widest", "{e}" }
+                },
+                None => rsx! {
+                    div { class: "text-center py-8 font-mono text-[10px] tracking-widest uppercase opacity-30 animate-pulse",
+                        "// SCANNING..."
+                    }
+                },
+            }
+        }
+    }
+}
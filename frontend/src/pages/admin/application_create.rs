@@ -1,4 +1,6 @@
+use crate::components::document_preview::DocumentPreview;
 use crate::models::application::CreateApplicationPayload;
+use crate::services::application_service::asset_url;
 use dioxus::prelude::*;
 
 #[component]
@@ -14,6 +16,8 @@ pub fn ApplicationCreate() -> Element {
     let mut cv_path = use_signal(|| "".to_string());
     let mut cover_letter = use_signal(|| "".to_string());
     let mut cover_letter_path = use_signal(|| "".to_string());
+    let mut cv_thumbnail = use_signal(|| None::<String>);
+    let mut cover_letter_thumbnail = use_signal(|| None::<String>);
     let mut logo_url = use_signal(|| "".to_string());
     let mut description = use_signal(|| "".to_string());
     let mut error_msg = use_signal(|| "".to_string());
@@ -115,11 +119,13 @@ pub fn ApplicationCreate() -> Element {
                 )
                 .await
                 {
-                    Ok(url) => {
+                    Ok(uploaded) => {
                         if target == "cv" {
-                            cv_path.set(url);
+                            cv_path.set(uploaded.url);
+                            cv_thumbnail.set(uploaded.thumbnail_url);
                         } else if target == "cover_letter" {
-                            cover_letter_path.set(url);
+                            cover_letter_path.set(uploaded.url);
+                            cover_letter_thumbnail.set(uploaded.thumbnail_url);
                         }
                     }
                     Err(e) => error_msg.set(format!("Upload failed: {}", e)),
@@ -194,6 +200,18 @@ pub fn ApplicationCreate() -> Element {
                                                             if let Some(logo) = intel.logo_url {
                                                                 logo_url.set(logo);
                                                             }
+                                                            // Structured JobPosting data beats generic meta tags
+                                                            if let Some(job) = intel.job {
+                                                                if let Some(title) = job.title {
+                                                                    role.set(title);
+                                                                }
+                                                                if let Some(range) = job.salary {
+                                                                    salary.set(range.display());
+                                                                }
+                                                                if let Some(desc) = job.description {
+                                                                    description.set(desc);
+                                                                }
+                                                            }
                                                         }
                                                         Err(e) => error_msg.set(format!("Intel fetch failed: {}", e)),
                                                     }
@@ -241,7 +259,7 @@ pub fn ApplicationCreate() -> Element {
                             div { class: "flex gap-4 items-center",
                                 if !logo_url().is_empty() {
                                     img {
-                                        src: "{logo_url}",
+                                        src: asset_url(&logo_url()),
                                         class: "w-12 h-12 rounded border p-1 bg-white/5 object-contain",
                                         style: "border-color: var(--glass-border);",
                                     }
@@ -265,6 +283,7 @@ pub fn ApplicationCreate() -> Element {
                                 class: "w-full bg-[var(--hover-bg)] border border-[var(--glass-border)] rounded px-4 py-3 text-xs font-black uppercase tracking-widest focus:border-[var(--accent-color)] outline-none transition-all text-white",
                                 value: "{status}",
                                 onchange: move |e| status.set(e.value()),
+                                option { value: "Lead", "Lead" }
                                 option { value: "Applied", "Applied" }
                                 option { value: "Interviewing", "Interviewing" }
                                 option { value: "Offer", "Offer" }
@@ -335,7 +354,7 @@ pub fn ApplicationCreate() -> Element {
                                             onchange: move |e| upload_handler(e, "cv")
                                         }
                                         if !cv_path().is_empty() {
-                                            div { class: "mt-2 text-[8px] font-mono truncate opacity-40 uppercase", "{cv_path}" }
+                                            DocumentPreview { path: cv_path(), thumbnail_url: cv_thumbnail() }
                                         }
                                     }
                                 }
@@ -353,7 +372,7 @@ pub fn ApplicationCreate() -> Element {
                                         onchange: move |e| upload_handler(e, "cover_letter")
                                     }
                                     if !cover_letter_path().is_empty() {
-                                        div { class: "mt-2 text-[8px] font-mono truncate opacity-40 uppercase", "{cover_letter_path}" }
+                                        DocumentPreview { path: cover_letter_path(), thumbnail_url: cover_letter_thumbnail() }
                                     }
                                 }
 
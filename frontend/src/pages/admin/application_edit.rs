@@ -1,4 +1,6 @@
+use crate::components::document_preview::DocumentPreview;
 use crate::models::application::UpdateApplicationPayload;
+use crate::services::application_service::asset_url;
 use dioxus::prelude::*;
 
 #[component]
@@ -14,8 +16,12 @@ pub fn ApplicationEdit(id: String) -> Element {
     let mut cv_path = use_signal(|| "".to_string());
     let mut cover_letter = use_signal(|| "".to_string());
     let mut cover_letter_path = use_signal(|| "".to_string());
+    let mut cv_thumbnail = use_signal(|| None::<String>);
+    let mut cover_letter_thumbnail = use_signal(|| None::<String>);
     let mut logo_url = use_signal(|| "".to_string());
     let mut description = use_signal(|| "".to_string());
+    let mut source = use_signal(|| None::<String>);
+    let mut comments_enabled = use_signal(|| true);
     let mut error_msg = use_signal(|| "".to_string());
     let mut uploading = use_signal(|| false);
 
@@ -31,10 +37,14 @@ pub fn ApplicationEdit(id: String) -> Element {
                 contact_person.set(app.contact_person.unwrap_or_default());
                 cv_version.set(app.cv_version.unwrap_or_default());
                 cv_path.set(app.cv_path.unwrap_or_default());
+                cv_thumbnail.set(app.cv_thumbnail_url);
                 cover_letter.set(app.cover_letter.unwrap_or_default());
                 cover_letter_path.set(app.cover_letter_path.unwrap_or_default());
+                cover_letter_thumbnail.set(app.cover_letter_thumbnail_url);
                 logo_url.set(app.logo_url.unwrap_or_default());
                 description.set(app.description.unwrap_or_default());
+                source.set(app.source);
+                comments_enabled.set(app.comments_enabled);
             }
             Err(e) => error_msg.set(format!("Failed to load: {}", e)),
         }
@@ -56,6 +66,7 @@ pub fn ApplicationEdit(id: String) -> Element {
                 cover_letter_path: Some(cover_letter_path()).filter(|s| !s.is_empty()),
                 logo_url: Some(logo_url()).filter(|s| !s.is_empty()),
                 description: Some(description()).filter(|s| !s.is_empty()),
+                comments_enabled: Some(comments_enabled()),
             };
 
             match crate::services::application_service::update_application(&id, payload).await {
@@ -101,11 +112,13 @@ pub fn ApplicationEdit(id: String) -> Element {
                 )
                 .await
                 {
-                    Ok(url) => {
+                    Ok(uploaded) => {
                         if target == "cv" {
-                            cv_path.set(url);
+                            cv_path.set(uploaded.url);
+                            cv_thumbnail.set(uploaded.thumbnail_url);
                         } else if target == "cover_letter" {
-                            cover_letter_path.set(url);
+                            cover_letter_path.set(uploaded.url);
+                            cover_letter_thumbnail.set(uploaded.thumbnail_url);
                         }
                     }
                     Err(e) => error_msg.set(format!("Upload failed: {}", e)),
@@ -131,6 +144,20 @@ pub fn ApplicationEdit(id: String) -> Element {
                 }
             }
 
+            if source().as_deref() == Some("contact_form") {
+                div { class: "noir-card p-4 flex items-center justify-between gap-4",
+                    p { class: "text-[10px] font-mono uppercase tracking-widest opacity-70",
+                        "// Came in via contact form"
+                    }
+                    Link {
+                        to: "/admin/inbox",
+                        class: "text-[10px] font-black uppercase tracking-[0.2em] transition-all opacity-60 hover:opacity-100",
+                        style: "color: var(--accent-color)",
+                        "Open Inbox >>"
+                    }
+                }
+            }
+
             div { class: "noir-card p-8",
                 div { class: "space-y-8",
                     // Basic Info
@@ -196,7 +223,7 @@ pub fn ApplicationEdit(id: String) -> Element {
                         div { class: "flex gap-4 items-center",
                             if !logo_url().is_empty() {
                                 img {
-                                    src: "{logo_url}",
+                                    src: asset_url(&logo_url()),
                                     class: "w-12 h-12 rounded border p-1 bg-white/5 object-contain",
                                     style: "border-color: var(--glass-border);",
                                 }
@@ -220,6 +247,7 @@ pub fn ApplicationEdit(id: String) -> Element {
                             class: "w-full bg-[var(--hover-bg)] border border-[var(--glass-border)] rounded px-4 py-3 text-xs font-black uppercase tracking-widest focus:border-[var(--accent-color)] outline-none transition-all text-white",
                             value: "{status}",
                             onchange: move |e| status.set(e.value()),
+                            option { value: "Lead", "Lead" }
                             option { value: "Applied", "Applied" }
                             option { value: "Interviewing", "Interviewing" }
                             option { value: "Offer", "Offer" }
@@ -228,6 +256,19 @@ pub fn ApplicationEdit(id: String) -> Element {
                         }
                     }
 
+                    label { class: "flex items-center gap-3 cursor-pointer",
+                        input {
+                            r#type: "checkbox",
+                            class: "accent-[var(--accent-color)]",
+                            checked: comments_enabled(),
+                            onchange: move |e| comments_enabled.set(e.checked()),
+                        }
+                        span { class: "text-[10px] font-black uppercase tracking-[0.2em] opacity-60",
+                            style: "color: var(--text-color)",
+                            "Allow visitor comments"
+                        }
+                    }
+
                     div { class: "grid grid-cols-1 md:grid-cols-2 gap-8",
                         div {
                             label { class: "block text-[10px] font-black uppercase tracking-[0.2em] mb-2 opacity-60",
@@ -289,7 +330,7 @@ pub fn ApplicationEdit(id: String) -> Element {
                                         onchange: move |e| upload_handler(e, "cv")
                                     }
                                     if !cv_path().is_empty() {
-                                        div { class: "mt-2 text-[8px] font-mono truncate opacity-40 uppercase", "{cv_path}" }
+                                        DocumentPreview { path: cv_path(), thumbnail_url: cv_thumbnail() }
                                     }
                                 }
                             }
@@ -306,7 +347,7 @@ pub fn ApplicationEdit(id: String) -> Element {
                                     onchange: move |e| upload_handler(e, "cover_letter")
                                 }
                                 if !cover_letter_path().is_empty() {
-                                    div { class: "mt-2 text-[8px] font-mono truncate opacity-40 uppercase", "{cover_letter_path}" }
+                                    DocumentPreview { path: cover_letter_path(), thumbnail_url: cover_letter_thumbnail() }
                                 }
                             }
 
@@ -1,10 +1,13 @@
 use crate::components::kanban_board::KanbanBoard;
-use crate::models::application::UpdateApplicationPayload;
+use crate::models::application::{DocumentSearchHit, UpdateApplicationPayload};
 use dioxus::prelude::*;
 
 #[component]
 pub fn ApplicationsList() -> Element {
     let mut view_mode = use_signal(|| "table".to_string());
+    let mut doc_query = use_signal(|| "".to_string());
+    let mut doc_hits = use_signal(Vec::<DocumentSearchHit>::new);
+    let mut doc_error = use_signal(|| "".to_string());
     let applications = use_resource(move || async move {
         crate::services::application_service::list_applications().await
     });
@@ -75,6 +78,67 @@ pub fn ApplicationsList() -> Element {
                 }
             }
 
+            // Document full-text search (CV / cover letter contents)
+            div { class: "noir-card p-6 space-y-4",
+                form {
+                    class: "flex gap-3",
+                    onsubmit: move |evt: FormEvent| async move {
+                        evt.prevent_default();
+                        doc_error.set("".to_string());
+                        match crate::services::application_service::search_documents(&doc_query()).await {
+                            Ok(hits) => doc_hits.set(hits),
+                            Err(e) => doc_error.set(e),
+                        }
+                    },
+                    input {
+                        r#type: "search",
+                        class: "flex-1 bg-[var(--hover-bg)] border border-[var(--glass-border)] rounded px-4 py-3 text-xs font-mono focus:border-[var(--accent-color)] outline-none transition-all tracking-wider text-white",
+                        placeholder: "Search CV & cover letter contents...",
+                        value: "{doc_query}",
+                        oninput: move |e| doc_query.set(e.value())
+                    }
+                    button {
+                        r#type: "submit",
+                        class: "noir-btn px-6 text-[10px]",
+                        "Search Docs"
+                    }
+                }
+                if !doc_error().is_empty() {
+                    p { class: "text-red-500 text-[10px] font-black uppercase tracking-widest", "{doc_error}" }
+                }
+                for hit in doc_hits() {
+                    div { class: "flex gap-4 items-start border-t pt-4",
+                        style: "border-color: var(--glass-border);",
+                        if let Some(thumb) = &hit.thumbnail_path {
+                            img {
+                                src: "{BASE_URL}{thumb}",
+                                class: "w-12 h-16 object-cover object-top rounded border bg-white/5",
+                                style: "border-color: var(--glass-border);",
+                            }
+                        }
+                        div { class: "flex-1 min-w-0 space-y-1",
+                            div { class: "flex items-center gap-3 text-[10px] font-black uppercase tracking-widest",
+                                a {
+                                    href: "{BASE_URL}{hit.file_path}",
+                                    target: "_blank",
+                                    style: "color: var(--accent-color)",
+                                    "{hit.original_name}"
+                                }
+                                if let (Some(app_id), Some(company)) = (hit.application_id, &hit.company) {
+                                    Link {
+                                        to: format!("/admin/applications/{}/edit", app_id),
+                                        class: "opacity-60 hover:opacity-100",
+                                        style: "color: var(--text-color)",
+                                        "{company} / {hit.role.clone().unwrap_or_default()}"
+                                    }
+                                }
+                            }
+                            p { class: "text-xs opacity-60 font-mono whitespace-pre-wrap", "{hit.snippet}" }
+                        }
+                    }
+                }
+            }
+
             match &*applications.read() {
                 Some(Ok(apps)) => rsx! {
                     if view_mode() == "table" {
@@ -0,0 +1,141 @@
+use crate::models::outbound_email::STATUSES;
+use crate::services::auth_service;
+use dioxus::prelude::*;
+
+const DATE_FMT: &str = "%Y-%m-%d %H:%M";
+
+fn status_style(status: &str) -> &'static str {
+    match status {
+        "sent" => "color: var(--status-offer); border-color: var(--status-offer);",
+        "dead" => "color: var(--status-rejected); border-color: var(--status-rejected);",
+        _ => "color: var(--status-interview); border-color: var(--status-interview);",
+    }
+}
+
+fn status_label(status: &str) -> &str {
+    STATUSES
+        .iter()
+        .find(|(id, _)| *id == status)
+        .map(|(_, label)| *label)
+        .unwrap_or(status)
+}
+
+#[component]
+pub fn Emails() -> Element {
+    let mut filter = use_signal(|| "dead".to_string());
+    let mut emails = use_resource(move || {
+        let status = filter();
+        async move {
+            let status = Some(status.as_str()).filter(|s| !s.is_empty());
+            auth_service::list_outbound_emails(status).await
+        }
+    });
+    let mut error_msg = use_signal(|| "".to_string());
+
+    rsx! {
+        div { class: "max-w-4xl mx-auto space-y-8",
+            div { class: "border-b pb-8 flex items-end justify-between gap-6",
+                style: "border-color: var(--glass-border);",
+                div {
+                    h2 { class: "text-4xl font-black tracking-tighter uppercase",
+                        style: "color: var(--text-color); text-shadow: 0 0 10px var(--accent-glow);",
+                        "Outbox"
+                    }
+                    p { class: "text-[10px] font-mono mt-2 uppercase tracking-[0.2em] opacity-40",
+                        style: "color: var(--accent-color)",
+                        ":: Outgoing email queue ::"
+                    }
+                }
+                select {
+                    class: "bg-[var(--hover-bg)] border border-[var(--glass-border)] rounded px-4 py-3 text-xs font-black uppercase tracking-widest focus:border-[var(--accent-color)] outline-none transition-all text-white",
+                    value: "{filter}",
+                    onchange: move |e| filter.set(e.value()),
+                    option { value: "", "All" }
+                    for (id, label) in STATUSES {
+                        option { value: "{id}", "{label}" }
+                    }
+                }
+            }
+
+            if !error_msg().is_empty() {
+                p { class: "text-red-500 text-[10px] font-black uppercase tracking-widest", "{error_msg}" }
+            }
+
+            match &*emails.read() {
+                Some(Ok(list)) if list.is_empty() => rsx! {
+                    div { class: "text-center py-8 font-mono text-[10px] tracking-widest uppercase opacity-30",
+                        "// NO EMAILS"
+                    }
+                },
+                Some(Ok(list)) => rsx! {
+                    div { class: "space-y-3",
+                        for email in list.iter().cloned() {
+                            div {
+                                key: "{email.id}",
+                                class: "noir-card p-5 flex items-center justify-between gap-6",
+                                div { class: "space-y-1 min-w-0",
+                                    div { class: "flex items-center gap-3",
+                                        span {
+                                            class: "px-2 py-0.5 rounded border text-[9px] font-black uppercase tracking-widest",
+                                            style: status_style(&email.status),
+                                            "{status_label(&email.status)}"
+                                        }
+                                        span { class: "font-bold truncate", "{email.subject}" }
+                                    }
+                                    p { class: "text-xs opacity-60 truncate", "{email.recipient} · {email.template}" }
+                                    p { class: "text-[10px] font-mono opacity-50 tracking-wider",
+                                        {
+                                            match (email.status.as_str(), &email.sent_at) {
+                                                ("sent", Some(at)) => format!("SENT {}", at.format(DATE_FMT)),
+                                                ("pending", _) if email.attempts > 0 => format!(
+                                                    "ATTEMPT {} FAILED · NEXT TRY {}",
+                                                    email.attempts,
+                                                    email.next_attempt_at.format(DATE_FMT)
+                                                ),
+                                                _ => format!(
+                                                    "QUEUED {} · {} ATTEMPT(S)",
+                                                    email.created_at.format(DATE_FMT),
+                                                    email.attempts
+                                                ),
+                                            }
+                                        }
+                                    }
+                                    if let Some(error) = &email.last_error {
+                                        p { class: "text-[10px] font-mono break-all",
+                                            style: "color: var(--status-rejected)",
+                                            "{error}"
+                                        }
+                                    }
+                                }
+                                if email.status != "sending" {
+                                    button {
+                                        class: "noir-btn px-4 py-2 text-[10px] shrink-0",
+                                        onclick: move |_| {
+                                            let id = email.id.to_string();
+                                            async move {
+                                                error_msg.set("".to_string());
+                                                match auth_service::resend_outbound_email(&id).await {
+                                                    Ok(_) => emails.restart(),
+                                                    Err(e) => error_msg.set(e),
+                                                }
+                                            }
+                                        },
+                                        "Resend"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+                Some(Err(e)) => rsx! {
+                    p { class: "text-red-500 text-[10px] font-black uppercase tracking-widest", "{e}" }
+                },
+                None => rsx! {
+                    div { class: "text-center py-8 font-mono text-[10px] tracking-widest uppercase opacity-30 animate-pulse",
+                        "// SCANNING..."
+                    }
+                },
+            }
+        }
+    }
+}
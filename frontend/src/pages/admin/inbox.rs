@@ -0,0 +1,276 @@
+use crate::models::contact_message::ContactMessage;
+use crate::services::contact_service;
+use crate::services::sse_service::INBOX_REVISION;
+use dioxus::prelude::*;
+
+const DATE_FMT: &str = "%Y-%m-%d %H:%M";
+
+/// (filter value, label); the empty filter is every message that isn't archived or spam
+const FILTERS: [(&str, &str); 5] = [
+    ("", "Inbox"),
+    ("unread", "Unread"),
+    ("read", "Read"),
+    ("archived", "Archived"),
+    ("spam", "Spam"),
+];
+
+#[component]
+pub fn Inbox() -> Element {
+    let mut filter = use_signal(|| "".to_string());
+    let mut messages = use_resource(move || {
+        let status = filter();
+        let _ = INBOX_REVISION();
+        async move {
+            let status = Some(status.as_str()).filter(|s| !s.is_empty());
+            contact_service::list_messages(status).await
+        }
+    });
+    let error_msg = use_signal(|| "".to_string());
+
+    rsx! {
+        div { class: "max-w-4xl mx-auto space-y-8",
+            div { class: "border-b pb-8",
+                style: "border-color: var(--glass-border);",
+                h2 { class: "text-4xl font-black tracking-tighter uppercase",
+                    style: "color: var(--text-color); text-shadow: 0 0 10px var(--accent-glow);",
+                    "Inbox"
+                }
+                p { class: "text-[10px] font-mono mt-2 uppercase tracking-[0.2em] opacity-40",
+                    style: "color: var(--accent-color)",
+                    ":: Messages from the contact form ::"
+                }
+            }
+
+            div { class: "flex gap-2",
+                for (value, label) in FILTERS {
+                    button {
+                        class: "px-4 py-2 rounded border text-[10px] font-black uppercase tracking-widest transition-all",
+                        style: if filter() == value { "color: var(--accent-color); border-color: var(--accent-color);" } else { "border-color: var(--glass-border); opacity: 0.6;" },
+                        onclick: move |_| filter.set(value.to_string()),
+                        "{label}"
+                    }
+                }
+            }
+
+            if !error_msg().is_empty() {
+                p { class: "text-red-500 text-[10px] font-black uppercase tracking-widest", "{error_msg}" }
+            }
+
+            match &*messages.read() {
+                Some(Ok(list)) if list.is_empty() => rsx! {
+                    div { class: "text-center py-8 font-mono text-[10px] tracking-widest uppercase opacity-30",
+                        "// NO MESSAGES"
+                    }
+                },
+                Some(Ok(list)) => rsx! {
+                    div { class: "space-y-3",
+                        for message in list.iter().cloned() {
+                            MessageCard {
+                                key: "{message.id}",
+                                message,
+                                on_change: move |_| messages.restart(),
+                                error_msg,
+                            }
+                        }
+                    }
+                },
+                Some(Err(e)) => rsx! {
+                    p { class: "text-red-500 text-[10px] font-black uppercase tracking-widest", "{e}" }
+                },
+                None => rsx! {
+                    div { class: "text-center py-8 font-mono text-[10px] tracking-widest uppercase opacity-30 animate-pulse",
+                        "// SCANNING..."
+                    }
+                },
+            }
+        }
+    }
+}
+
+#[component]
+fn MessageCard(
+    message: ContactMessage,
+    on_change: EventHandler<()>,
+    mut error_msg: Signal<String>,
+) -> Element {
+    let navigator = use_navigator();
+    let mut replying = use_signal(|| false);
+    let mut creating_lead = use_signal(|| false);
+    let mut reply = use_signal(|| "".to_string());
+    let id = message.id.to_string();
+    let unread = message.status == "unread";
+    let archived = message.status == "archived";
+    let spam = message.status == "spam";
+
+    let set_status = {
+        let id = id.clone();
+        move |status: &'static str| {
+            let id = id.clone();
+            async move {
+                error_msg.set("".to_string());
+                match contact_service::set_message_status(&id, status).await {
+                    Ok(_) => on_change.call(()),
+                    Err(e) => error_msg.set(e),
+                }
+            }
+        }
+    };
+    let toggle_read = set_status.clone();
+    let toggle_archived = set_status;
+
+    let on_delete = {
+        let id = id.clone();
+        move |_| {
+            let id = id.clone();
+            async move {
+                error_msg.set("".to_string());
+                match contact_service::delete_message(&id).await {
+                    Ok(_) => on_change.call(()),
+                    Err(e) => error_msg.set(e),
+                }
+            }
+        }
+    };
+
+    let on_create_lead = {
+        let id = id.clone();
+        move |_| {
+            let id = id.clone();
+            async move {
+                error_msg.set("".to_string());
+                creating_lead.set(true);
+                match contact_service::create_lead(&id).await {
+                    Ok(app) => {
+                        navigator.push(format!("/admin/applications/{}/edit", app.id));
+                    }
+                    Err(e) => error_msg.set(e),
+                }
+                creating_lead.set(false);
+            }
+        }
+    };
+
+    let on_reply = move |evt: FormEvent| {
+        let id = id.clone();
+        async move {
+            evt.prevent_default();
+            error_msg.set("".to_string());
+            match contact_service::reply_to_message(&id, &reply()).await {
+                Ok(_) => {
+                    reply.set("".to_string());
+                    replying.set(false);
+                    on_change.call(());
+                }
+                Err(e) => error_msg.set(e),
+            }
+        }
+    };
+
+    rsx! {
+        div { class: "noir-card p-5 space-y-3",
+            style: if unread { "border-color: var(--accent-color);" } else { "" },
+            div { class: "flex items-start justify-between gap-6",
+                div { class: "space-y-1 min-w-0",
+                    div { class: "flex items-center gap-3",
+                        if unread {
+                            span { class: "w-2 h-2 rounded-full shrink-0", style: "background: var(--accent-color);" }
+                        }
+                        span { class: "font-bold truncate", "{message.name}" }
+                        a { class: "text-xs opacity-60 truncate hover:underline", href: "mailto:{message.email}", "{message.email}" }
+                    }
+                    p { class: "text-[10px] font-mono opacity-50 tracking-wider",
+                        "RECEIVED {message.created_at.format(DATE_FMT)}"
+                        if let Some(at) = message.replied_at {
+                            " · REPLIED {at.format(DATE_FMT)}"
+                        }
+                    }
+                }
+                div { class: "flex gap-2 shrink-0",
+                    if spam {
+                        button {
+                            class: "px-3 py-2 rounded border text-[10px] font-black uppercase tracking-widest transition-all",
+                            style: "color: var(--status-offer); border-color: var(--status-offer);",
+                            onclick: move |_| toggle_read("unread"),
+                            "Not Spam"
+                        }
+                    } else {
+                        button {
+                            class: "px-3 py-2 rounded border text-[10px] font-black uppercase tracking-widest transition-all",
+                            style: "border-color: var(--glass-border);",
+                            onclick: move |_| toggle_read(if unread { "read" } else { "unread" }),
+                            if unread { "Mark Read" } else { "Mark Unread" }
+                        }
+                        button {
+                            class: "px-3 py-2 rounded border text-[10px] font-black uppercase tracking-widest transition-all",
+                            style: "border-color: var(--glass-border);",
+                            onclick: move |_| toggle_archived(if archived { "read" } else { "archived" }),
+                            if archived { "Unarchive" } else { "Archive" }
+                        }
+                    }
+                    button {
+                        class: "px-3 py-2 rounded border text-[10px] font-black uppercase tracking-widest hover:bg-red-500/10 transition-all",
+                        style: "color: var(--status-rejected); border-color: var(--status-rejected);",
+                        onclick: on_delete,
+                        "Delete"
+                    }
+                }
+            }
+
+            if !message.spam_reasons.is_empty() {
+                p { class: "text-[10px] font-mono tracking-wider",
+                    style: "color: var(--status-rejected)",
+                    "SPAM FILTER: "
+                    {message.spam_reasons.join(", ")}
+                }
+            }
+            if let Some(link) = &message.link {
+                p { class: "text-xs font-mono break-all", style: "color: var(--accent-color)", "{link}" }
+            }
+            p { class: "text-sm whitespace-pre-wrap opacity-80", "{message.message}" }
+
+            if replying() {
+                form { class: "space-y-3", onsubmit: on_reply,
+                    textarea {
+                        class: "w-full h-32 bg-[var(--hover-bg)] border border-[var(--glass-border)] rounded px-4 py-3 text-xs font-mono focus:border-[var(--accent-color)] outline-none transition-all text-white",
+                        placeholder: "Your reply is emailed to {message.email}",
+                        value: "{reply}",
+                        oninput: move |e| reply.set(e.value())
+                    }
+                    div { class: "flex gap-2",
+                        button { r#type: "submit", class: "noir-btn px-6 py-3 text-[10px]", "Send Reply" }
+                        button {
+                            r#type: "button",
+                            class: "px-4 py-2 text-[10px] font-black uppercase tracking-widest opacity-60",
+                            onclick: move |_| replying.set(false),
+                            "Cancel"
+                        }
+                    }
+                }
+            } else {
+                div { class: "flex gap-2",
+                    button {
+                        class: "noir-btn px-6 py-3 text-[10px]",
+                        onclick: move |_| replying.set(true),
+                        "Reply"
+                    }
+                    if let Some(application_id) = message.application_id {
+                        Link {
+                            to: format!("/admin/applications/{}/edit", application_id),
+                            class: "px-4 py-3 rounded border text-[10px] font-black uppercase tracking-widest transition-all",
+                            style: "color: var(--accent-color); border-color: var(--accent-color);",
+                            "Open Lead"
+                        }
+                    } else {
+                        button {
+                            class: "px-4 py-3 rounded border text-[10px] font-black uppercase tracking-widest transition-all disabled:opacity-30",
+                            style: "color: var(--accent-color); border-color: var(--accent-color);",
+                            disabled: creating_lead(),
+                            onclick: on_create_lead,
+                            if creating_lead() { "Scanning..." } else { "Create Lead" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
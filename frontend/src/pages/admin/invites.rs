@@ -0,0 +1,147 @@
+use crate::models::invite::{CreateInvitePayload, CreatedInvite};
+use crate::services::auth_service;
+use dioxus::prelude::*;
+
+const DATE_FMT: &str = "%Y-%m-%d %H:%M";
+
+#[component]
+pub fn Invites() -> Element {
+    let mut invites = use_resource(move || async move { auth_service::list_invites().await });
+    let mut note = use_signal(|| "".to_string());
+    let mut days = use_signal(|| "7".to_string());
+    let mut created = use_signal(|| None::<CreatedInvite>);
+    let mut error_msg = use_signal(|| "".to_string());
+
+    let on_create = move |evt: FormEvent| async move {
+        evt.prevent_default();
+        error_msg.set("".to_string());
+        let payload = CreateInvitePayload {
+            note: Some(note()).filter(|n| !n.trim().is_empty()),
+            expires_in_days: days().trim().parse().ok(),
+        };
+        match auth_service::create_invite(payload).await {
+            Ok(invite) => {
+                created.set(Some(invite));
+                note.set("".to_string());
+                invites.restart();
+            }
+            Err(e) => error_msg.set(e),
+        }
+    };
+
+    rsx! {
+        div { class: "max-w-4xl mx-auto space-y-8",
+            div { class: "border-b pb-8",
+                style: "border-color: var(--glass-border);",
+                h2 { class: "text-4xl font-black tracking-tighter uppercase",
+                    style: "color: var(--text-color); text-shadow: 0 0 10px var(--accent-glow);",
+                    "Invites"
+                }
+                p { class: "text-[10px] font-mono mt-2 uppercase tracking-[0.2em] opacity-40",
+                    style: "color: var(--accent-color)",
+                    ":: Single-use registration codes ::"
+                }
+            }
+
+            form { class: "noir-card p-6 flex flex-col md:flex-row gap-3 md:items-end", onsubmit: on_create,
+                div { class: "flex-1 space-y-2",
+                    label { class: "text-[10px] font-black uppercase tracking-widest opacity-60", "Note (who is it for?)" }
+                    input {
+                        r#type: "text",
+                        class: "w-full bg-[var(--hover-bg)] border border-[var(--glass-border)] rounded px-4 py-3 text-xs font-mono focus:border-[var(--accent-color)] outline-none transition-all tracking-wider text-white",
+                        value: "{note}",
+                        oninput: move |e| note.set(e.value())
+                    }
+                }
+                div { class: "space-y-2",
+                    label { class: "text-[10px] font-black uppercase tracking-widest opacity-60", "Valid (days)" }
+                    input {
+                        r#type: "number",
+                        min: "1",
+                        max: "90",
+                        class: "w-28 bg-[var(--hover-bg)] border border-[var(--glass-border)] rounded px-4 py-3 text-xs font-mono focus:border-[var(--accent-color)] outline-none transition-all tracking-wider text-white",
+                        value: "{days}",
+                        oninput: move |e| days.set(e.value())
+                    }
+                }
+                button { r#type: "submit", class: "noir-btn px-6 py-3 text-[10px]", "Create Invite" }
+            }
+
+            if !error_msg().is_empty() {
+                p { class: "text-red-500 text-[10px] font-black uppercase tracking-widest", "{error_msg}" }
+            }
+
+            if let Some(invite) = created() {
+                div { class: "noir-card p-6 space-y-3",
+                    p { class: "text-xs opacity-60", "Share this code (or the link) now. It is not shown again and works once." }
+                    p { class: "font-mono text-xl tracking-widest select-all", style: "color: var(--accent-color)", "{invite.code}" }
+                    p { class: "text-[10px] font-mono opacity-50 break-all select-all",
+                        "/admin/register?invite={invite.code}"
+                    }
+                }
+            }
+
+            match &*invites.read() {
+                Some(Ok(list)) if list.is_empty() => rsx! {
+                    div { class: "text-center py-8 font-mono text-[10px] tracking-widest uppercase opacity-30",
+                        "// NO INVITES ISSUED"
+                    }
+                },
+                Some(Ok(list)) => rsx! {
+                    div { class: "space-y-3",
+                        for invite in list.iter().cloned() {
+                            div {
+                                key: "{invite.id}",
+                                class: "noir-card p-5 flex items-center justify-between gap-6",
+                                div { class: "space-y-1 min-w-0",
+                                    div { class: "flex items-center gap-3",
+                                        span { class: "font-mono font-bold tracking-widest", "{invite.code_hint}-····" }
+                                        if let Some(n) = &invite.note {
+                                            span { class: "text-xs opacity-60 truncate", "{n}" }
+                                        }
+                                    }
+                                    p { class: "text-[10px] font-mono opacity-50 tracking-wider",
+                                        {
+                                            match (&invite.used_at, &invite.used_by_email) {
+                                                (Some(at), email) => format!(
+                                                    "USED {} BY {}",
+                                                    at.format(DATE_FMT),
+                                                    email.as_deref().unwrap_or("deleted account")
+                                                ),
+                                                (None, _) => format!("EXPIRES {}", invite.expires_at.format(DATE_FMT)),
+                                            }
+                                        }
+                                    }
+                                }
+                                if invite.used_at.is_none() {
+                                    button {
+                                        class: "px-4 py-2 rounded border text-[10px] font-black uppercase tracking-widest hover:bg-red-500/10 transition-all",
+                                        style: "color: var(--status-rejected); border-color: var(--status-rejected);",
+                                        onclick: move |_| {
+                                            let id = invite.id.to_string();
+                                            async move {
+                                                match auth_service::delete_invite(&id).await {
+                                                    Ok(_) => invites.restart(),
+                                                    Err(e) => error_msg.set(e),
+                                                }
+                                            }
+                                        },
+                                        "Revoke"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+                Some(Err(e)) => rsx! {
+                    p { class: "text-red-500 text-[10px] font-black uppercase tracking-widest", "{e}" }
+                },
+                None => rsx! {
+                    div { class: "text-center py-8 font-mono text-[10px] tracking-widest uppercase opacity-30 animate-pulse",
+                        "// SCANNING..."
+                    }
+                },
+            }
+        }
+    }
+}
@@ -6,15 +6,11 @@ pub fn AdminLayout() -> Element {
     // Simple auth check via effect
     use_effect(move || {
         spawn(async move {
-            let mut eval = document::eval("return localStorage.getItem('admin_token')");
-            let res = eval.recv::<serde_json::Value>().await;
-            match res {
-                Ok(val) if !val.is_null() => {
-                    // Valid token exist
-                }
-                _ => {
-                    navigator.push("/admin/login");
-                }
+            if crate::services::application_service::ensure_session()
+                .await
+                .is_none()
+            {
+                navigator.push("/admin/login");
             }
         });
     });
@@ -65,6 +61,86 @@ pub fn AdminLayout() -> Element {
                             span { class: "font-medium tracking-wide uppercase text-xs opacity-70 group-hover:opacity-100", "Applications" }
                         }
                     }
+
+                    Link {
+                        to: "/admin/inbox",
+                        class: "block px-4 py-3 rounded border border-transparent hover:bg-[var(--hover-bg)] transition-all duration-300 group",
+                        style: "color: var(--text-color);",
+                        div { class: "flex items-center gap-3",
+                            span { class: "text-xl group-hover:drop-shadow-[0_0_5px_var(--accent-glow)]", "📥" }
+                            span { class: "font-medium tracking-wide uppercase text-xs opacity-70 group-hover:opacity-100", "Inbox" }
+                        }
+                    }
+
+                    Link {
+                        to: "/admin/moderation",
+                        class: "block px-4 py-3 rounded border border-transparent hover:bg-[var(--hover-bg)] transition-all duration-300 group",
+                        style: "color: var(--text-color);",
+                        div { class: "flex items-center gap-3",
+                            span { class: "text-xl group-hover:drop-shadow-[0_0_5px_var(--accent-glow)]", "💬" }
+                            span { class: "font-medium tracking-wide uppercase text-xs opacity-70 group-hover:opacity-100", "Moderation" }
+                        }
+                    }
+
+                    Link {
+                        to: "/admin/sessions",
+                        class: "block px-4 py-3 rounded border border-transparent hover:bg-[var(--hover-bg)] transition-all duration-300 group",
+                        style: "color: var(--text-color);",
+                        div { class: "flex items-center gap-3",
+                            span { class: "text-xl group-hover:drop-shadow-[0_0_5px_var(--accent-glow)]", "🔐" }
+                            span { class: "font-medium tracking-wide uppercase text-xs opacity-70 group-hover:opacity-100", "Sessions" }
+                        }
+                    }
+
+                    Link {
+                        to: "/admin/security",
+                        class: "block px-4 py-3 rounded border border-transparent hover:bg-[var(--hover-bg)] transition-all duration-300 group",
+                        style: "color: var(--text-color);",
+                        div { class: "flex items-center gap-3",
+                            span { class: "text-xl group-hover:drop-shadow-[0_0_5px_var(--accent-glow)]", "🛡️" }
+                            span { class: "font-medium tracking-wide uppercase text-xs opacity-70 group-hover:opacity-100", "Security" }
+                        }
+                    }
+
+                    Link {
+                        to: "/admin/invites",
+                        class: "block px-4 py-3 rounded border border-transparent hover:bg-[var(--hover-bg)] transition-all duration-300 group",
+                        style: "color: var(--text-color);",
+                        div { class: "flex items-center gap-3",
+                            span { class: "text-xl group-hover:drop-shadow-[0_0_5px_var(--accent-glow)]", "✉️" }
+                            span { class: "font-medium tracking-wide uppercase text-xs opacity-70 group-hover:opacity-100", "Invites" }
+                        }
+                    }
+
+                    Link {
+                        to: "/admin/tokens",
+                        class: "block px-4 py-3 rounded border border-transparent hover:bg-[var(--hover-bg)] transition-all duration-300 group",
+                        style: "color: var(--text-color);",
+                        div { class: "flex items-center gap-3",
+                            span { class: "text-xl group-hover:drop-shadow-[0_0_5px_var(--accent-glow)]", "🔑" }
+                            span { class: "font-medium tracking-wide uppercase text-xs opacity-70 group-hover:opacity-100", "API Tokens" }
+                        }
+                    }
+
+                    Link {
+                        to: "/admin/emails",
+                        class: "block px-4 py-3 rounded border border-transparent hover:bg-[var(--hover-bg)] transition-all duration-300 group",
+                        style: "color: var(--text-color);",
+                        div { class: "flex items-center gap-3",
+                            span { class: "text-xl group-hover:drop-shadow-[0_0_5px_var(--accent-glow)]", "📨" }
+                            span { class: "font-medium tracking-wide uppercase text-xs opacity-70 group-hover:opacity-100", "Outbox" }
+                        }
+                    }
+
+                    Link {
+                        to: "/admin/account",
+                        class: "block px-4 py-3 rounded border border-transparent hover:bg-[var(--hover-bg)] transition-all duration-300 group",
+                        style: "color: var(--text-color);",
+                        div { class: "flex items-center gap-3",
+                            span { class: "text-xl group-hover:drop-shadow-[0_0_5px_var(--accent-glow)]", "👤" }
+                            span { class: "font-medium tracking-wide uppercase text-xs opacity-70 group-hover:opacity-100", "Account" }
+                        }
+                    }
                 }
 
                 div { class: "p-6 border-t",
@@ -74,7 +150,7 @@ pub fn AdminLayout() -> Element {
                         style: "color: var(--status-rejected);",
                         onclick: move |_| {
                             spawn(async move {
-                                let _ = document::eval("localStorage.removeItem('admin_token')").await;
+                                crate::services::auth_service::logout().await;
                                 navigator.push("/admin/login");
                             });
                         },
@@ -1,5 +1,13 @@
+pub mod account;
+pub mod api_tokens;
 pub mod application_create;
 pub mod application_edit;
 pub mod applications_list;
 pub mod dashboard;
+pub mod emails;
+pub mod inbox;
+pub mod invites;
 pub mod layout;
+pub mod moderation;
+pub mod sessions;
+pub mod security;
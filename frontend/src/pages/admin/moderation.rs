@@ -0,0 +1,248 @@
+use crate::models::application::{CommentWithContext, COMMENT_STATUSES};
+use crate::services::application_service;
+use crate::services::sse_service::MODERATION_REVISION;
+use dioxus::prelude::*;
+
+const DATE_FMT: &str = "%Y-%m-%d %H:%M";
+
+/// (mode, label) pairs for the moderation setting
+const MODES: [(&str, &str); 2] = [("auto", "Publish at once"), ("review", "Hold for review")];
+
+#[component]
+pub fn Moderation() -> Element {
+    let mut filter = use_signal(|| "pending".to_string());
+    let mut comments = use_resource(move || {
+        let status = filter();
+        let _ = MODERATION_REVISION();
+        async move { application_service::list_moderated_comments(&status).await }
+    });
+    let mut settings = use_resource(application_service::get_moderation_settings);
+    let mut error_msg = use_signal(|| "".to_string());
+
+    let set_mode = move |mode: &'static str| async move {
+        error_msg.set("".to_string());
+        match application_service::update_moderation_settings(mode).await {
+            Ok(_) => settings.restart(),
+            Err(e) => error_msg.set(e),
+        }
+    };
+
+    let mode = match &*settings.read() {
+        Some(Ok(s)) => s.mode.clone(),
+        _ => "".to_string(),
+    };
+
+    rsx! {
+        div { class: "max-w-4xl mx-auto space-y-8",
+            div { class: "border-b pb-8",
+                style: "border-color: var(--glass-border);",
+                h2 { class: "text-4xl font-black tracking-tighter uppercase",
+                    style: "color: var(--text-color); text-shadow: 0 0 10px var(--accent-glow);",
+                    "Moderation"
+                }
+                p { class: "text-[10px] font-mono mt-2 uppercase tracking-[0.2em] opacity-40",
+                    style: "color: var(--accent-color)",
+                    ":: Visitor comments on your applications ::"
+                }
+            }
+
+            div { class: "noir-card p-5 flex items-center justify-between gap-6",
+                div { class: "space-y-1",
+                    p { class: "text-xs font-black uppercase tracking-widest", "New Comments" }
+                    p { class: "text-[10px] font-mono opacity-50 tracking-wider",
+                        "Comments can also be closed per application on its edit page"
+                    }
+                }
+                div { class: "flex gap-2 shrink-0",
+                    for (value, label) in MODES {
+                        button {
+                            class: "px-4 py-2 rounded border text-[10px] font-black uppercase tracking-widest transition-all",
+                            style: if mode == value { "color: var(--accent-color); border-color: var(--accent-color);" } else { "border-color: var(--glass-border); opacity: 0.6;" },
+                            onclick: move |_| set_mode(value),
+                            "{label}"
+                        }
+                    }
+                }
+            }
+
+            div { class: "flex gap-2",
+                for (value, label) in COMMENT_STATUSES {
+                    button {
+                        class: "px-4 py-2 rounded border text-[10px] font-black uppercase tracking-widest transition-all",
+                        style: if filter() == value { "color: var(--accent-color); border-color: var(--accent-color);" } else { "border-color: var(--glass-border); opacity: 0.6;" },
+                        onclick: move |_| filter.set(value.to_string()),
+                        "{label}"
+                    }
+                }
+            }
+
+            if !error_msg().is_empty() {
+                p { class: "text-red-500 text-[10px] font-black uppercase tracking-widest", "{error_msg}" }
+            }
+
+            match &*comments.read() {
+                Some(Ok(list)) if list.is_empty() => rsx! {
+                    div { class: "text-center py-8 font-mono text-[10px] tracking-widest uppercase opacity-30",
+                        "// NO COMMENTS"
+                    }
+                },
+                Some(Ok(list)) => rsx! {
+                    div { class: "space-y-3",
+                        for comment in list.iter().cloned() {
+                            CommentCard {
+                                key: "{comment.id}",
+                                comment,
+                                on_change: move |_| comments.restart(),
+                                error_msg,
+                            }
+                        }
+                    }
+                },
+                Some(Err(e)) => rsx! {
+                    p { class: "text-red-500 text-[10px] font-black uppercase tracking-widest", "{e}" }
+                },
+                None => rsx! {
+                    div { class: "text-center py-8 font-mono text-[10px] tracking-widest uppercase opacity-30 animate-pulse",
+                        "// SCANNING..."
+                    }
+                },
+            }
+        }
+    }
+}
+
+#[component]
+fn CommentCard(
+    comment: CommentWithContext,
+    on_change: EventHandler<()>,
+    mut error_msg: Signal<String>,
+) -> Element {
+    let mut replying = use_signal(|| false);
+    let mut reply = use_signal(|| "".to_string());
+    let id = comment.id.to_string();
+
+    let act = {
+        let id = id.clone();
+        move |action: &'static str| {
+            let id = id.clone();
+            async move {
+                error_msg.set("".to_string());
+                let result = match action {
+                    "delete" => application_service::delete_comment(&id).await,
+                    _ => application_service::moderate_comment(&id, action).await,
+                };
+                match result {
+                    Ok(_) => on_change.call(()),
+                    Err(e) => error_msg.set(e),
+                }
+            }
+        }
+    };
+    let approve = act.clone();
+    let hide = act.clone();
+    let delete = act;
+
+    let on_reply = move |evt: FormEvent| {
+        let id = id.clone();
+        async move {
+            evt.prevent_default();
+            error_msg.set("".to_string());
+            match application_service::reply_to_comment(&id, &reply()).await {
+                Ok(_) => {
+                    reply.set("".to_string());
+                    replying.set(false);
+                    on_change.call(());
+                }
+                Err(e) => error_msg.set(e),
+            }
+        }
+    };
+
+    rsx! {
+        div { class: "noir-card p-5 space-y-3",
+            div { class: "flex items-start justify-between gap-6",
+                div { class: "space-y-1 min-w-0",
+                    div { class: "flex items-center gap-3",
+                        span { class: "font-bold truncate", "{comment.visitor_name}" }
+                        if comment.is_owner {
+                            span {
+                                class: "px-2 py-0.5 rounded border text-[9px] font-black uppercase tracking-widest",
+                                style: "color: var(--accent-color); border-color: var(--accent-color);",
+                                "✓ Owner"
+                            }
+                        } else if comment.parent_id.is_some() {
+                            span { class: "text-[9px] font-black uppercase tracking-widest opacity-40", "Reply" }
+                        }
+                    }
+                    p { class: "text-[10px] font-mono opacity-50 tracking-wider",
+                        "{comment.created_at.format(DATE_FMT)} · "
+                        Link {
+                            to: crate::Route::ApplicationDetail { id: comment.application_id.to_string() },
+                            class: "hover:underline",
+                            style: "color: var(--accent-color)",
+                            "{comment.company} / {comment.role}"
+                        }
+                    }
+                }
+                div { class: "flex gap-2 shrink-0",
+                    if comment.status != "approved" {
+                        button {
+                            class: "px-3 py-2 rounded border text-[10px] font-black uppercase tracking-widest transition-all",
+                            style: "color: var(--status-offer); border-color: var(--status-offer);",
+                            onclick: move |_| approve("approve"),
+                            "Approve"
+                        }
+                    }
+                    if comment.status != "rejected" {
+                        button {
+                            class: "px-3 py-2 rounded border text-[10px] font-black uppercase tracking-widest transition-all",
+                            style: "border-color: var(--glass-border);",
+                            onclick: move |_| hide("hide"),
+                            "Hide"
+                        }
+                    }
+                    button {
+                        class: "px-3 py-2 rounded border text-[10px] font-black uppercase tracking-widest hover:bg-red-500/10 transition-all",
+                        style: "color: var(--status-rejected); border-color: var(--status-rejected);",
+                        onclick: move |_| delete("delete"),
+                        "Delete"
+                    }
+                }
+            }
+            if !comment.spam_reasons.is_empty() {
+                p { class: "text-[10px] font-mono tracking-wider",
+                    style: "color: var(--status-rejected)",
+                    "SPAM FILTER: "
+                    {comment.spam_reasons.join(", ")}
+                }
+            }
+            p { class: "text-sm whitespace-pre-wrap opacity-80", "{comment.content}" }
+
+            if replying() {
+                form { class: "space-y-3", onsubmit: on_reply,
+                    textarea {
+                        class: "w-full h-24 bg-[var(--hover-bg)] border border-[var(--glass-border)] rounded px-4 py-3 text-xs font-mono focus:border-[var(--accent-color)] outline-none transition-all text-white",
+                        placeholder: "Shown publicly with a verified owner badge",
+                        value: "{reply}",
+                        oninput: move |e| reply.set(e.value())
+                    }
+                    div { class: "flex gap-2",
+                        button { r#type: "submit", class: "noir-btn px-6 py-3 text-[10px]", "Post Reply" }
+                        button {
+                            r#type: "button",
+                            class: "px-4 py-2 text-[10px] font-black uppercase tracking-widest opacity-60",
+                            onclick: move |_| replying.set(false),
+                            "Cancel"
+                        }
+                    }
+                }
+            } else if !comment.is_owner && comment.status != "rejected" {
+                button {
+                    class: "noir-btn px-6 py-3 text-[10px]",
+                    onclick: move |_| replying.set(true),
+                    if comment.status == "pending" { "Approve & Reply" } else { "Reply" }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use crate::models::two_factor::TwoFactorSetup;
+use crate::services::auth_service;
+use dioxus::prelude::*;
+
+#[component]
+pub fn Security() -> Element {
+    let mut status = use_resource(move || async move { auth_service::get_two_factor_status().await });
+    let mut setup = use_signal(|| None::<TwoFactorSetup>);
+    let mut recovery_codes = use_signal(Vec::<String>::new);
+    let mut code = use_signal(|| "".to_string());
+    let mut error_msg = use_signal(|| "".to_string());
+
+    let start_setup = move |_| async move {
+        error_msg.set("".to_string());
+        match auth_service::start_two_factor_setup().await {
+            Ok(s) => setup.set(Some(s)),
+            Err(e) => error_msg.set(e),
+        }
+    };
+
+    let confirm = move |evt: FormEvent| async move {
+        evt.prevent_default();
+        error_msg.set("".to_string());
+        match auth_service::confirm_two_factor(&code()).await {
+            Ok(codes) => {
+                setup.set(None);
+                code.set("".to_string());
+                recovery_codes.set(codes);
+                status.restart();
+            }
+            Err(e) => error_msg.set(e),
+        }
+    };
+
+    let disable = move |evt: FormEvent| async move {
+        evt.prevent_default();
+        error_msg.set("".to_string());
+        match auth_service::disable_two_factor(&code()).await {
+            Ok(_) => {
+                code.set("".to_string());
+                recovery_codes.set(Vec::new());
+                status.restart();
+            }
+            Err(e) => error_msg.set(e),
+        }
+    };
+
+    rsx! {
+        div { class: "max-w-4xl mx-auto space-y-8",
+            div { class: "border-b pb-8",
+                style: "border-color: var(--glass-border);",
+                h2 { class: "text-4xl font-black tracking-tighter uppercase",
+                    style: "color: var(--text-color); text-shadow: 0 0 10px var(--accent-glow);",
+                    "Security"
+                }
+                p { class: "text-[10px] font-mono mt-2 uppercase tracking-[0.2em] opacity-40",
+                    style: "color: var(--accent-color)",
+                    ":: Two-factor authentication ::"
+                }
+            }
+
+            if !error_msg().is_empty() {
+                p { class: "text-red-500 text-[10px] font-black uppercase tracking-widest", "{error_msg}" }
+            }
+
+            if !recovery_codes().is_empty() {
+                div { class: "noir-card p-6 space-y-4",
+                    h3 { class: "text-sm font-black uppercase tracking-widest", style: "color: var(--accent-color)", "Recovery codes" }
+                    p { class: "text-xs opacity-60",
+                        "Each code works once if you lose your authenticator. Store them somewhere safe: they will not be shown again."
+                    }
+                    div { class: "grid grid-cols-2 gap-2 font-mono text-sm",
+                        for rc in recovery_codes() {
+                            span { class: "px-3 py-2 rounded bg-white/5 border border-white/10 text-center", "{rc}" }
+                        }
+                    }
+                }
+            }
+
+            match &*status.read() {
+                Some(Ok(s)) if s.enabled => rsx! {
+                    div { class: "noir-card p-6 space-y-4",
+                        p { class: "text-sm font-bold", style: "color: var(--status-accepted)", "Two-factor authentication is ON" }
+                        p { class: "text-xs opacity-60", "{s.recovery_codes_remaining} recovery codes left." }
+                        form { class: "flex gap-3 items-center", onsubmit: disable,
+                            CodeInput { code }
+                            button {
+                                r#type: "submit",
+                                class: "px-6 py-3 rounded border text-[10px] font-black uppercase tracking-widest hover:bg-red-500/10 transition-all",
+                                style: "color: var(--status-rejected); border-color: var(--status-rejected);",
+                                "Disable 2FA"
+                            }
+                        }
+                    }
+                },
+                Some(Ok(_)) => rsx! {
+                    div { class: "noir-card p-6 space-y-6",
+                        p { class: "text-sm opacity-80",
+                            "Protect this account with a code from an authenticator app (Aegis, Google Authenticator, 1Password...)."
+                        }
+                        if let Some(s) = setup() {
+                            div { class: "flex flex-col md:flex-row gap-8 items-start",
+                                div { class: "bg-white p-2 rounded w-52 h-52 shrink-0", dangerous_inner_html: "{s.qr_svg}" }
+                                div { class: "space-y-4 min-w-0",
+                                    p { class: "text-xs opacity-60", "Scan the QR code, or enter this key manually:" }
+                                    p { class: "font-mono text-sm break-all select-all", "{s.secret}" }
+                                    form { class: "flex gap-3 items-center", onsubmit: confirm,
+                                        CodeInput { code }
+                                        button { r#type: "submit", class: "noir-btn px-6 py-3 text-[10px]", "Confirm" }
+                                    }
+                                }
+                            }
+                        } else {
+                            button { class: "noir-btn px-6 py-2.5 text-[10px]", onclick: start_setup, "Enable 2FA" }
+                        }
+                    }
+                },
+                Some(Err(e)) => rsx! {
+                    p { class: "text-red-500 text-[10px] font-black uppercase tracking-widest", "{e}" }
+                },
+                None => rsx! {
+                    div { class: "text-center py-8 font-mono text-[10px] tracking-widest uppercase opacity-30 animate-pulse",
+                        "// SCANNING..."
+                    }
+                },
+            }
+        }
+    }
+}
+
+#[component]
+fn CodeInput(code: Signal<String>) -> Element {
+    let mut code = code;
+    rsx! {
+        input {
+            r#type: "text",
+            inputmode: "numeric",
+            autocomplete: "one-time-code",
+            required: true,
+            class: "w-48 bg-[var(--hover-bg)] border border-[var(--glass-border)] rounded px-4 py-3 text-sm font-mono text-center tracking-[0.4em] focus:border-[var(--accent-color)] outline-none transition-all text-white",
+            placeholder: "000000",
+            value: "{code}",
+            oninput: move |e| code.set(e.value())
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use crate::services::auth_service;
+use dioxus::prelude::*;
+
+const DATE_FMT: &str = "%Y-%m-%d %H:%M";
+
+#[component]
+pub fn Sessions() -> Element {
+    let navigator = use_navigator();
+    let mut error_msg = use_signal(|| "".to_string());
+    let mut sessions = use_resource(move || async move { auth_service::list_sessions().await });
+
+    rsx! {
+        div { class: "max-w-4xl mx-auto space-y-8",
+            div { class: "flex flex-col md:flex-row justify-between items-start md:items-center gap-6 border-b pb-8",
+                style: "border-color: var(--glass-border);",
+                div {
+                    h2 { class: "text-4xl font-black tracking-tighter uppercase",
+                        style: "color: var(--text-color); text-shadow: 0 0 10px var(--accent-glow);",
+                        "Active Sessions"
+                    }
+                    p { class: "text-[10px] font-mono mt-2 uppercase tracking-[0.2em] opacity-40",
+                        style: "color: var(--accent-color)",
+                        ":: Devices currently holding access ::"
+                    }
+                }
+
+                button {
+                    class: "noir-btn px-6 py-2.5 text-[10px]",
+                    style: "background: var(--status-rejected);",
+                    onclick: move |_| async move {
+                        match auth_service::revoke_all_sessions().await {
+                            Ok(_) => {
+                                auth_service::clear_session().await;
+                                navigator.push("/admin/login");
+                            }
+                            Err(e) => error_msg.set(e),
+                        }
+                    },
+                    "Sign Out Everywhere"
+                }
+            }
+
+            if !error_msg().is_empty() {
+                p { class: "text-red-500 text-[10px] font-black uppercase tracking-widest", "{error_msg}" }
+            }
+
+            match &*sessions.read() {
+                Some(Ok(list)) if list.is_empty() => rsx! {
+                    div { class: "text-center py-8 font-mono text-[10px] tracking-widest uppercase opacity-30",
+                        "// NO ACTIVE SESSIONS"
+                    }
+                },
+                Some(Ok(list)) => rsx! {
+                    div { class: "space-y-3",
+                        for session in list.iter().cloned() {
+                            div {
+                                key: "{session.id}",
+                                class: "noir-card p-6 flex items-center justify-between gap-6",
+                                div { class: "space-y-1 min-w-0",
+                                    div { class: "flex items-center gap-3",
+                                        span { class: "font-bold tracking-wide", "{session.device_label()}" }
+                                        if session.current {
+                                            span { class: "text-[9px] font-black uppercase tracking-widest px-2 py-0.5 rounded",
+                                                style: "background: var(--accent-color); color: white;",
+                                                "This device"
+                                            }
+                                        }
+                                    }
+                                    p { class: "text-[10px] font-mono opacity-50 tracking-wider",
+                                        {
+                                            format!(
+                                                "IP {} · SIGNED IN {} · LAST USED {}",
+                                                session.ip_address.as_deref().unwrap_or("unknown"),
+                                                session.created_at.format(DATE_FMT),
+                                                session.last_used_at.format(DATE_FMT),
+                                            )
+                                        }
+                                    }
+                                }
+                                if !session.current {
+                                    button {
+                                        class: "px-4 py-2 rounded border text-[10px] font-black uppercase tracking-widest hover:bg-red-500/10 transition-all",
+                                        style: "color: var(--status-rejected); border-color: var(--status-rejected);",
+                                        onclick: move |_| {
+                                            let id = session.id.to_string();
+                                            async move {
+                                                match auth_service::revoke_session(&id).await {
+                                                    Ok(_) => sessions.restart(),
+                                                    Err(e) => error_msg.set(e),
+                                                }
+                                            }
+                                        },
+                                        "Revoke"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+                Some(Err(e)) => rsx! {
+                    p { class: "text-red-500 text-[10px] font-black uppercase tracking-widest", "{e}" }
+                },
+                None => rsx! {
+                    div { class: "text-center py-8 font-mono text-[10px] tracking-widest uppercase opacity-30 animate-pulse",
+                        "// SCANNING..."
+                    }
+                },
+            }
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::models::error::ApiError;
 use dioxus::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -5,16 +6,34 @@ use serde::{Deserialize, Serialize};
 struct LoginPayload {
     email: String,
     password: String,
+    use_cookies: bool,
 }
 
+#[derive(Serialize)]
+struct SecondStepPayload {
+    challenge_token: String,
+    code: String,
+    use_cookies: bool,
+}
+
+/// Either a cookie session, or a challenge when the account has 2FA enabled.
 #[derive(Deserialize)]
 struct LoginResponse {
-    token: String,
+    csrf_token: Option<String>,
+    expires_in: Option<i64>,
+    #[serde(default)]
+    two_factor_required: bool,
+    challenge_token: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ResendPayload {
+    email: String,
 }
 
 #[derive(Deserialize)]
-struct ErrorResponse {
-    error: String,
+struct MessageResponse {
+    message: String,
 }
 
 #[component]
@@ -22,51 +41,129 @@ pub fn AdminLogin() -> Element {
     let mut email = use_signal(|| "".to_string());
     let mut password = use_signal(|| "".to_string());
     let mut error_msg = use_signal(|| "".to_string());
+    let mut challenge = use_signal(|| None::<String>);
+    let mut code = use_signal(|| "".to_string());
+    let mut unverified = use_signal(|| false);
+    let mut info_msg = use_signal(|| "".to_string());
     let navigator = use_navigator();
 
     let onsubmit = move |evt: FormEvent| async move {
         evt.prevent_default();
         // Prevent default submission handled by Dioxus usually, but we want manual control
 
+        info_msg.set("".to_string());
         let client = reqwest::Client::new();
         let payload = LoginPayload {
             email: email(),
             password: password(),
+            use_cookies: true,
         };
 
         // TODO: Make base URL configurable
-        let res = client
+        let res = crate::services::application_service::with_credentials(client.post(format!(
+            "{}/auth/login",
+            crate::services::application_service::API_BASE_URL
+        )))
+        .json(&payload)
+        .send()
+        .await;
+
+        match res {
+            Ok(response) => {
+                if response.status().is_success() {
+                    if let Ok(data) = response.json::<LoginResponse>().await {
+                        if data.two_factor_required {
+                            error_msg.set("".to_string());
+                            challenge.set(data.challenge_token);
+                            return;
+                        }
+                        if let (Some(csrf_token), Some(expires_in)) =
+                            (data.csrf_token, data.expires_in)
+                        {
+                            crate::services::auth_service::store_session(&csrf_token, expires_in);
+                            navigator.push("/admin/dashboard"); // Assuming we will have a dashboard
+                        }
+                    }
+                } else {
+                    let err = ApiError::from_response(response, "Login failed").await;
+                    unverified.set(err.code == "email_not_verified");
+                    error_msg.set(err.to_string());
+                }
+            }
+            Err(e) => {
+                error_msg.set(format!("Network error: {}", e));
+            }
+        }
+    };
+
+    let on_resend = move |_| async move {
+        let res = reqwest::Client::new()
             .post(format!(
-                "{}/auth/login",
+                "{}/auth/resend-verification",
                 crate::services::application_service::API_BASE_URL
             ))
-            .json(&payload)
+            .json(&ResendPayload { email: email() })
             .send()
             .await;
 
+        match res {
+            Ok(response) if response.status().is_success() => {
+                if let Ok(data) = response.json::<MessageResponse>().await {
+                    error_msg.set("".to_string());
+                    unverified.set(false);
+                    info_msg.set(data.message);
+                }
+            }
+            Ok(response) => {
+                let err = ApiError::from_response(response, "Could not resend the link").await;
+                error_msg.set(err.to_string());
+            }
+            Err(e) => {
+                error_msg.set(format!("Network error: {}", e));
+            }
+        }
+    };
+
+    let on_code_submit = move |evt: FormEvent| async move {
+        evt.prevent_default();
+        let Some(challenge_token) = challenge() else {
+            return;
+        };
+
+        let payload = SecondStepPayload {
+            challenge_token,
+            code: code(),
+            use_cookies: true,
+        };
+        let res = crate::services::application_service::with_credentials(
+            reqwest::Client::new().post(format!(
+                "{}/auth/login/2fa",
+                crate::services::application_service::API_BASE_URL
+            )),
+        )
+        .json(&payload)
+        .send()
+        .await;
+
         match res {
             Ok(response) => {
                 if response.status().is_success() {
                     if let Ok(data) = response.json::<LoginResponse>().await {
-                        // TODO: Store token securely (e.g. specialized storage crate or JS interop)
-                        // For now just console log or basic storage if possible
-                        // Dioxus web doesn't have direct access to localStorage without web-sys or eval
-
-                        let token = data.token;
-                        // Using eval to set localStorage for now as it's the quick way in Dioxus
-                        let _ = document::eval(&format!(
-                            "localStorage.setItem('admin_token', '{}')",
-                            token
-                        ));
-
-                        navigator.push("/admin/dashboard"); // Assuming we will have a dashboard
+                        if let (Some(csrf_token), Some(expires_in)) =
+                            (data.csrf_token, data.expires_in)
+                        {
+                            crate::services::auth_service::store_session(&csrf_token, expires_in);
+                            navigator.push("/admin/dashboard");
+                        }
                     }
                 } else {
-                    if let Ok(err) = response.json::<ErrorResponse>().await {
-                        error_msg.set(err.error);
-                    } else {
-                        error_msg.set("Login failed".to_string());
+                    let err = ApiError::from_response(response, "Verification failed").await;
+                    // An expired challenge means starting over from the password step
+                    if err.code == "challenge_expired" {
+                        challenge.set(None);
                     }
+                    error_msg.set(err.to_string());
+                    code.set("".to_string());
                 }
             }
             Err(e) => {
@@ -88,76 +185,159 @@ pub fn AdminLogin() -> Element {
 
             div {
                 class: "mt-10 sm:mx-auto sm:w-full sm:max-w-sm",
-                form {
-                    class: "space-y-6 bg-white/5 p-8 rounded-lg border border-white/10 backdrop-blur-sm shadow-xl",
-                    onsubmit: onsubmit,
-
-                    div {
-                        label {
-                            r#for: "email",
-                            class: "block text-sm font-medium leading-6 text-gray-300",
-                            "Email address"
+                if challenge().is_some() {
+                    form {
+                        class: "space-y-6 bg-white/5 p-8 rounded-lg border border-white/10 backdrop-blur-sm shadow-xl",
+                        onsubmit: on_code_submit,
+
+                        div {
+                            label {
+                                r#for: "code",
+                                class: "block text-sm font-medium leading-6 text-gray-300",
+                                "Authentication code"
+                            }
+                            p {
+                                class: "mt-1 text-xs text-gray-500",
+                                "Enter the 6-digit code from your authenticator app, or a recovery code."
+                            }
+                            div {
+                                class: "mt-2",
+                                input {
+                                    id: "code",
+                                    name: "code",
+                                    r#type: "text",
+                                    inputmode: "numeric",
+                                    autocomplete: "one-time-code",
+                                    autofocus: true,
+                                    required: true,
+                                    class: "block w-full rounded-md border-0 py-1.5 bg-gray-900/50 text-white text-center tracking-[0.5em] shadow-sm ring-1 ring-inset ring-white/10 placeholder:text-gray-500 focus:ring-2 focus:ring-inset focus:ring-indigo-500 sm:text-sm sm:leading-6",
+                                    value: "{code}",
+                                    oninput: move |e| code.set(e.value())
+                                }
+                            }
+                        }
+
+                        if !error_msg().is_empty() {
+                            div {
+                                class: "text-red-400 text-sm text-center bg-red-900/20 py-2 rounded border border-red-500/20",
+                                "{error_msg}"
+                            }
+                        }
+
+                        div {
+                            button {
+                                r#type: "submit",
+                                class: "flex w-full justify-center rounded-md bg-indigo-500 px-3 py-1.5 text-sm font-semibold leading-6 text-white shadow-sm hover:bg-indigo-400 transition-colors duration-200",
+                                "Verify"
+                            }
                         }
+
                         div {
-                            class: "mt-2",
-                            input {
-                                id: "email",
-                                name: "email",
-                                r#type: "email",
-                                autocomplete: "email",
-                                required: true,
-                                class: "block w-full rounded-md border-0 py-1.5 bg-gray-900/50 text-white shadow-sm ring-1 ring-inset ring-white/10 placeholder:text-gray-500 focus:ring-2 focus:ring-inset focus:ring-indigo-500 sm:text-sm sm:leading-6",
-                                value: "{email}",
-                                oninput: move |e| email.set(e.value())
+                            class: "text-center text-sm",
+                            button {
+                                r#type: "button",
+                                class: "font-semibold text-indigo-400 hover:text-indigo-300",
+                                onclick: move |_| {
+                                    challenge.set(None);
+                                    code.set("".to_string());
+                                    error_msg.set("".to_string());
+                                },
+                                "Back to sign in"
                             }
                         }
                     }
+                } else {
+                    form {
+                        class: "space-y-6 bg-white/5 p-8 rounded-lg border border-white/10 backdrop-blur-sm shadow-xl",
+                        onsubmit: onsubmit,
 
-                    div {
                         div {
-                            class: "flex items-center justify-between",
                             label {
-                                r#for: "password",
+                                r#for: "email",
                                 class: "block text-sm font-medium leading-6 text-gray-300",
-                                "Password"
+                                "Email address"
+                            }
+                            div {
+                                class: "mt-2",
+                                input {
+                                    id: "email",
+                                    name: "email",
+                                    r#type: "email",
+                                    autocomplete: "email",
+                                    required: true,
+                                    class: "block w-full rounded-md border-0 py-1.5 bg-gray-900/50 text-white shadow-sm ring-1 ring-inset ring-white/10 placeholder:text-gray-500 focus:ring-2 focus:ring-inset focus:ring-indigo-500 sm:text-sm sm:leading-6",
+                                    value: "{email}",
+                                    oninput: move |e| email.set(e.value())
+                                }
                             }
                         }
+
                         div {
-                            class: "mt-2",
-                            input {
-                                id: "password",
-                                name: "password",
-                                r#type: "password",
-                                autocomplete: "current-password",
-                                required: true,
-                                class: "block w-full rounded-md border-0 py-1.5 bg-gray-900/50 text-white shadow-sm ring-1 ring-inset ring-white/10 placeholder:text-gray-500 focus:ring-2 focus:ring-inset focus:ring-indigo-500 sm:text-sm sm:leading-6",
-                                value: "{password}",
-                                oninput: move |e| password.set(e.value())
+                            div {
+                                class: "flex items-center justify-between",
+                                label {
+                                    r#for: "password",
+                                    class: "block text-sm font-medium leading-6 text-gray-300",
+                                    "Password"
+                                }
+                                Link {
+                                    to: "/admin/forgot",
+                                    class: "text-sm font-semibold text-indigo-400 hover:text-indigo-300",
+                                    "Forgot password?"
+                                }
+                            }
+                            div {
+                                class: "mt-2",
+                                input {
+                                    id: "password",
+                                    name: "password",
+                                    r#type: "password",
+                                    autocomplete: "current-password",
+                                    required: true,
+                                    class: "block w-full rounded-md border-0 py-1.5 bg-gray-900/50 text-white shadow-sm ring-1 ring-inset ring-white/10 placeholder:text-gray-500 focus:ring-2 focus:ring-inset focus:ring-indigo-500 sm:text-sm sm:leading-6",
+                                    value: "{password}",
+                                    oninput: move |e| password.set(e.value())
+                                }
                             }
                         }
-                    }
 
-                    if !error_msg().is_empty() {
-                        div {
-                            class: "text-red-400 text-sm text-center bg-red-900/20 py-2 rounded border border-red-500/20",
-                            "{error_msg}"
+                        if !error_msg().is_empty() {
+                            div {
+                                class: "text-red-400 text-sm text-center bg-red-900/20 py-2 rounded border border-red-500/20",
+                                "{error_msg}"
+                                if unverified() {
+                                    button {
+                                        r#type: "button",
+                                        class: "block mx-auto mt-1 font-semibold text-indigo-400 hover:text-indigo-300",
+                                        onclick: on_resend,
+                                        "Resend verification email"
+                                    }
+                                }
+                            }
                         }
-                    }
 
-                    div {
-                        button {
-                            r#type: "submit",
-                            class: "flex w-full justify-center rounded-md bg-indigo-500 px-3 py-1.5 text-sm font-semibold leading-6 text-white shadow-sm hover:bg-indigo-400 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-indigo-500 transition-colors duration-200",
-                            "Sign in"
+                        if !info_msg().is_empty() {
+                            div {
+                                class: "text-green-400 text-sm text-center bg-green-900/20 py-2 rounded border border-green-500/20",
+                                "{info_msg}"
+                            }
                         }
-                    }
 
-                    div {
-                        class: "text-center text-sm",
-                        Link {
-                            to: "/admin/register",
-                            class: "font-semibold text-indigo-400 hover:text-indigo-300",
-                            "Don't have an account? Register"
+                        div {
+                            button {
+                                r#type: "submit",
+                                class: "flex w-full justify-center rounded-md bg-indigo-500 px-3 py-1.5 text-sm font-semibold leading-6 text-white shadow-sm hover:bg-indigo-400 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-indigo-500 transition-colors duration-200",
+                                "Sign in"
+                            }
+                        }
+
+                        div {
+                            class: "text-center text-sm",
+                            Link {
+                                to: "/admin/register",
+                                class: "font-semibold text-indigo-400 hover:text-indigo-300",
+                                "Don't have an account? Register"
+                            }
                         }
                     }
                 }
@@ -1,3 +1,4 @@
+use crate::models::error::ApiError;
 use dioxus::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -5,11 +6,12 @@ use serde::{Deserialize, Serialize};
 struct RegisterPayload {
     email: String,
     password: String,
+    invite_code: Option<String>,
 }
 
 #[derive(Deserialize)]
-struct ErrorResponse {
-    error: String,
+struct RegistrationMode {
+    mode: String,
 }
 
 #[component]
@@ -19,6 +21,39 @@ pub fn AdminRegister() -> Element {
     let mut confirm_password = use_signal(|| "".to_string());
     let mut error_msg = use_signal(|| "".to_string());
     let mut success_msg = use_signal(|| "".to_string());
+    let mut invite_code = use_signal(|| "".to_string());
+
+    // "open", "invite_only" or "closed"
+    let mode = use_resource(move || async move {
+        reqwest::Client::new()
+            .get(format!(
+                "{}/auth/registration",
+                crate::services::application_service::API_BASE_URL
+            ))
+            .send()
+            .await
+            .ok()?
+            .json::<RegistrationMode>()
+            .await
+            .ok()
+            .map(|m| m.mode)
+    });
+    let needs_invite = move || matches!(&*mode.read(), Some(Some(m)) if m == "invite_only");
+    let is_closed = move || matches!(&*mode.read(), Some(Some(m)) if m == "closed");
+
+    // Invite links look like /admin/register?invite=XXXX-XXXX-XXXX-XXXX
+    use_effect(move || {
+        spawn(async move {
+            let mut eval = document::eval(
+                "dioxus.send(new URLSearchParams(window.location.search).get('invite'))",
+            );
+            if let Ok(val) = eval.recv::<serde_json::Value>().await {
+                if let Some(code) = val.as_str() {
+                    invite_code.set(code.to_string());
+                }
+            }
+        });
+    });
     // let navigator = use_navigator(); // Redirect could vary based on user preference
 
     let onsubmit = move |evt: FormEvent| async move {
@@ -35,6 +70,7 @@ pub fn AdminRegister() -> Element {
         let payload = RegisterPayload {
             email: email(),
             password: password(),
+            invite_code: Some(invite_code()).filter(|c| !c.trim().is_empty()),
         };
 
         // TODO: Make base URL configurable
@@ -56,11 +92,8 @@ pub fn AdminRegister() -> Element {
                     password.set("".to_string());
                     confirm_password.set("".to_string());
                 } else {
-                    if let Ok(err) = response.json::<ErrorResponse>().await {
-                        error_msg.set(err.error);
-                    } else {
-                        error_msg.set("Registration failed".to_string());
-                    }
+                    let err = ApiError::from_response(response, "Registration failed").await;
+                    error_msg.set(err.to_string());
                 }
             }
             Err(e) => {
@@ -80,103 +113,141 @@ pub fn AdminRegister() -> Element {
                 }
             }
 
-            div {
-                class: "mt-10 sm:mx-auto sm:w-full sm:max-w-sm",
-                form {
-                    class: "space-y-6 bg-white/5 p-8 rounded-lg border border-white/10 backdrop-blur-sm shadow-xl",
-                    onsubmit: onsubmit,
-
+            if is_closed() {
+                div {
+                    class: "mt-10 sm:mx-auto sm:w-full sm:max-w-sm text-center text-sm text-gray-400 bg-white/5 p-8 rounded-lg border border-white/10",
+                    "Registration is closed on this instance."
                     div {
-                        label {
-                            r#for: "email",
-                            class: "block text-sm font-medium leading-6 text-gray-300",
-                            "Email address"
-                        }
-                        div {
-                            class: "mt-2",
-                            input {
-                                id: "email",
-                                name: "email",
-                                r#type: "email",
-                                autocomplete: "email",
-                                required: true,
-                                class: "block w-full rounded-md border-0 py-1.5 bg-gray-900/50 text-white shadow-sm ring-1 ring-inset ring-white/10 placeholder:text-gray-500 focus:ring-2 focus:ring-inset focus:ring-indigo-500 sm:text-sm sm:leading-6",
-                                value: "{email}",
-                                oninput: move |e| email.set(e.value())
-                            }
+                        class: "mt-4",
+                        Link {
+                            to: "/admin/login",
+                            class: "font-semibold text-indigo-400 hover:text-indigo-300",
+                            "Back to sign in"
                         }
                     }
+                }
+            } else {
+                div {
+                    class: "mt-10 sm:mx-auto sm:w-full sm:max-w-sm",
+                    form {
+                        class: "space-y-6 bg-white/5 p-8 rounded-lg border border-white/10 backdrop-blur-sm shadow-xl",
+                        onsubmit: onsubmit,
 
-                    div {
-                        label {
-                            r#for: "password",
-                            class: "block text-sm font-medium leading-6 text-gray-300",
-                            "Password"
+                        if needs_invite() {
+                            div {
+                                label {
+                                    r#for: "invite_code",
+                                    class: "block text-sm font-medium leading-6 text-gray-300",
+                                    "Invite code"
+                                }
+                                div {
+                                    class: "mt-2",
+                                    input {
+                                        id: "invite_code",
+                                        name: "invite_code",
+                                        r#type: "text",
+                                        required: true,
+                                        placeholder: "XXXX-XXXX-XXXX-XXXX",
+                                        class: "block w-full rounded-md border-0 py-1.5 bg-gray-900/50 text-white font-mono uppercase shadow-sm ring-1 ring-inset ring-white/10 placeholder:text-gray-500 focus:ring-2 focus:ring-inset focus:ring-indigo-500 sm:text-sm sm:leading-6",
+                                        value: "{invite_code}",
+                                        oninput: move |e| invite_code.set(e.value())
+                                    }
+                                }
+                            }
                         }
+
                         div {
-                            class: "mt-2",
-                            input {
-                                id: "password",
-                                name: "password",
-                                r#type: "password",
-                                autocomplete: "new-password",
-                                required: true,
-                                class: "block w-full rounded-md border-0 py-1.5 bg-gray-900/50 text-white shadow-sm ring-1 ring-inset ring-white/10 placeholder:text-gray-500 focus:ring-2 focus:ring-inset focus:ring-indigo-500 sm:text-sm sm:leading-6",
-                                value: "{password}",
-                                oninput: move |e| password.set(e.value())
+                            label {
+                                r#for: "email",
+                                class: "block text-sm font-medium leading-6 text-gray-300",
+                                "Email address"
+                            }
+                            div {
+                                class: "mt-2",
+                                input {
+                                    id: "email",
+                                    name: "email",
+                                    r#type: "email",
+                                    autocomplete: "email",
+                                    required: true,
+                                    class: "block w-full rounded-md border-0 py-1.5 bg-gray-900/50 text-white shadow-sm ring-1 ring-inset ring-white/10 placeholder:text-gray-500 focus:ring-2 focus:ring-inset focus:ring-indigo-500 sm:text-sm sm:leading-6",
+                                    value: "{email}",
+                                    oninput: move |e| email.set(e.value())
+                                }
                             }
                         }
-                    }
 
-                    div {
-                        label {
-                            r#for: "confirm_password",
-                            class: "block text-sm font-medium leading-6 text-gray-300",
-                            "Confirm Password"
-                        }
                         div {
-                            class: "mt-2",
-                            input {
-                                id: "confirm_password",
-                                name: "confirm_password",
-                                r#type: "password",
-                                autocomplete: "new-password",
-                                required: true,
-                                class: "block w-full rounded-md border-0 py-1.5 bg-gray-900/50 text-white shadow-sm ring-1 ring-inset ring-white/10 placeholder:text-gray-500 focus:ring-2 focus:ring-inset focus:ring-indigo-500 sm:text-sm sm:leading-6",
-                                value: "{confirm_password}",
-                                oninput: move |e| confirm_password.set(e.value())
+                            label {
+                                r#for: "password",
+                                class: "block text-sm font-medium leading-6 text-gray-300",
+                                "Password"
+                            }
+                            div {
+                                class: "mt-2",
+                                input {
+                                    id: "password",
+                                    name: "password",
+                                    r#type: "password",
+                                    autocomplete: "new-password",
+                                    required: true,
+                                    class: "block w-full rounded-md border-0 py-1.5 bg-gray-900/50 text-white shadow-sm ring-1 ring-inset ring-white/10 placeholder:text-gray-500 focus:ring-2 focus:ring-inset focus:ring-indigo-500 sm:text-sm sm:leading-6",
+                                    value: "{password}",
+                                    oninput: move |e| password.set(e.value())
+                                }
                             }
                         }
-                    }
 
-                    if !error_msg().is_empty() {
                         div {
-                            class: "text-red-400 text-sm text-center bg-red-900/20 py-2 rounded border border-red-500/20",
-                            "{error_msg}"
+                            label {
+                                r#for: "confirm_password",
+                                class: "block text-sm font-medium leading-6 text-gray-300",
+                                "Confirm Password"
+                            }
+                            div {
+                                class: "mt-2",
+                                input {
+                                    id: "confirm_password",
+                                    name: "confirm_password",
+                                    r#type: "password",
+                                    autocomplete: "new-password",
+                                    required: true,
+                                    class: "block w-full rounded-md border-0 py-1.5 bg-gray-900/50 text-white shadow-sm ring-1 ring-inset ring-white/10 placeholder:text-gray-500 focus:ring-2 focus:ring-inset focus:ring-indigo-500 sm:text-sm sm:leading-6",
+                                    value: "{confirm_password}",
+                                    oninput: move |e| confirm_password.set(e.value())
+                                }
+                            }
                         }
-                    }
 
-                    if !success_msg().is_empty() {
-                         div {
-                            class: "text-emerald-400 text-sm text-center bg-emerald-900/20 py-2 rounded border border-emerald-500/20",
-                            "{success_msg}"
+                        if !error_msg().is_empty() {
+                            div {
+                                class: "text-red-400 text-sm text-center bg-red-900/20 py-2 rounded border border-red-500/20",
+                                "{error_msg}"
+                            }
                         }
-                    }
 
-                    div {
-                        button {
-                            r#type: "submit",
-                            class: "flex w-full justify-center rounded-md bg-indigo-500 px-3 py-1.5 text-sm font-semibold leading-6 text-white shadow-sm hover:bg-indigo-400 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-indigo-500 transition-colors duration-200",
-                            "Register"
+                        if !success_msg().is_empty() {
+                             div {
+                                class: "text-emerald-400 text-sm text-center bg-emerald-900/20 py-2 rounded border border-emerald-500/20",
+                                "{success_msg}"
+                            }
                         }
-                    }
 
-                    div {
-                        class: "text-center text-sm",
-                        Link {
-                            to: "/admin/login",
-                            class: "font-semibold text-indigo-400 hover:text-indigo-300",
-                            "Already have an account? Sign in"
+                        div {
+                            button {
+                                r#type: "submit",
+                                class: "flex w-full justify-center rounded-md bg-indigo-500 px-3 py-1.5 text-sm font-semibold leading-6 text-white shadow-sm hover:bg-indigo-400 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-indigo-500 transition-colors duration-200",
+                                "Register"
+                            }
+                        }
+
+                        div {
+                            class: "text-center text-sm",
+                            Link {
+                                to: "/admin/login",
+                                class: "font-semibold text-indigo-400 hover:text-indigo-300",
+                                "Already have an account? Sign in"
+                            }
                         }
                     }
                 }
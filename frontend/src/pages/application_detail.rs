@@ -1,11 +1,32 @@
-use crate::models::application::CreateComment;
+use crate::models::application::{Comment, CreateComment};
+use crate::models::event::AppEvent;
 use crate::services::application_service::{
-    create_comment, get_comments, get_public_application_detail, BASE_URL,
+    asset_url, create_comment, get_comments, get_public_application_detail, BASE_URL,
 };
+use crate::services::sse_service::use_comment_events;
 use dioxus::prelude::*;
+use uuid::Uuid;
 
 const DATE_FMT: &str = "%Y-%m-%d %H:%M";
 
+/// Groups the flat comment list into threads: top-level comments newest first,
+/// each followed by its replies oldest first.
+fn threads(comments: &[Comment]) -> Vec<(Comment, Vec<Comment>)> {
+    comments
+        .iter()
+        .filter(|c| c.parent_id.is_none())
+        .map(|root| {
+            let mut replies: Vec<Comment> = comments
+                .iter()
+                .filter(|c| c.parent_id == Some(root.id))
+                .cloned()
+                .collect();
+            replies.sort_by_key(|c| c.created_at);
+            (root.clone(), replies)
+        })
+        .collect()
+}
+
 #[component]
 pub fn ApplicationDetail(id: String) -> Element {
     let application_resource = use_resource(use_reactive(&id, |id| async move {
@@ -21,6 +42,27 @@ pub fn ApplicationDetail(id: String) -> Element {
     let mut new_comment_content = use_signal(|| "".to_string());
     let mut visitor_name = use_signal(|| "".to_string());
     let mut submitting = use_signal(|| false);
+    // Set when the last comment sent was held for moderation
+    let mut held = use_signal(|| false);
+    // The thread the form answers, if any
+    let mut replying_to = use_signal(|| None::<(Uuid, String)>);
+    let mut owner_replied = use_signal(|| false);
+    let opened_at = use_hook(chrono::Utc::now);
+
+    let comment_events = use_comment_events();
+    use_effect(use_reactive(&id, move |id| {
+        let on_page = |application_id: Uuid| application_id.to_string() == id;
+        match comment_events() {
+            Some(AppEvent::CommentReplied { application_id, .. }) if on_page(application_id) => {
+                owner_replied.set(true);
+                comments_resource.restart();
+            }
+            Some(AppEvent::CommentCreated { application_id, .. }) if on_page(application_id) => {
+                comments_resource.restart();
+            }
+            _ => {}
+        }
+    }));
 
     rsx! {
         div { class: "max-w-7xl mx-auto px-4 py-20 min-h-screen scanline",
@@ -54,7 +96,7 @@ pub fn ApplicationDetail(id: String) -> Element {
                                         div { class: "flex flex-col md:flex-row items-start gap-6 w-full",
                                             if let Some(logo) = &app.logo_url {
                                                 img {
-                                                    src: "{logo}",
+                                                    src: asset_url(logo),
                                                     class: "w-16 h-16 md:w-24 md:h-24 rounded-lg bg-white/5 object-contain border border-white/10 p-2 shadow-2xl",
                                                 }
                                             }
@@ -94,6 +136,11 @@ pub fn ApplicationDetail(id: String) -> Element {
                                         div {
                                             div { class: "text-[10px] uppercase tracking-[0.3em] opacity-40 mb-2", "Init_Log" }
                                             div { class: "text-xl font-mono opacity-80", "{date_str}" }
+                                            if app.source.as_deref() == Some("contact_form") {
+                                                div { class: "text-[10px] font-mono uppercase tracking-[0.2em] mt-2 text-accent-color opacity-70",
+                                                    "Came in via contact form"
+                                                }
+                                            }
                                         }
                                     }
 
@@ -123,6 +170,17 @@ pub fn ApplicationDetail(id: String) -> Element {
                                 if let Some(cv_path) = &app.cv_path {
                                     div { class: "glass p-8 rounded-sm",
                                         h3 { class: "text-[10px] uppercase tracking-[0.5em] font-black opacity-30 mb-6", "RESOURCES" }
+                                        if let Some(thumb) = &app.cv_thumbnail_url {
+                                            a {
+                                                href: "{BASE_URL}{cv_path}",
+                                                target: "_blank",
+                                                class: "block mb-6 border border-white/10 bg-white/5 p-2 hover:border-accent-color transition-colors",
+                                                img {
+                                                    src: "{BASE_URL}{thumb}",
+                                                    class: "w-full object-contain opacity-80",
+                                                }
+                                            }
+                                        }
                                         a {
                                             href: "{BASE_URL}{cv_path}",
                                             target: "_blank",
@@ -135,63 +193,93 @@ pub fn ApplicationDetail(id: String) -> Element {
                                 // Comms Channel
                                 div { class: "glass p-8 rounded-sm space-y-8",
                                     h3 { class: "text-[10px] uppercase tracking-[0.5em] font-black opacity-30", "COMM_CHANNEL" }
+                                    if owner_replied() {
+                                        p { class: "text-[10px] font-mono tracking-widest text-accent-color", "// INCOMING: OWNER_REPLY_RECEIVED" }
+                                    }
 
                                     // New Message
-                                    div { class: "space-y-4",
-                                        input {
-                                            class: "w-full bg-white/5 border border-white/10 p-3 text-sm focus:outline-none focus:border-accent-color transition-colors font-mono",
-                                            placeholder: "ID_TAG (OPTIONAL)",
-                                            value: "{visitor_name}",
-                                            oninput: move |e| visitor_name.set(e.value())
-                                        }
-                                        textarea {
-                                            class: "w-full bg-white/5 border border-white/10 p-3 text-sm focus:outline-none focus:border-accent-color h-32 transition-colors font-mono",
-                                            placeholder: "TRANSMIT_SIG...",
-                                            value: "{new_comment_content}",
-                                            oninput: move |e| new_comment_content.set(e.value())
-                                        }
-                                        button {
-                                            class: "noir-btn w-full py-4 text-xs tracking-widest",
-                                            disabled: "{submitting}",
-                                            onclick: {
-                                                let app_id = app.id.to_string();
-                                                move |_| {
-                                                    submitting.set(true);
-                                                    let content = new_comment_content();
-                                                    let name = if visitor_name().trim().is_empty() { "Anonymous".to_string() } else { visitor_name() };
-                                                    let app_id = app_id.clone();
-
-                                                    spawn(async move {
-                                                        if !content.trim().is_empty() {
-                                                                let _ = create_comment(&app_id, CreateComment {
-                                                                visitor_name: name,
-                                                                content: content,
-                                                            }).await;
-                                                            new_comment_content.set("".to_string());
-                                                            comments_resource.restart();
-                                                        }
-                                                        submitting.set(false);
-                                                    });
+                                    if app.comments_enabled {
+                                        div { class: "space-y-4",
+                                            if let Some((_, name)) = replying_to() {
+                                                div { class: "flex justify-between items-center text-[10px] font-mono tracking-widest text-accent-color",
+                                                    span { "RE: {name.to_uppercase()}" }
+                                                    button {
+                                                        class: "opacity-50 hover:opacity-100",
+                                                        onclick: move |_| replying_to.set(None),
+                                                        "[CANCEL]"
+                                                    }
                                                 }
-                                            },
-                                            if submitting() { "TRANSMITTING..." } else { "EXEC_SEND" }
+                                            }
+                                            input {
+                                                class: "w-full bg-white/5 border border-white/10 p-3 text-sm focus:outline-none focus:border-accent-color transition-colors font-mono",
+                                                placeholder: "ID_TAG (OPTIONAL)",
+                                                value: "{visitor_name}",
+                                                oninput: move |e| visitor_name.set(e.value())
+                                            }
+                                            textarea {
+                                                class: "w-full bg-white/5 border border-white/10 p-3 text-sm focus:outline-none focus:border-accent-color h-32 transition-colors font-mono",
+                                                placeholder: "TRANSMIT_SIG...",
+                                                value: "{new_comment_content}",
+                                                oninput: move |e| new_comment_content.set(e.value())
+                                            }
+                                            button {
+                                                class: "noir-btn w-full py-4 text-xs tracking-widest",
+                                                disabled: "{submitting}",
+                                                onclick: {
+                                                    let app_id = app.id.to_string();
+                                                    move |_| {
+                                                        submitting.set(true);
+                                                        let content = new_comment_content();
+                                                        let name = if visitor_name().trim().is_empty() { "Anonymous".to_string() } else { visitor_name() };
+                                                        let app_id = app_id.clone();
+
+                                                        spawn(async move {
+                                                            if !content.trim().is_empty() {
+                                                                    let result = create_comment(&app_id, CreateComment {
+                                                                    visitor_name: name,
+                                                                    content: content,
+                                                                    parent_id: replying_to().map(|(id, _)| id),
+                                                                    fill_time_ms: (chrono::Utc::now() - opened_at).num_milliseconds().try_into().ok(),
+                                                                }).await;
+                                                                held.set(matches!(&result, Ok(c) if c.status == "pending"));
+                                                                new_comment_content.set("".to_string());
+                                                                replying_to.set(None);
+                                                                comments_resource.restart();
+                                                            }
+                                                            submitting.set(false);
+                                                        });
+                                                    }
+                                                },
+                                                if submitting() { "TRANSMITTING..." } else { "EXEC_SEND" }
+                                            }
+                                        }
+                                        if held() {
+                                            p { class: "text-[10px] font-mono tracking-widest text-accent-color", "// SIGNAL_HELD: AWAITING_MODERATION" }
                                         }
+                                    } else {
+                                        p { class: "text-center opacity-30 font-mono text-[10px] tracking-widest", "--- CHANNEL_CLOSED ---" }
                                     }
 
                                     // Message Log
                                     match &*comments_resource.read() {
                                         Some(Ok(comments)) => rsx! {
                                             div { class: "space-y-4 max-h-[400px] overflow-y-auto pr-2 custom-scrollbar",
-                                                for comment in comments {
-                                                    {
-                                                        let time_str = comment.created_at.format("%H:%M").to_string();
-                                                        rsx! {
-                                                            div { class: "p-4 border-l-2 border-accent-color/30 bg-white/5",
-                                                                div { class: "flex justify-between items-center mb-1",
-                                                                    span { class: "text-[10px] font-black tracking-widest text-accent-color", "{comment.visitor_name.to_uppercase()}" }
-                                                                    span { class: "text-[8px] font-mono opacity-30", "{time_str}" }
-                                                                }
-                                                                p { class: "text-sm opacity-80 leading-relaxed", "{comment.content}" }
+                                                for (comment, replies) in threads(comments) {
+                                                    div { key: "{comment.id}", class: "space-y-2",
+                                                        CommentEntry { comment: comment.clone() }
+                                                        for reply in replies {
+                                                            div { key: "{reply.id}", class: "ml-6",
+                                                                CommentEntry { comment: reply }
+                                                            }
+                                                        }
+                                                        if app.comments_enabled {
+                                                            button {
+                                                                class: "ml-4 text-[9px] font-mono tracking-widest opacity-40 hover:opacity-100 hover:text-accent-color transition-all",
+                                                                onclick: {
+                                                                    let target = (comment.id, comment.visitor_name.clone());
+                                                                    move |_| replying_to.set(Some(target.clone()))
+                                                                },
+                                                                ">> REPLY"
                                                             }
                                                         }
                                                     }
@@ -224,3 +312,28 @@ pub fn ApplicationDetail(id: String) -> Element {
         }
     }
 }
+
+#[component]
+fn CommentEntry(comment: Comment) -> Element {
+    let time_str = comment.created_at.format("%H:%M").to_string();
+    rsx! {
+        div {
+            class: "p-4 border-l-2 bg-white/5",
+            class: if comment.is_owner { "border-accent-color" } else { "border-accent-color/30" },
+            div { class: "flex justify-between items-center mb-1",
+                div { class: "flex items-center gap-2",
+                    span { class: "text-[10px] font-black tracking-widest text-accent-color", "{comment.visitor_name.to_uppercase()}" }
+                    if comment.is_owner {
+                        span {
+                            class: "px-1.5 py-0.5 border border-accent-color text-[8px] font-black tracking-widest text-accent-color",
+                            title: "Verified reply from the owner of this dossier",
+                            "✓ OWNER"
+                        }
+                    }
+                }
+                span { class: "text-[8px] font-mono opacity-30", "{time_str}" }
+            }
+            p { class: "text-sm opacity-80 leading-relaxed", "{comment.content}" }
+        }
+    }
+}
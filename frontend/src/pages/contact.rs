@@ -7,6 +7,8 @@ struct ContactPayload {
     email: String,
     link: Option<String>,
     message: String,
+    /// Lets the server's spam filter tell typed messages from scripted ones
+    fill_time_ms: Option<u64>,
     bot_field: Option<String>,
 }
 
@@ -17,6 +19,7 @@ pub fn Contact() -> Element {
     let mut link = use_signal(|| String::new());
     let mut message = use_signal(|| String::new());
     let mut bot_field = use_signal(|| String::new());
+    let opened_at = use_hook(chrono::Utc::now);
     let mut is_submitting = use_signal(|| false);
     let mut success_message = use_signal(|| Option::<String>::None);
     let mut error_message = use_signal(|| Option::<String>::None);
@@ -40,6 +43,10 @@ pub fn Contact() -> Element {
                 Some(link())
             },
             message: message(),
+            fill_time_ms: (chrono::Utc::now() - opened_at)
+                .num_milliseconds()
+                .try_into()
+                .ok(),
             bot_field: if bot_field().is_empty() {
                 None
             } else {
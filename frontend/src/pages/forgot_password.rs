@@ -0,0 +1,125 @@
+use crate::models::error::ApiError;
+use dioxus::prelude::*;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct ForgotPayload {
+    email: String,
+}
+
+#[component]
+pub fn ForgotPassword() -> Element {
+    let mut email = use_signal(|| "".to_string());
+    let mut message = use_signal(|| "".to_string());
+    let mut is_error = use_signal(|| false);
+
+    let onsubmit = move |evt: FormEvent| async move {
+        evt.prevent_default();
+        let client = reqwest::Client::new();
+        let payload = ForgotPayload { email: email() };
+
+        match client
+            .post(format!(
+                "{}/auth/forgot",
+                crate::services::application_service::API_BASE_URL
+            ))
+            .json(&payload)
+            .send()
+            .await
+        {
+            Ok(resp) => {
+                if resp.status().is_success() {
+                    is_error.set(false);
+                    message.set(
+                        "If the account exists, a reset link is on its way. Check your inbox."
+                            .to_string(),
+                    );
+                } else {
+                    is_error.set(true);
+                    let err = ApiError::from_response(resp, "Request failed").await;
+                    message.set(err.to_string());
+                }
+            }
+            Err(e) => {
+                is_error.set(true);
+                message.set(format!("Network error: {}", e));
+            }
+        }
+    };
+
+    let msg_color_class = if is_error() {
+        "text-red-400 bg-red-900/20 border-red-500/20"
+    } else {
+        "text-green-400 bg-green-900/20 border-green-500/20"
+    };
+
+    rsx! {
+        div {
+            class: "flex min-h-full flex-col justify-center px-6 py-12 lg:px-8 bg-[#0f1116] text-white",
+            div {
+                class: "sm:mx-auto sm:w-full sm:max-w-sm",
+                h2 {
+                    class: "mt-10 text-center text-2xl font-bold leading-9 tracking-tight text-white",
+                    "Forgot your password?"
+                }
+                p {
+                    class: "mt-2 text-center text-sm text-gray-400",
+                    "We'll email you a link to choose a new one."
+                }
+            }
+
+            div {
+                class: "mt-10 sm:mx-auto sm:w-full sm:max-w-sm",
+                form {
+                    class: "space-y-6 bg-white/5 p-8 rounded-lg border border-white/10 backdrop-blur-sm shadow-xl",
+                    onsubmit: onsubmit,
+
+                    div {
+                        label {
+                            r#for: "email",
+                            class: "block text-sm font-medium leading-6 text-gray-300",
+                            "Email address"
+                        }
+                        div {
+                            class: "mt-2",
+                            input {
+                                id: "email",
+                                name: "email",
+                                r#type: "email",
+                                autocomplete: "email",
+                                required: true,
+                                class: "block w-full rounded-md border-0 py-1.5 bg-gray-900/50 text-white shadow-sm ring-1 ring-inset ring-white/10 placeholder:text-gray-500 focus:ring-2 focus:ring-inset focus:ring-indigo-500 sm:text-sm sm:leading-6",
+                                value: "{email}",
+                                oninput: move |e| email.set(e.value())
+                            }
+                        }
+                    }
+
+                    if !message().is_empty() {
+                         div {
+                            class: "{msg_color_class} text-sm text-center py-2 rounded border",
+                            "{message}"
+                        }
+                    }
+
+                    div {
+                        button {
+                            r#type: "submit",
+                            class: "flex w-full justify-center rounded-md bg-indigo-500 px-3 py-1.5 text-sm font-semibold leading-6 text-white shadow-sm hover:bg-indigo-400",
+                            "Send reset link"
+                        }
+                    }
+
+                    div {
+                        class: "text-center text-sm",
+                        Link {
+                            to: "/admin/login",
+                            class: "font-semibold text-indigo-400 hover:text-indigo-300",
+                            "Back to sign in"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -3,4 +3,6 @@ pub mod admin_login;
 pub mod admin_register;
 pub mod application_detail;
 pub mod contact;
+pub mod forgot_password;
+pub mod reset_password;
 pub mod verify_email; // sub-folder
@@ -0,0 +1,184 @@
+use crate::models::error::ApiError;
+use dioxus::prelude::*;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct ResetPayload {
+    token: String,
+    password: String,
+}
+
+#[component]
+pub fn ResetPassword() -> Element {
+    let mut token_input = use_signal(|| "".to_string());
+    let mut password = use_signal(|| "".to_string());
+    let mut confirm_password = use_signal(|| "".to_string());
+    let mut message = use_signal(|| "".to_string());
+    let mut is_error = use_signal(|| false);
+
+    // The emailed link carries ?token=..., prefill it so the user only picks a password
+    use_effect(move || {
+        spawn(async move {
+            let mut eval = document::eval(
+                "dioxus.send(new URLSearchParams(window.location.search).get('token'))",
+            );
+            if let Ok(val) = eval.recv::<serde_json::Value>().await {
+                if let Some(token) = val.as_str() {
+                    token_input.set(token.to_string());
+                }
+            }
+        });
+    });
+
+    let onsubmit = move |evt: FormEvent| async move {
+        evt.prevent_default();
+
+        if password() != confirm_password() {
+            is_error.set(true);
+            message.set("Passwords do not match".to_string());
+            return;
+        }
+
+        let client = reqwest::Client::new();
+        let payload = ResetPayload {
+            token: token_input(),
+            password: password(),
+        };
+
+        match client
+            .post(format!(
+                "{}/auth/reset",
+                crate::services::application_service::API_BASE_URL
+            ))
+            .json(&payload)
+            .send()
+            .await
+        {
+            Ok(resp) => {
+                if resp.status().is_success() {
+                    message.set("Password updated! Redirecting to login...".to_string());
+                    is_error.set(false);
+                    // Every session was signed out, drop whatever this browser still holds
+                    crate::services::auth_service::clear_session().await;
+                    let _ = document::eval(
+                        "setTimeout(() => { window.location.href = '/admin/login' }, 2000)",
+                    )
+                    .await;
+                } else {
+                    is_error.set(true);
+                    let err = ApiError::from_response(resp, "Reset failed").await;
+                    message.set(err.to_string());
+                }
+            }
+            Err(e) => {
+                is_error.set(true);
+                message.set(format!("Network error: {}", e));
+            }
+        }
+    };
+
+    let msg_color_class = if is_error() {
+        "text-red-400 bg-red-900/20 border-red-500/20"
+    } else {
+        "text-green-400 bg-green-900/20 border-green-500/20"
+    };
+
+    rsx! {
+        div {
+            class: "flex min-h-full flex-col justify-center px-6 py-12 lg:px-8 bg-[#0f1116] text-white",
+            div {
+                class: "sm:mx-auto sm:w-full sm:max-w-sm",
+                h2 {
+                    class: "mt-10 text-center text-2xl font-bold leading-9 tracking-tight text-white",
+                    "Choose a new password"
+                }
+                p {
+                    class: "mt-2 text-center text-sm text-gray-400",
+                    "All existing sessions will be signed out."
+                }
+            }
+
+            div {
+                class: "mt-10 sm:mx-auto sm:w-full sm:max-w-sm",
+                form {
+                    class: "space-y-6 bg-white/5 p-8 rounded-lg border border-white/10 backdrop-blur-sm shadow-xl",
+                    onsubmit: onsubmit,
+
+                    div {
+                        label {
+                            class: "block text-sm font-medium leading-6 text-gray-300",
+                            "Reset Token"
+                        }
+                        div {
+                            class: "mt-2",
+                            input {
+                                r#type: "text",
+                                required: true,
+                                class: "block w-full rounded-md border-0 py-1.5 bg-gray-900/50 text-white shadow-sm ring-1 ring-inset ring-white/10 placeholder:text-gray-500 focus:ring-2 focus:ring-inset focus:ring-indigo-500 sm:text-sm sm:leading-6",
+                                value: "{token_input}",
+                                oninput: move |e| token_input.set(e.value())
+                            }
+                        }
+                    }
+
+                    div {
+                        label {
+                            r#for: "password",
+                            class: "block text-sm font-medium leading-6 text-gray-300",
+                            "New Password"
+                        }
+                        div {
+                            class: "mt-2",
+                            input {
+                                id: "password",
+                                name: "password",
+                                r#type: "password",
+                                autocomplete: "new-password",
+                                required: true,
+                                class: "block w-full rounded-md border-0 py-1.5 bg-gray-900/50 text-white shadow-sm ring-1 ring-inset ring-white/10 placeholder:text-gray-500 focus:ring-2 focus:ring-inset focus:ring-indigo-500 sm:text-sm sm:leading-6",
+                                value: "{password}",
+                                oninput: move |e| password.set(e.value())
+                            }
+                        }
+                    }
+
+                    div {
+                        label {
+                            r#for: "confirm_password",
+                            class: "block text-sm font-medium leading-6 text-gray-300",
+                            "Confirm Password"
+                        }
+                        div {
+                            class: "mt-2",
+                            input {
+                                id: "confirm_password",
+                                name: "confirm_password",
+                                r#type: "password",
+                                autocomplete: "new-password",
+                                required: true,
+                                class: "block w-full rounded-md border-0 py-1.5 bg-gray-900/50 text-white shadow-sm ring-1 ring-inset ring-white/10 placeholder:text-gray-500 focus:ring-2 focus:ring-inset focus:ring-indigo-500 sm:text-sm sm:leading-6",
+                                value: "{confirm_password}",
+                                oninput: move |e| confirm_password.set(e.value())
+                            }
+                        }
+                    }
+
+                    if !message().is_empty() {
+                         div {
+                            class: "{msg_color_class} text-sm text-center py-2 rounded border",
+                            "{message}"
+                        }
+                    }
+
+                    div {
+                        button {
+                            r#type: "submit",
+                            class: "flex w-full justify-center rounded-md bg-indigo-500 px-3 py-1.5 text-sm font-semibold leading-6 text-white shadow-sm hover:bg-indigo-400",
+                            "Reset password"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
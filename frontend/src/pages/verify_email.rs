@@ -1,16 +1,12 @@
+use crate::models::error::ApiError;
 use dioxus::prelude::*;
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
 
 #[derive(Serialize)]
 struct VerifyPayload {
     token: String,
 }
 
-#[derive(Deserialize)]
-struct ErrorResponse {
-    error: String,
-}
-
 #[component]
 pub fn VerifyEmail() -> Element {
     let mut token_input = use_signal(|| "".to_string());
@@ -49,11 +45,8 @@ pub fn VerifyEmail() -> Element {
                     .await;
                 } else {
                     is_error.set(true);
-                    if let Ok(err) = resp.json::<ErrorResponse>().await {
-                        message.set(err.error);
-                    } else {
-                        message.set("Verification failed".to_string());
-                    }
+                    let err = ApiError::from_response(resp, "Verification failed").await;
+                    message.set(err.to_string());
                 }
             }
             Err(e) => {
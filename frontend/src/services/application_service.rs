@@ -1,45 +1,105 @@
 use crate::models::application::{Application, CreateApplicationPayload, UpdateApplicationPayload};
+use crate::models::error::ApiError;
 use dioxus::prelude::*;
-use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
+use reqwest::header::CONTENT_TYPE;
+use reqwest::Method;
 
 pub const BASE_URL: &str = if let Some(url) = option_env!("API_URL") {
     if url.is_empty() {
-        "http://127.0.0.1:3000"
+        "http://localhost:3000"
     } else {
         url
     }
 } else {
-    "http://127.0.0.1:3000"
+    "http://localhost:3000"
 };
 
 pub const API_BASE_URL: &str = const_format::concatcp!(BASE_URL, "/api");
 
-pub async fn get_token() -> Option<String> {
-    let mut eval = document::eval(
-        "
-        let token = localStorage.getItem('admin_token');
-        dioxus.send(token);
-    ",
-    );
+/// Mirrored files come back as `/uploads/...` paths on the API host; remote URLs pass through.
+pub fn asset_url(path: &str) -> String {
+    if path.starts_with('/') {
+        format!("{}{}", BASE_URL, path)
+    } else {
+        path.to_string()
+    }
+}
 
-    match eval.recv::<serde_json::Value>().await {
-        Ok(val) => {
-            if let Some(token) = val.as_str() {
-                return Some(token.to_string());
+/// Header carrying the CSRF token that pairs with the HttpOnly session cookie
+pub const CSRF_HEADER: &str = "X-CSRF-Token";
+
+/// Makes sure the cookie session is fresh, refreshing it shortly before the access
+/// cookie expires. Returns the CSRF token, or `None` when signed out. The tokens
+/// themselves live in HttpOnly cookies that scripts cannot read. Concurrent callers
+/// share one refresh request so the rotated refresh cookie is only presented once.
+pub async fn ensure_session() -> Option<String> {
+    let script = r#"
+        const expiry = () => Number(localStorage.getItem('admin_session_expires') || 0);
+        let csrf = localStorage.getItem('admin_csrf');
+        if (csrf && expiry() - 30000 < Date.now()) {
+            if (!window.__adminRefresh) {
+                window.__adminRefresh = (async () => {
+                    const res = await fetch('__API__/auth/refresh', {
+                        method: 'POST',
+                        credentials: 'include',
+                    }).catch(() => null);
+                    if (res && res.ok) {
+                        const data = await res.json();
+                        localStorage.setItem('admin_csrf', data.csrf_token);
+                        localStorage.setItem('admin_session_expires', String(Date.now() + data.expires_in * 1000));
+                        return data.csrf_token;
+                    }
+                    // Another tab may have refreshed first
+                    if (expiry() > Date.now()) {
+                        return localStorage.getItem('admin_csrf');
+                    }
+                    if (res) {
+                        localStorage.removeItem('admin_csrf');
+                        localStorage.removeItem('admin_session_expires');
+                    }
+                    return null;
+                })().finally(() => { window.__adminRefresh = null; });
             }
+            csrf = await window.__adminRefresh;
         }
-        Err(_) => {}
+        dioxus.send(csrf);
+    "#
+    .replace("__API__", API_BASE_URL);
+
+    let mut eval = document::eval(&script);
+
+    match eval.recv::<serde_json::Value>().await {
+        Ok(val) => val.as_str().map(|csrf| csrf.to_string()),
+        Err(_) => None,
     }
-    None
 }
 
-pub async fn list_applications() -> Result<Vec<Application>, String> {
-    let token = get_token().await.ok_or("No token found")?;
-    let client = reqwest::Client::new();
+/// Starts an authenticated API request: the browser attaches the session cookie
+/// and the CSRF header proves the request comes from this app.
+pub async fn authed(method: Method, url: String) -> Result<reqwest::RequestBuilder, String> {
+    let csrf = ensure_session().await.ok_or("Not authenticated")?;
+    Ok(with_credentials(
+        reqwest::Client::new()
+            .request(method, url)
+            .header(CSRF_HEADER, csrf),
+    ))
+}
 
-    let res = client
-        .get(format!("{}/applications", API_BASE_URL))
-        .header(AUTHORIZATION, format!("Bearer {}", token))
+/// Lets the browser store and send the session cookies on cross-origin API calls.
+pub fn with_credentials(builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    #[cfg(target_arch = "wasm32")]
+    let builder = builder.fetch_credentials_include();
+    builder
+}
+
+/// The server's problem detail for a failed response, for showing to the user.
+pub async fn error_message(res: reqwest::Response, fallback: &str) -> String {
+    ApiError::from_response(res, fallback).await.to_string()
+}
+
+pub async fn list_applications() -> Result<Vec<Application>, String> {
+    let res = authed(Method::GET, format!("{}/applications", API_BASE_URL))
+        .await?
         .send()
         .await
         .map_err(|e| e.to_string())?;
@@ -49,17 +109,13 @@ pub async fn list_applications() -> Result<Vec<Application>, String> {
             .await
             .map_err(|e| e.to_string())
     } else {
-        Err(format!("Request failed: {}", res.status()))
+        Err(error_message(res, "Request failed").await)
     }
 }
 
 pub async fn create_application(payload: CreateApplicationPayload) -> Result<Application, String> {
-    let token = get_token().await.ok_or("No token found")?;
-    let client = reqwest::Client::new();
-
-    let res = client
-        .post(format!("{}/applications", API_BASE_URL))
-        .header(AUTHORIZATION, format!("Bearer {}", token))
+    let res = authed(Method::POST, format!("{}/applications", API_BASE_URL))
+        .await?
         .header(CONTENT_TYPE, "application/json")
         .json(&payload)
         .send()
@@ -69,9 +125,7 @@ pub async fn create_application(payload: CreateApplicationPayload) -> Result<App
     if res.status().is_success() {
         res.json::<Application>().await.map_err(|e| e.to_string())
     } else {
-        let status = res.status();
-        let text = res.text().await.unwrap_or_default();
-        Err(format!("Request failed: {} - {}", status, text))
+        Err(error_message(res, "Request failed").await)
     }
 }
 
@@ -79,12 +133,8 @@ pub async fn update_application(
     id: &str,
     payload: UpdateApplicationPayload,
 ) -> Result<Application, String> {
-    let token = get_token().await.ok_or("No token found")?;
-    let client = reqwest::Client::new();
-
-    let res = client
-        .put(format!("{}/applications/{}", API_BASE_URL, id))
-        .header(AUTHORIZATION, format!("Bearer {}", token))
+    let res = authed(Method::PUT, format!("{}/applications/{}", API_BASE_URL, id))
+        .await?
         .header(CONTENT_TYPE, "application/json")
         .json(&payload)
         .send()
@@ -94,34 +144,32 @@ pub async fn update_application(
     if res.status().is_success() {
         res.json::<Application>().await.map_err(|e| e.to_string())
     } else {
-        Err(format!("Request failed: {}", res.status()))
+        Err(error_message(res, "Request failed").await)
     }
 }
 
 pub async fn delete_application(id: &str) -> Result<(), String> {
-    let token = get_token().await.ok_or("Not authenticated")?;
-
-    let client = reqwest::Client::new();
-    let response = client
-        .delete(format!("{}/applications/{}", API_BASE_URL, id))
-        .header(AUTHORIZATION, format!("Bearer {}", token))
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+    let response = authed(
+        Method::DELETE,
+        format!("{}/applications/{}", API_BASE_URL, id),
+    )
+    .await?
+    .send()
+    .await
+    .map_err(|e| e.to_string())?;
 
     if response.status().is_success() {
         Ok(())
     } else {
-        Err(format!("Failed to delete: {}", response.status()))
+        Err(error_message(response, "Failed to delete").await)
     }
 }
 
 pub async fn fetch_company_intel(
     url: &str,
 ) -> Result<crate::models::application::CompanyIntel, String> {
-    let client = reqwest::Client::new();
-    let response = client
-        .get(format!("{}/intel", API_BASE_URL))
+    let response = authed(Method::GET, format!("{}/intel", API_BASE_URL))
+        .await?
         .query(&[("url", url)])
         .send()
         .await
@@ -133,49 +181,56 @@ pub async fn fetch_company_intel(
             .await
             .map_err(|e| e.to_string())
     } else {
-        Err(format!("Failed to fetch intel: {}", response.status()))
+        Err(error_message(response, "Failed to fetch intel").await)
     }
 }
 
-pub async fn upload_file(file_data: Vec<u8>, file_name: String) -> Result<String, String> {
-    let token = get_token().await.ok_or("No token found")?;
-    let client = reqwest::Client::new();
-
+pub async fn upload_file(
+    file_data: Vec<u8>,
+    file_name: String,
+) -> Result<crate::models::application::UploadedFile, String> {
     // Create multipart form
     let part = reqwest::multipart::Part::bytes(file_data).file_name(file_name);
     let form = reqwest::multipart::Form::new().part("file", part);
 
-    let res = client
-        .post(format!("{}/upload", API_BASE_URL))
-        .header(AUTHORIZATION, format!("Bearer {}", token))
+    let res = authed(Method::POST, format!("{}/upload", API_BASE_URL))
+        .await?
         .multipart(form)
         .send()
         .await
         .map_err(|e| e.to_string())?;
 
     if res.status().is_success() {
-        let json: serde_json::Value = res.json().await.map_err(|e| e.to_string())?;
-        Ok(json["url"].as_str().unwrap_or("").to_string())
+        res.json::<crate::models::application::UploadedFile>()
+            .await
+            .map_err(|e| e.to_string())
     } else {
-        let status = res.status();
-        let error_text = res.text().await.unwrap_or_default();
-        // Try to parse as JSON error response
-        if let Ok(json) = serde_json::from_str::<serde_json::Value>(&error_text) {
-            if let Some(msg) = json["error"].as_str() {
-                return Err(msg.to_string());
-            }
-        }
-        Err(format!("Upload failed ({}): {}", status, error_text))
+        Err(error_message(res, "Upload failed").await)
     }
 }
 
-pub async fn get_application(id: &str) -> Result<Application, String> {
-    let token = get_token().await.ok_or("No token found")?;
-    let client = reqwest::Client::new();
+pub async fn search_documents(
+    query: &str,
+) -> Result<Vec<crate::models::application::DocumentSearchHit>, String> {
+    let res = authed(Method::GET, format!("{}/documents/search", API_BASE_URL))
+        .await?
+        .query(&[("q", query)])
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
 
-    let res = client
-        .get(format!("{}/applications/{}", API_BASE_URL, id))
-        .header(AUTHORIZATION, format!("Bearer {}", token))
+    if res.status().is_success() {
+        res.json::<Vec<crate::models::application::DocumentSearchHit>>()
+            .await
+            .map_err(|e| e.to_string())
+    } else {
+        Err(error_message(res, "Request failed").await)
+    }
+}
+
+pub async fn get_application(id: &str) -> Result<Application, String> {
+    let res = authed(Method::GET, format!("{}/applications/{}", API_BASE_URL, id))
+        .await?
         .send()
         .await
         .map_err(|e| e.to_string())?;
@@ -183,7 +238,7 @@ pub async fn get_application(id: &str) -> Result<Application, String> {
     if res.status().is_success() {
         res.json::<Application>().await.map_err(|e| e.to_string())
     } else {
-        Err(format!("Request failed: {}", res.status()))
+        Err(error_message(res, "Request failed").await)
     }
 }
 
@@ -202,7 +257,7 @@ pub async fn get_public_applications(
             .await
             .map_err(|e| e.to_string())
     } else {
-        Err(format!("Request failed: {}", res.status()))
+        Err(error_message(res, "Request failed").await)
     }
 }
 
@@ -222,7 +277,7 @@ pub async fn get_public_application_detail(
             .await
             .map_err(|e| e.to_string())
     } else {
-        Err(format!("Request failed: {}", res.status()))
+        Err(error_message(res, "Request failed").await)
     }
 }
 
@@ -245,7 +300,7 @@ pub async fn get_comments(
             .await
             .map_err(|e| e.to_string())
     } else {
-        Err(format!("Request failed: {}", res.status()))
+        Err(error_message(res, "Request failed").await)
     }
 }
 
@@ -271,18 +326,14 @@ pub async fn create_comment(
             .await
             .map_err(|e| e.to_string())
     } else {
-        Err(format!("Request failed: {}", res.status()))
+        Err(error_message(res, "Request failed").await)
     }
 }
 
 pub async fn get_recent_comments(
 ) -> Result<Vec<crate::models::application::CommentWithContext>, String> {
-    let token = get_token().await.ok_or("No token found")?;
-    let client = reqwest::Client::new();
-
-    let res = client
-        .get(format!("{}/comments/recent", API_BASE_URL))
-        .header(AUTHORIZATION, format!("Bearer {}", token))
+    let res = authed(Method::GET, format!("{}/comments/recent", API_BASE_URL))
+        .await?
         .send()
         .await
         .map_err(|e| e.to_string())?;
@@ -292,17 +343,131 @@ pub async fn get_recent_comments(
             .await
             .map_err(|e| e.to_string())
     } else {
-        Err(format!("Request failed: {}", res.status()))
+        Err(error_message(res, "Request failed").await)
     }
 }
 
-pub async fn get_dashboard_stats() -> Result<crate::models::application::DashboardStats, String> {
-    let token = get_token().await.ok_or("No token found")?;
-    let client = reqwest::Client::new();
+/// Comments on the owner's applications in one state; `pending` is the moderation queue.
+pub async fn list_moderated_comments(
+    status: &str,
+) -> Result<Vec<crate::models::application::CommentWithContext>, String> {
+    let res = authed(
+        Method::GET,
+        format!("{}/comments?status={}", API_BASE_URL, status),
+    )
+    .await?
+    .send()
+    .await
+    .map_err(|e| e.to_string())?;
 
-    let res = client
-        .get(format!("{}/applications/stats", API_BASE_URL))
-        .header(reqwest::header::AUTHORIZATION, format!("Bearer {}", token))
+    if res.status().is_success() {
+        res.json::<Vec<crate::models::application::CommentWithContext>>()
+            .await
+            .map_err(|e| e.to_string())
+    } else {
+        Err(error_message(res, "Failed to load comments").await)
+    }
+}
+
+/// `action` is "approve" or "hide".
+pub async fn moderate_comment(id: &str, action: &str) -> Result<(), String> {
+    let res = authed(
+        Method::POST,
+        format!("{}/comments/{}/{}", API_BASE_URL, id, action),
+    )
+    .await?
+    .send()
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        Ok(())
+    } else {
+        Err(error_message(res, "Failed to update comment").await)
+    }
+}
+
+/// Posts a verified owner reply in the comment's thread.
+pub async fn reply_to_comment(
+    id: &str,
+    content: &str,
+) -> Result<crate::models::application::Comment, String> {
+    let res = authed(
+        Method::POST,
+        format!("{}/comments/{}/reply", API_BASE_URL, id),
+    )
+    .await?
+    .json(&crate::models::application::OwnerReply {
+        content: content.to_string(),
+    })
+    .send()
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        res.json::<crate::models::application::Comment>()
+            .await
+            .map_err(|e| e.to_string())
+    } else {
+        Err(error_message(res, "Failed to send reply").await)
+    }
+}
+
+pub async fn delete_comment(id: &str) -> Result<(), String> {
+    let res = authed(Method::DELETE, format!("{}/comments/{}", API_BASE_URL, id))
+        .await?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        Ok(())
+    } else {
+        Err(error_message(res, "Failed to delete comment").await)
+    }
+}
+
+pub async fn get_moderation_settings(
+) -> Result<crate::models::application::ModerationSettings, String> {
+    let res = authed(Method::GET, format!("{}/comments/settings", API_BASE_URL))
+        .await?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        res.json::<crate::models::application::ModerationSettings>()
+            .await
+            .map_err(|e| e.to_string())
+    } else {
+        Err(error_message(res, "Request failed").await)
+    }
+}
+
+pub async fn update_moderation_settings(
+    mode: &str,
+) -> Result<crate::models::application::ModerationSettings, String> {
+    let res = authed(Method::PUT, format!("{}/comments/settings", API_BASE_URL))
+        .await?
+        .json(&crate::models::application::ModerationSettings {
+            mode: mode.to_string(),
+        })
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        res.json::<crate::models::application::ModerationSettings>()
+            .await
+            .map_err(|e| e.to_string())
+    } else {
+        Err(error_message(res, "Failed to save settings").await)
+    }
+}
+
+pub async fn get_dashboard_stats() -> Result<crate::models::application::DashboardStats, String> {
+    let res = authed(Method::GET, format!("{}/applications/stats", API_BASE_URL))
+        .await?
         .send()
         .await
         .map_err(|e| e.to_string())?;
@@ -312,7 +477,7 @@ pub async fn get_dashboard_stats() -> Result<crate::models::application::Dashboa
             .await
             .map_err(|e| e.to_string())
     } else {
-        Err(format!("Request failed: {}", res.status()))
+        Err(error_message(res, "Request failed").await)
     }
 }
 
@@ -329,6 +494,6 @@ pub async fn record_visit() -> Result<crate::models::visitor::VisitResponse, Str
             .await
             .map_err(|e| e.to_string())
     } else {
-        Err(format!("Request failed: {}", res.status()))
+        Err(error_message(res, "Request failed").await)
     }
 }
@@ -0,0 +1,382 @@
+use crate::models::account::{AccountInfo, CookieSession};
+use crate::models::api_token::{ApiToken, CreateApiTokenPayload, CreatedApiToken};
+use crate::models::invite::{CreateInvitePayload, CreatedInvite, Invite};
+use crate::models::outbound_email::OutboundEmail;
+use crate::models::session::SessionInfo;
+use crate::models::two_factor::{RecoveryCodes, TwoFactorSetup, TwoFactorStatus};
+use crate::services::application_service::{authed, error_message, API_BASE_URL};
+use dioxus::prelude::*;
+use reqwest::Method;
+
+/// The tokens themselves are HttpOnly cookies; scripts only keep the CSRF token and
+/// the access cookie's expiry so requests know when to refresh.
+pub fn store_session(csrf_token: &str, expires_in: i64) {
+    let _ = document::eval(&format!(
+        "localStorage.setItem('admin_csrf', '{}'); localStorage.setItem('admin_session_expires', String(Date.now() + {} * 1000));",
+        csrf_token, expires_in
+    ));
+}
+
+pub async fn clear_session() {
+    let _ = document::eval(
+        "localStorage.removeItem('admin_csrf'); localStorage.removeItem('admin_session_expires');",
+    )
+    .await;
+}
+
+/// Revokes the current session on the server, which also clears the cookies.
+pub async fn logout() {
+    if let Ok(req) = authed(Method::POST, format!("{}/auth/logout", API_BASE_URL)).await {
+        let _ = req.send().await;
+    }
+    clear_session().await;
+}
+
+pub async fn list_sessions() -> Result<Vec<SessionInfo>, String> {
+    let res = authed(Method::GET, format!("{}/auth/sessions", API_BASE_URL))
+        .await?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        res.json::<Vec<SessionInfo>>()
+            .await
+            .map_err(|e| e.to_string())
+    } else {
+        Err(error_message(res, "Failed to load sessions").await)
+    }
+}
+
+pub async fn revoke_session(id: &str) -> Result<(), String> {
+    let res = authed(
+        Method::DELETE,
+        format!("{}/auth/sessions/{}", API_BASE_URL, id),
+    )
+    .await?
+    .send()
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        Ok(())
+    } else {
+        Err(error_message(res, "Failed to revoke session").await)
+    }
+}
+
+pub async fn revoke_all_sessions() -> Result<(), String> {
+    let res = authed(Method::DELETE, format!("{}/auth/sessions", API_BASE_URL))
+        .await?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        Ok(())
+    } else {
+        Err(error_message(res, "Failed to revoke sessions").await)
+    }
+}
+
+pub async fn get_two_factor_status() -> Result<TwoFactorStatus, String> {
+    let res = authed(Method::GET, format!("{}/auth/2fa", API_BASE_URL))
+        .await?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        res.json::<TwoFactorStatus>()
+            .await
+            .map_err(|e| e.to_string())
+    } else {
+        Err(error_message(res, "Failed to load 2FA status").await)
+    }
+}
+
+pub async fn start_two_factor_setup() -> Result<TwoFactorSetup, String> {
+    let res = authed(Method::POST, format!("{}/auth/2fa/setup", API_BASE_URL))
+        .await?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        res.json::<TwoFactorSetup>()
+            .await
+            .map_err(|e| e.to_string())
+    } else {
+        Err(error_message(res, "Failed to start 2FA setup").await)
+    }
+}
+
+pub async fn confirm_two_factor(code: &str) -> Result<Vec<String>, String> {
+    let res = authed(Method::POST, format!("{}/auth/2fa/confirm", API_BASE_URL))
+        .await?
+        .json(&serde_json::json!({ "code": code }))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        res.json::<RecoveryCodes>()
+            .await
+            .map(|r| r.recovery_codes)
+            .map_err(|e| e.to_string())
+    } else {
+        Err(error_message(res, "Failed to enable 2FA").await)
+    }
+}
+
+pub async fn disable_two_factor(code: &str) -> Result<(), String> {
+    let res = authed(Method::POST, format!("{}/auth/2fa/disable", API_BASE_URL))
+        .await?
+        .json(&serde_json::json!({ "code": code }))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        Ok(())
+    } else {
+        Err(error_message(res, "Failed to disable 2FA").await)
+    }
+}
+
+pub async fn list_invites() -> Result<Vec<Invite>, String> {
+    let res = authed(Method::GET, format!("{}/invites", API_BASE_URL))
+        .await?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        res.json::<Vec<Invite>>().await.map_err(|e| e.to_string())
+    } else {
+        Err(error_message(res, "Failed to load invites").await)
+    }
+}
+
+pub async fn create_invite(payload: CreateInvitePayload) -> Result<CreatedInvite, String> {
+    let res = authed(Method::POST, format!("{}/invites", API_BASE_URL))
+        .await?
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        res.json::<CreatedInvite>().await.map_err(|e| e.to_string())
+    } else {
+        Err(error_message(res, "Failed to create invite").await)
+    }
+}
+
+pub async fn delete_invite(id: &str) -> Result<(), String> {
+    let res = authed(Method::DELETE, format!("{}/invites/{}", API_BASE_URL, id))
+        .await?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        Ok(())
+    } else {
+        Err(error_message(res, "Failed to delete invite").await)
+    }
+}
+
+pub async fn list_outbound_emails(status: Option<&str>) -> Result<Vec<OutboundEmail>, String> {
+    let url = match status {
+        Some(status) => format!("{}/emails?status={}", API_BASE_URL, status),
+        None => format!("{}/emails", API_BASE_URL),
+    };
+    let res = authed(Method::GET, url)
+        .await?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        res.json::<Vec<OutboundEmail>>()
+            .await
+            .map_err(|e| e.to_string())
+    } else {
+        Err(error_message(res, "Failed to load emails").await)
+    }
+}
+
+pub async fn resend_outbound_email(id: &str) -> Result<(), String> {
+    let res = authed(
+        Method::POST,
+        format!("{}/emails/{}/resend", API_BASE_URL, id),
+    )
+    .await?
+    .send()
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        Ok(())
+    } else {
+        Err(error_message(res, "Failed to resend email").await)
+    }
+}
+
+pub async fn list_api_tokens() -> Result<Vec<ApiToken>, String> {
+    let res = authed(Method::GET, format!("{}/tokens", API_BASE_URL))
+        .await?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        res.json::<Vec<ApiToken>>().await.map_err(|e| e.to_string())
+    } else {
+        Err(error_message(res, "Failed to load tokens").await)
+    }
+}
+
+pub async fn create_api_token(payload: CreateApiTokenPayload) -> Result<CreatedApiToken, String> {
+    let res = authed(Method::POST, format!("{}/tokens", API_BASE_URL))
+        .await?
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        res.json::<CreatedApiToken>()
+            .await
+            .map_err(|e| e.to_string())
+    } else {
+        Err(error_message(res, "Failed to create token").await)
+    }
+}
+
+pub async fn revoke_api_token(id: &str) -> Result<(), String> {
+    let res = authed(Method::DELETE, format!("{}/tokens/{}", API_BASE_URL, id))
+        .await?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        Ok(())
+    } else {
+        Err(error_message(res, "Failed to revoke token").await)
+    }
+}
+
+pub async fn get_account() -> Result<AccountInfo, String> {
+    let res = authed(Method::GET, format!("{}/account", API_BASE_URL))
+        .await?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        res.json::<AccountInfo>().await.map_err(|e| e.to_string())
+    } else {
+        Err(error_message(res, "Failed to load account").await)
+    }
+}
+
+pub async fn request_account_deletion(password: &str) -> Result<AccountInfo, String> {
+    let res = authed(Method::POST, format!("{}/account/delete", API_BASE_URL))
+        .await?
+        .json(&serde_json::json!({ "password": password }))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        res.json::<AccountInfo>().await.map_err(|e| e.to_string())
+    } else {
+        Err(error_message(res, "Failed to schedule deletion").await)
+    }
+}
+
+pub async fn cancel_account_deletion() -> Result<AccountInfo, String> {
+    let res = authed(
+        Method::POST,
+        format!("{}/account/delete/cancel", API_BASE_URL),
+    )
+    .await?
+    .send()
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        res.json::<AccountInfo>().await.map_err(|e| e.to_string())
+    } else {
+        Err(error_message(res, "Failed to cancel deletion").await)
+    }
+}
+
+/// Fetches the data export and hands it to the browser as a file download.
+pub async fn download_account_export() -> Result<(), String> {
+    let res = authed(Method::GET, format!("{}/account/export", API_BASE_URL))
+        .await?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !res.status().is_success() {
+        return Err(error_message(res, "Export failed").await);
+    }
+    let body = res.text().await.map_err(|e| e.to_string())?;
+
+    let eval = document::eval(
+        r#"
+        const body = await dioxus.recv();
+        const url = URL.createObjectURL(new Blob([body], { type: 'application/json' }));
+        const a = document.createElement('a');
+        a.href = url;
+        a.download = 'oisko-toita-export.json';
+        a.click();
+        URL.revokeObjectURL(url);
+        "#,
+    );
+    eval.send(body).map_err(|e| e.to_string())
+}
+
+/// Other devices are signed out by the backend; this one continues with fresh cookies.
+pub async fn change_password(current_password: &str, new_password: &str) -> Result<(), String> {
+    let res = authed(Method::POST, format!("{}/account/password", API_BASE_URL))
+        .await?
+        .json(&serde_json::json!({
+            "current_password": current_password,
+            "new_password": new_password,
+        }))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        let session = res
+            .json::<CookieSession>()
+            .await
+            .map_err(|e| e.to_string())?;
+        store_session(&session.csrf_token, session.expires_in);
+        Ok(())
+    } else {
+        Err(error_message(res, "Failed to change password").await)
+    }
+}
+
+pub async fn change_email(new_email: &str, password: &str) -> Result<AccountInfo, String> {
+    let res = authed(Method::POST, format!("{}/account/email", API_BASE_URL))
+        .await?
+        .json(&serde_json::json!({ "new_email": new_email, "password": password }))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        res.json::<AccountInfo>().await.map_err(|e| e.to_string())
+    } else {
+        Err(error_message(res, "Failed to change email").await)
+    }
+}
@@ -0,0 +1,104 @@
+use crate::models::application::Application;
+use crate::models::contact_message::{ContactMessage, ContactReply, UpdateContactMessage};
+use crate::services::application_service::{authed, error_message, API_BASE_URL};
+use reqwest::Method;
+
+/// Without a status, every message that isn't archived.
+pub async fn list_messages(status: Option<&str>) -> Result<Vec<ContactMessage>, String> {
+    let url = match status {
+        Some(status) => format!("{}/contact/messages?status={}", API_BASE_URL, status),
+        None => format!("{}/contact/messages", API_BASE_URL),
+    };
+    let res = authed(Method::GET, url)
+        .await?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        res.json::<Vec<ContactMessage>>()
+            .await
+            .map_err(|e| e.to_string())
+    } else {
+        Err(error_message(res, "Failed to load messages").await)
+    }
+}
+
+pub async fn set_message_status(id: &str, status: &str) -> Result<ContactMessage, String> {
+    let res = authed(
+        Method::PUT,
+        format!("{}/contact/messages/{}", API_BASE_URL, id),
+    )
+    .await?
+    .json(&UpdateContactMessage {
+        status: status.to_string(),
+    })
+    .send()
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        res.json::<ContactMessage>()
+            .await
+            .map_err(|e| e.to_string())
+    } else {
+        Err(error_message(res, "Failed to update message").await)
+    }
+}
+
+pub async fn reply_to_message(id: &str, message: &str) -> Result<ContactMessage, String> {
+    let res = authed(
+        Method::POST,
+        format!("{}/contact/messages/{}/reply", API_BASE_URL, id),
+    )
+    .await?
+    .json(&ContactReply {
+        message: message.to_string(),
+    })
+    .send()
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        res.json::<ContactMessage>()
+            .await
+            .map_err(|e| e.to_string())
+    } else {
+        Err(error_message(res, "Failed to send reply").await)
+    }
+}
+
+pub async fn delete_message(id: &str) -> Result<(), String> {
+    let res = authed(
+        Method::DELETE,
+        format!("{}/contact/messages/{}", API_BASE_URL, id),
+    )
+    .await?
+    .send()
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        Ok(())
+    } else {
+        Err(error_message(res, "Failed to delete message").await)
+    }
+}
+
+/// Creates a lead application from the message and returns it.
+pub async fn create_lead(id: &str) -> Result<Application, String> {
+    let res = authed(
+        Method::POST,
+        format!("{}/contact/messages/{}/lead", API_BASE_URL, id),
+    )
+    .await?
+    .send()
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if res.status().is_success() {
+        res.json::<Application>().await.map_err(|e| e.to_string())
+    } else {
+        Err(error_message(res, "Failed to create lead").await)
+    }
+}
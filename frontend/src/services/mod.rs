@@ -1,2 +1,4 @@
 pub mod application_service;
+pub mod auth_service;
+pub mod contact_service;
 pub mod sse_service;
@@ -16,10 +16,18 @@ pub struct Notification {
 pub enum NotificationType {
     Comment,
     StatusUpdate,
+    ContactMessage,
+    CommentAwaitingReview,
 }
 
 pub static NOTIFICATIONS: GlobalSignal<Vec<Notification>> = Signal::global(|| Vec::new());
 
+/// Bumped for every new contact message so an open inbox reloads itself
+pub static INBOX_REVISION: GlobalSignal<u32> = Signal::global(|| 0);
+
+/// Bumped when a comment is held or published so the moderation queue reloads itself
+pub static MODERATION_REVISION: GlobalSignal<u32> = Signal::global(|| 0);
+
 pub fn use_sse() {
     use_effect(move || {
         let mut ev_source = document::eval(&format!(
@@ -45,17 +53,60 @@ pub fn use_sse() {
     });
 }
 
+/// Live comment activity for the public detail page, which shows no admin
+/// notifications. Holds the latest published comment or owner reply on any
+/// application; the page checks the id. The connection closes with the page.
+pub fn use_comment_events() -> Signal<Option<AppEvent>> {
+    let mut latest = use_signal(|| None::<AppEvent>);
+
+    use_effect(move || {
+        let mut ev_source = document::eval(&format!(
+            "
+                window.__commentEvents?.close();
+                window.__commentEvents = new EventSource('{}/events');
+                window.__commentEvents.onmessage = (event) => {{
+                    dioxus.send(event.data);
+                }};
+            ",
+            API_BASE_URL
+        ));
+
+        spawn(async move {
+            while let Ok(data) = ev_source.recv::<String>().await {
+                let Ok(event) = serde_json::from_str::<AppEvent>(&data) else {
+                    continue;
+                };
+                if matches!(
+                    event,
+                    AppEvent::CommentCreated { .. } | AppEvent::CommentReplied { .. }
+                ) {
+                    latest.set(Some(event));
+                }
+            }
+        });
+    });
+
+    use_drop(|| {
+        document::eval("window.__commentEvents?.close(); window.__commentEvents = null;");
+    });
+
+    latest
+}
+
 fn handle_event(event: AppEvent) {
     let (title, message, type_) = match event {
         AppEvent::CommentCreated {
             visitor_name,
             company,
             ..
-        } => (
-            "NEW SIGNAL".to_string(),
-            format!("{} commented on mission {}", visitor_name, company),
-            NotificationType::Comment,
-        ),
+        } => {
+            *MODERATION_REVISION.write() += 1;
+            (
+                "NEW SIGNAL".to_string(),
+                format!("{} commented on mission {}", visitor_name, company),
+                NotificationType::Comment,
+            )
+        }
         AppEvent::ApplicationStatusUpdated {
             company, status, ..
         } => (
@@ -63,6 +114,24 @@ fn handle_event(event: AppEvent) {
             format!("Mission {} updated to {}", company, status),
             NotificationType::StatusUpdate,
         ),
+        // The owner wrote it, so there is nothing to announce in the admin
+        AppEvent::CommentReplied { .. } => return,
+        AppEvent::CommentAwaitingReview { .. } => {
+            *MODERATION_REVISION.write() += 1;
+            (
+                "SIGNAL HELD".to_string(),
+                "New comment awaiting moderation".to_string(),
+                NotificationType::CommentAwaitingReview,
+            )
+        }
+        AppEvent::ContactMessageReceived { .. } => {
+            *INBOX_REVISION.write() += 1;
+            (
+                "INCOMING TRANSMISSION".to_string(),
+                "New message in the contact inbox".to_string(),
+                NotificationType::ContactMessage,
+            )
+        }
     };
 
     let id = Uuid::new_v4();